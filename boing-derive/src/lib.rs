@@ -0,0 +1,292 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Derive macros for [*boing*](https://crates.io/crates/boing).
+//!
+//! These macros are re-exported by *boing* with feature `derive`, and should be used through it.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input,
+    spanned::Spanned,
+    Attribute,
+    Data,
+    DeriveInput,
+    Expr,
+    Fields,
+    LitStr,
+};
+
+/// Derives `boing::settings::Settings` for a struct with named fields, or
+/// `boing::settings::Choice` and `boing::settings::Field` for an enum without fields.
+///
+/// See the documentation of `boing::settings` for the supported attributes.
+#[proc_macro_derive(Settings, attributes(settings))]
+pub fn derive_settings(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let result = match &input.data {
+        Data::Struct(_) => derive_struct(&input),
+        Data::Enum(_) => derive_enum(&input),
+        Data::Union(_) => Err(syn::Error::new(
+            input.span(),
+            "`Settings` cannot be derived for unions",
+        )),
+    };
+
+    result.unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// The options of a `#[settings(...)]` attribute.
+#[derive(Default)]
+struct Options {
+    title: Option<LitStr>,
+    label: Option<LitStr>,
+    min: Option<Expr>,
+    max: Option<Expr>,
+    style: Option<syn::Ident>,
+    skip: bool,
+}
+
+impl Options {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("settings")) {
+            attr.parse_nested_meta(|meta| {
+                let Some(ident) = meta.path.get_ident() else {
+                    return Err(meta.error("unknown `settings` option"));
+                };
+                match ident.to_string().as_str() {
+                    "title" => options.title = Some(meta.value()?.parse()?),
+                    "label" => options.label = Some(meta.value()?.parse()?),
+                    "min" => options.min = Some(meta.value()?.parse()?),
+                    "max" => options.max = Some(meta.value()?.parse()?),
+                    "skip" => options.skip = true,
+                    "slider" | "radio" | "multiline" | "password" => {
+                        if options.style.is_some() {
+                            return Err(meta.error("only one style may be given"));
+                        }
+                        options.style = Some(ident.clone());
+                    }
+                    _ => return Err(meta.error("unknown `settings` option")),
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(options)
+    }
+
+    /// Fails if any option other than those named is given.
+    fn allow_only(&self, span: proc_macro2::Span, allowed: &[&str]) -> syn::Result<()> {
+        let given = [
+            ("title", self.title.is_some()),
+            ("label", self.label.is_some()),
+            ("min", self.min.is_some()),
+            ("max", self.max.is_some()),
+            ("style", self.style.is_some()),
+            ("skip", self.skip),
+        ];
+        match given
+            .into_iter()
+            .find(|(name, is_given)| *is_given && !allowed.contains(name))
+        {
+            Some((name, _)) => Err(syn::Error::new(
+                span,
+                format!("`settings` option `{}` isn't allowed here", name),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+fn derive_struct(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        unreachable!();
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new(
+            input.span(),
+            "`Settings` can only be derived for structs with named fields",
+        ));
+    };
+
+    let options = Options::parse(&input.attrs)?;
+    options.allow_only(input.span(), &["title"])?;
+    let title = match options.title {
+        Some(title) => quote!(::core::option::Option::Some(#title)),
+        None => quote!(::core::option::Option::None),
+    };
+
+    let mut calls = Vec::new();
+    for field in &fields.named {
+        let options = Options::parse(&field.attrs)?;
+        options.allow_only(field.span(), &["label", "min", "max", "style", "skip"])?;
+        if options.skip {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let label = match options.label {
+            Some(label) => label.value(),
+            None => default_label(&ident.to_string()),
+        };
+        if let (Some(min), Some(max)) = (&options.min, &options.max) {
+            if let (Some(min_value), Some(max_value)) = (int_literal(min), int_literal(max)) {
+                if min_value > max_value {
+                    return Err(syn::Error::new(
+                        max.span(),
+                        "`settings` option `max` is less than `min`",
+                    ));
+                }
+            }
+        }
+
+        let min = optional(options.min);
+        let max = optional(options.max);
+        let style = match options.style {
+            Some(style) => match style.to_string().as_str() {
+                "slider" => quote!(Slider),
+                "radio" => quote!(Radio),
+                "multiline" => quote!(Multiline),
+                _ => quote!(Password),
+            },
+            None => quote!(Default),
+        };
+
+        calls.push(quote! {
+            builder.field::<#ty>(
+                #label,
+                &::boing::settings::Options {
+                    min: #min,
+                    max: #max,
+                    style: ::boing::settings::Style::#style,
+                },
+                |settings| &settings.#ident,
+                |settings| &mut settings.#ident,
+            )?;
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::boing::settings::Settings for #name #ty_generics #where_clause {
+            fn title() -> ::core::option::Option<&'static str> {
+                #title
+            }
+
+            fn build(
+                builder: &mut ::boing::settings::Builder<'_, '_, Self>,
+            ) -> ::core::result::Result<(), ::boing::Error> {
+                #(#calls)*
+
+                ::core::result::Result::Ok(())
+            }
+        }
+    })
+}
+
+fn derive_enum(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        unreachable!();
+    };
+    if data.variants.is_empty() {
+        return Err(syn::Error::new(
+            input.span(),
+            "`Settings` cannot be derived for enums without variants",
+        ));
+    }
+    Options::parse(&input.attrs)?.allow_only(input.span(), &[])?;
+
+    let mut labels = Vec::new();
+    let mut idents = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new(
+                variant.span(),
+                "`Settings` can only be derived for enums without fields",
+            ));
+        }
+        let options = Options::parse(&variant.attrs)?;
+        options.allow_only(variant.span(), &["label"])?;
+
+        labels.push(match options.label {
+            Some(label) => label.value(),
+            None => variant.ident.to_string(),
+        });
+        idents.push(&variant.ident);
+    }
+    let indices = 0..idents.len();
+    let first = idents[0];
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::boing::settings::Choice for #name #ty_generics #where_clause {
+            const LABELS: &'static [&'static str] = &[#(#labels),*];
+
+            fn index(&self) -> usize {
+                match self {
+                    #(Self::#idents => #indices,)*
+                }
+            }
+
+            fn from_index(index: usize) -> Self {
+                [#(Self::#idents),*].into_iter().nth(index).unwrap_or(Self::#first)
+            }
+        }
+
+        impl #impl_generics ::boing::settings::Field for #name #ty_generics #where_clause {
+            fn create_widget<'ui>(
+                ui: &'ui ::boing::Ui,
+                value: &Self,
+                options: &::boing::settings::Options,
+                changed: ::boing::settings::Notifier<'ui>,
+            ) -> ::core::result::Result<::boing::settings::Widget<'ui, Self>, ::boing::Error> {
+                ::boing::settings::create_choice_widget(ui, value, options, changed)
+            }
+        }
+    })
+}
+
+fn optional(expr: Option<Expr>) -> TokenStream2 {
+    match expr {
+        Some(expr) => quote!(::core::option::Option::Some(#expr)),
+        None => quote!(::core::option::Option::None),
+    }
+}
+
+/// The value of an integer literal, possibly negated, or `None` if the expression is anything
+/// else.
+fn int_literal(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(int),
+            ..
+        }) => int.base10_parse().ok(),
+        Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => int_literal(expr)?.checked_neg(),
+        Expr::Paren(syn::ExprParen { expr, .. }) | Expr::Group(syn::ExprGroup { expr, .. }) => {
+            int_literal(expr)
+        }
+        _ => None,
+    }
+}
+
+/// Converts a field name like `font_size` into a label like "Font size".
+fn default_label(name: &str) -> String {
+    let name = name.strip_prefix("r#").unwrap_or(name).replace('_', " ");
+    let mut chars = name.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -76,140 +76,61 @@ mod menubar {
 }
 
 mod tab {
-    pub fn create(ui: &boing::Ui) -> &mut boing::Tab {
-        let tab = ui.create_tab().unwrap();
-        let basic_controls = tab.push_new_page("Basic Controls", basic_controls::create(ui))
-            .unwrap();
-        let numbers_n_lists = tab.push_new_page("Numbers and Lists", numbers_n_lists::create(ui))
-            .unwrap();
-        let data_choosers = tab.push_new_page("Data Choosers", data_choosers::create(ui))
-            .unwrap();
+    pub fn create(ui: &boing::Ui) -> &boing::Tab {
+        static LABEL_TEXT: &str = "This is a label. Right now, labels can only span one line.";
+
+        let (tab, slider, progress_bar) = boing::layout!(ui => (tab, slider, progress_bar)
+            tab = create_tab() [
+                page("Basic Controls").margined() => create_vertical_axis() { set_padded(true) } [
+                    create_horizontal_axis() { set_padded(true) } [
+                        create_pushbutton("Button"),
+                        create_checkbox("Checkbox"),
+                    ],
+                    create_label(LABEL_TEXT),
+                    create_horizontal_separator(),
+                ],
+                page("Numbers and Lists").margined() => create_horizontal_axis() [
+                    stretchy() => create_group("Numbers") { set_margined(true) } [
+                        create_vertical_axis() [
+                            create_spinbox(0, 100),
+                            slider = create_slider(0, 100),
+                            progress_bar = create_progress_bar(),
+                            create_progress_bar() { set_as_indefinite() },
+                        ],
+                    ],
+                    stretchy() => create_group("Lists") { set_margined(true) } [
+                        create_vertical_axis() [
+                            create_combobox() {
+                                push_new_item("Combobox Item 1"),
+                                push_new_item("Combobox Item 2"),
+                                push_new_item("Combobox Item 3"),
+                                push_new_item("Combobox Item 4"),
+                            },
+                            create_radio_buttons() {
+                                push_new_item("Radio Button 1"),
+                                push_new_item("Radio Button 2"),
+                                push_new_item("Radio Button 3"),
+                                push_new_item("Radio Button 4"),
+                            },
+                        ],
+                    ],
+                ],
+                page("Data Choosers").margined() => create_horizontal_axis() { set_padded(true) } [
+                    create_vertical_axis() { set_padded(true) } [
+                        create_font_picker(),
+                        create_color_picker(),
+                    ],
+                    create_vertical_separator(),
+                ],
+            ]
+        )
+        .unwrap();
 
-        tab.set_page_margined(basic_controls, true);
-        tab.set_page_margined(numbers_n_lists, true);
-        tab.set_page_margined(data_choosers, true);
+        slider.on_changed(move |slider| {
+            let percent = slider.value().clamp(0, 100) as u8;
+            progress_bar.set_value(boing::Progress::Determinate(percent));
+        });
 
         tab
     }
-
-    mod basic_controls {
-        pub fn create(ui: &boing::Ui) -> &mut boing::Axis {
-            static LABEL_TEXT: &str = "This is a label. Right now, labels can only span one line.";
-
-            let axis = ui.create_vertical_axis().unwrap();
-            axis.set_padded(true);
-            axis.push_new_child(create_x_axis(ui), false);
-            axis.push_new_child(ui.create_label(LABEL_TEXT).unwrap(), false);
-            axis.push_new_child(ui.create_horizontal_separator().unwrap(), false);
-
-            axis
-        }
-
-        fn create_x_axis(ui: &boing::Ui) -> &mut boing::Axis {
-            let axis = ui.create_horizontal_axis().unwrap();
-            axis.set_padded(true);
-            axis.push_new_child(ui.create_pushbutton("Button").unwrap(), false);
-            axis.push_new_child(ui.create_checkbox("Checkbox").unwrap(), false);
-
-            axis
-        }
-    }
-
-    mod numbers_n_lists {
-        pub fn create(ui: &boing::Ui) -> &mut boing::Axis {
-            let axis = ui.create_horizontal_axis().unwrap();
-            axis.push_new_child(create_numbers_group(ui), true);
-            axis.push_new_child(create_lists_group(ui), true);
-
-            axis
-        }
-
-        fn create_numbers_group(ui: &boing::Ui) -> &mut boing::Group {
-            let group = ui.create_group("Numbers").unwrap();
-            group.set_margined(true);
-            group.set_child(create_numbers_axis(ui));
-
-            group
-        }
-
-        fn create_numbers_axis(ui: &boing::Ui) -> &mut boing::Axis {
-            let axis = ui.create_vertical_axis().unwrap();
-            axis.push_new_child(ui.create_spinbox(0, 100).unwrap(), false);
-
-            let slider = ui.create_slider(0, 100).unwrap();
-            let progress_bar = ui.create_progress_bar().unwrap();
-            axis.push_new_child(slider, false);
-            axis.push_new_child(progress_bar, false);
-            slider.on_changed(move |slider| {
-                progress_bar.set_value(slider.value());
-            });
-
-            axis.push_new_child(create_loading_bar(ui), false);
-
-            axis
-        }
-
-        fn create_loading_bar(ui: &boing::Ui) -> &mut boing::ProgressBar {
-            let loading_bar = ui.create_progress_bar().unwrap();
-            loading_bar.set_as_indefinite();
-
-            loading_bar
-        }
-
-        fn create_lists_group(ui: &boing::Ui) -> &mut boing::Group {
-            let group = ui.create_group("Lists").unwrap();
-            group.set_margined(true);
-            group.set_child(create_lists_axis(ui));
-
-            group
-        }
-
-        fn create_lists_axis(ui: &boing::Ui) -> &mut boing::Axis {
-            let axis = ui.create_vertical_axis().unwrap();
-            axis.push_new_child(create_combobox(ui), false);
-            axis.push_new_child(create_radio_buttons(ui), false);
-
-            axis
-        }
-
-        fn create_combobox(ui: &boing::Ui) -> &mut boing::Combobox {
-            let combobox = ui.create_combobox().unwrap();
-            combobox.push_new_item("Combobox Item 1").unwrap();
-            combobox.push_new_item("Combobox Item 2").unwrap();
-            combobox.push_new_item("Combobox Item 3").unwrap();
-            combobox.push_new_item("Combobox Item 4").unwrap();
-
-            combobox
-        }
-
-        fn create_radio_buttons(ui: &boing::Ui) -> &mut boing::RadioButtons {
-            let buttons = ui.create_radio_buttons().unwrap();
-            buttons.push_new_item("Radio Button 1").unwrap();
-            buttons.push_new_item("Radio Button 2").unwrap();
-            buttons.push_new_item("Radio Button 3").unwrap();
-            buttons.push_new_item("Radio Button 4").unwrap();
-
-            buttons
-        }
-    }
-
-    mod data_choosers {
-        pub fn create(ui: &boing::Ui) -> &mut boing::Axis {
-            let axis = ui.create_horizontal_axis().unwrap();
-            axis.set_padded(true);
-            axis.push_new_child(create_picker_axis(ui), false);
-            axis.push_new_child(ui.create_vertical_separator().unwrap(), false);
-
-            axis
-        }
-
-        fn create_picker_axis(ui: &boing::Ui) -> &mut boing::Axis {
-            let axis = ui.create_vertical_axis().unwrap();
-            axis.set_padded(true);
-            axis.push_new_child(ui.create_font_picker().unwrap(), false);
-            axis.push_new_child(ui.create_color_picker().unwrap(), false);
-
-            axis
-        }
-    }
 }
@@ -2,10 +2,49 @@
 
 //! [`Area`].
 
-use crate::prelude::*;
+use std::os::raw::c_int;
+
+use crate::{
+    draw::{native::NativeContext, DrawContext},
+    prelude::*,
+};
+
+impl Ui {
+    /// Creates a new [`Area`] that draws with, and forwards mouse events to, the given handler.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_area<'ui>(
+        &'ui self,
+        handler: impl 'ui + Handler,
+    ) -> Result<&'ui Area<'ui>, crate::Error> {
+        let raw: &mut RawHandler = self.alloc_object(RawHandler {
+            raw: uiAreaHandler {
+                Draw: Some(draw),
+                MouseEvent: Some(mouse_event),
+                MouseCrossed: Some(mouse_crossed),
+                DragBroken: Some(drag_broken),
+                KeyEvent: Some(key_event),
+            },
+            ui: self,
+            handler: Box::new(handler),
+        });
+
+        // `RawHandler` begins with a `uiAreaHandler`, so *libui-ng* may use it as one.
+        call_libui_new_fn!(
+            ui: self,
+            fn: uiNewArea(std::ptr::addr_of_mut!(*raw).cast()) -> Area,
+        )
+    }
+}
 
 def_subcontrol!(
     docs: "
+        A control that is drawn, and responds to the mouse, through a [`Handler`].
+
         # Examples
 
         ```no_run
@@ -13,5 +52,217 @@ def_subcontrol!(
         ```
     ",
     ty: Area,
+    kind: Area,
     handle: uiArea,
 );
+
+impl Area<'_> {
+    bind_fn!(
+        docs: "
+            Asks for this area to be redrawn in its entirety.
+
+            The area isn't redrawn immediately, but once control returns to the main loop.
+
+            # Examples
+
+            ```no_run
+            // TODO
+            ```
+        ",
+        self: { fn: queue_redraw_all() },
+        libui: { fn: uiAreaQueueRedrawAll() },
+    );
+}
+
+/// The drawing code of an [`Area`].
+///
+/// Handlers draw through a [`DrawContext`], so the same handler can draw into a [`Canvas`] as
+/// well.
+///
+/// [`Canvas`]: crate::draw::Canvas
+pub trait Handler {
+    /// Draws the contents of the area.
+    fn draw(&mut self, ctx: &mut dyn DrawContext, params: &DrawParams);
+
+    /// Responds to the mouse moving within the area, or to a mouse button being pressed or
+    /// released.
+    ///
+    /// This does nothing by default.
+    fn mouse_event(&mut self, _area: &Area, _event: &MouseEvent) {}
+
+    /// Responds to the mouse entering the area, or to it leaving if `has_left` is `true`.
+    ///
+    /// This does nothing by default.
+    fn mouse_crossed(&mut self, _area: &Area, _has_left: bool) {}
+
+    /// Responds to a drag being interrupted by the system, e.g., because another window was
+    /// raised. The mouse buttons held during the drag should be treated as released.
+    ///
+    /// This does nothing by default.
+    fn drag_broken(&mut self, _area: &Area) {}
+}
+
+/// The parameters of a call to [`Handler::draw`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DrawParams {
+    /// The width of the area.
+    pub area_width: f64,
+    /// The height of the area.
+    pub area_height: f64,
+    /// The left edge of the region that needs to be redrawn.
+    pub clip_x: f64,
+    /// The top edge of the region that needs to be redrawn.
+    pub clip_y: f64,
+    /// The width of the region that needs to be redrawn.
+    pub clip_width: f64,
+    /// The height of the region that needs to be redrawn.
+    pub clip_height: f64,
+}
+
+/// A mouse event, as given to [`Handler::mouse_event`].
+///
+/// Mouse buttons are numbered from 1, which is the primary button.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MouseEvent {
+    /// The horizontal position of the mouse within the area.
+    pub x: f64,
+    /// The vertical position of the mouse within the area.
+    pub y: f64,
+    /// The width of the area.
+    pub area_width: f64,
+    /// The height of the area.
+    pub area_height: f64,
+    /// The button that was pressed, if any.
+    pub down: Option<u32>,
+    /// The button that was released, if any.
+    pub up: Option<u32>,
+    /// The number of clicks in quick succession that [`MouseEvent::down`] is part of, e.g., 2 for
+    /// a double click.
+    pub count: u32,
+    pub modifiers: Modifiers,
+    /// The buttons held during the event, where bit `n - 1` is set if button `n` is held.
+    pub held: u64,
+}
+
+/// The modifier keys held during a [`MouseEvent`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    /// The Windows or Command key.
+    pub super_key: bool,
+}
+
+impl Modifiers {
+    fn from_raw(raw: uiModifiers) -> Self {
+        let is_held = |modifier| raw & (modifier as uiModifiers) != 0;
+
+        Self {
+            ctrl: is_held(uiModifierCtrl),
+            alt: is_held(uiModifierAlt),
+            shift: is_held(uiModifierShift),
+            super_key: is_held(uiModifierSuper),
+        }
+    }
+}
+
+impl MouseEvent {
+    fn from_raw(raw: &uiAreaMouseEvent) -> Self {
+        let button = |number: c_int| u32::try_from(number).ok().filter(|&number| number != 0);
+
+        Self {
+            x: raw.X,
+            y: raw.Y,
+            area_width: raw.AreaWidth,
+            area_height: raw.AreaHeight,
+            down: button(raw.Down),
+            up: button(raw.Up),
+            count: raw.Count.try_into().unwrap_or(0),
+            modifiers: Modifiers::from_raw(raw.Modifiers),
+            held: raw.Held1To64,
+        }
+    }
+}
+
+/// The memory layout of the `uiAreaHandler` given to *libui-ng* by [`Ui::create_area`].
+#[repr(C)]
+struct RawHandler<'ui> {
+    raw: uiAreaHandler,
+    ui: &'ui Ui,
+    handler: Box<dyn 'ui + Handler>,
+}
+
+impl<'ui> RawHandler<'ui> {
+    /// Reinterprets a `uiAreaHandler` created by [`Ui::create_area`] as a [`RawHandler`].
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a handler created by [`Ui::create_area`].
+    unsafe fn from_ptr<'a>(ptr: *mut uiAreaHandler) -> &'a mut Self {
+        debug_assert!(!ptr.is_null());
+
+        &mut *ptr.cast()
+    }
+
+    /// Calls the given function with the handler and a handle to the area it belongs to.
+    ///
+    /// # Safety
+    ///
+    /// `area` must point to a live area.
+    unsafe fn with_area(&mut self, area: *mut uiArea, f: impl FnOnce(&mut dyn Handler, &Area)) {
+        debug_assert!(!area.is_null());
+
+        // Like the handles given to callbacks, this handle must never destroy the area.
+        let area = std::mem::ManuallyDrop::new(Area::new(self.ui, area));
+        f(&mut *self.handler, &area);
+    }
+}
+
+unsafe extern "C" fn draw(ah: *mut uiAreaHandler, _: *mut uiArea, params: *mut uiAreaDrawParams) {
+    debug_assert!(!params.is_null());
+    let params = &*params;
+    let mut ctx = NativeContext::new(params.Context);
+
+    RawHandler::from_ptr(ah).handler.draw(
+        &mut ctx,
+        &DrawParams {
+            area_width: params.AreaWidth,
+            area_height: params.AreaHeight,
+            clip_x: params.ClipX,
+            clip_y: params.ClipY,
+            clip_width: params.ClipWidth,
+            clip_height: params.ClipHeight,
+        },
+    );
+}
+
+unsafe extern "C" fn mouse_event(
+    ah: *mut uiAreaHandler,
+    area: *mut uiArea,
+    event: *mut uiAreaMouseEvent,
+) {
+    debug_assert!(!event.is_null());
+    let event = MouseEvent::from_raw(&*event);
+
+    RawHandler::from_ptr(ah).with_area(area, |handler, area| handler.mouse_event(area, &event));
+}
+
+unsafe extern "C" fn mouse_crossed(ah: *mut uiAreaHandler, area: *mut uiArea, left: c_int) {
+    RawHandler::from_ptr(ah).with_area(area, |handler, area| {
+        handler.mouse_crossed(area, left != 0);
+    });
+}
+
+unsafe extern "C" fn drag_broken(ah: *mut uiAreaHandler, area: *mut uiArea) {
+    RawHandler::from_ptr(ah).with_area(area, |handler, area| handler.drag_broken(area));
+}
+
+unsafe extern "C" fn key_event(
+    _: *mut uiAreaHandler,
+    _: *mut uiArea,
+    _: *mut uiAreaKeyEvent,
+) -> c_int {
+    // Key events are left for *libui-ng* to handle.
+    0
+}
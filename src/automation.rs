@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Addressing controls by ID for automation.
+//!
+//! Together with the `simulate_*` methods of controls, e.g., [`Pushbutton::simulate_click`],
+//! this lets scripts drive an application as a user would. Simulated events call the same
+//! callbacks that real events do.
+//!
+//! [`Pushbutton::simulate_click`]: crate::Pushbutton::simulate_click
+
+use crate::{prelude::*, Subcontrol};
+
+impl Ui {
+    /// Assigns an ID to a control so that it may be looked up later with [`Ui::find`].
+    ///
+    /// An ID refers to at most one control, so assigning an ID that is already in use takes it
+    /// from the control it previously referred to. A control may have any number of IDs, which it
+    /// loses when it is destroyed.
+    ///
+    /// Any handle to the control may be given, including those passed to callbacks.
+    ///
+    /// # Errors
+    ///
+    /// Only controls created by the `Ui::create_*` methods of this [`Ui`] may be given IDs. Other
+    /// controls, and controls that have been destroyed, cause [`crate::Error::ForeignControl`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), boing::Error> {
+    /// use boing::{Pushbutton, Ui};
+    ///
+    /// let ui: Ui;
+    /// # ui = Ui::new()?;
+    ///
+    /// let button = ui.create_pushbutton("Save")?;
+    /// button.on_clicked(|_| println!("Saved!"));
+    /// ui.set_id("save", button)?;
+    ///
+    /// // Elsewhere, e.g., in a test script...
+    /// if let Some(button) = ui.find::<Pushbutton>("save") {
+    ///     button.simulate_click();
+    /// }
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_id(&self, id: impl Into<String>, control: &Control) -> Result<(), crate::Error> {
+        if self.registry.set_id(id.into(), control.as_ptr()) {
+            Ok(())
+        } else {
+            Err(crate::Error::ForeignControl)
+        }
+    }
+
+    /// Removes an ID assigned with [`Ui::set_id`], returning whether or not it was in use.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn remove_id(&self, id: &str) -> bool {
+        self.registry.remove_id(id)
+    }
+
+    /// The control with the given ID, or `None` if there is no such control or it is not of type
+    /// `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), boing::Error> {
+    /// use boing::{Checkbox, Ui};
+    ///
+    /// let ui: Ui;
+    /// # ui = Ui::new()?;
+    /// ui.set_id("remember-me", ui.create_checkbox("Remember me")?)?;
+    ///
+    /// let checkbox = ui.find::<Checkbox>("remember-me").unwrap();
+    /// checkbox.simulate_toggle();
+    /// assert!(checkbox.checked());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find<'ui, T: Subcontrol<'ui>>(&'ui self, id: &str) -> Option<&'ui T> {
+        self.find_control(id)?.downcast_ref()
+    }
+
+    /// The control with the given ID, of any type.
+    pub(crate) fn find_control(&self, id: &str) -> Option<&Control<'_>> {
+        self.handle_of(self.registry.find(id)?)
+    }
+
+    /// The handle to the given control, or `None` if it wasn't created by this `Ui` or has been
+    /// destroyed.
+    pub(crate) fn handle_of(&self, control: *mut uiControl) -> Option<&Control<'_>> {
+        // SAFETY: Registered handles are allocated in the arena of this `Ui`, so they live for as
+        // long as it does, and they are forgotten once their controls are destroyed. Only shared
+        // references to them are ever handed out, so this doesn't alias a mutable one.
+        self.registry
+            .handle(control)
+            .map(|control| unsafe { &*control.cast::<Control>() })
+    }
+
+    /// All IDs, along with the controls they refer to.
+    pub(crate) fn controls_with_ids(&self) -> Vec<(String, &Control<'_>)> {
+        self.registry
+            .ids()
+            .into_iter()
+            .filter_map(|(id, control)| Some((id, self.handle_of(control)?)))
+            .collect()
+    }
+}
@@ -12,7 +12,7 @@ impl Ui {
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn create_horizontal_axis<'ui>(&'ui self) -> Result<&'ui mut Axis, crate::Error> {
+    pub fn create_horizontal_axis<'ui>(&'ui self) -> Result<&'ui Axis, crate::Error> {
         call_libui_new_fn!(
             ui: self,
             fn: uiNewHorizontalBox() -> Axis,
@@ -26,7 +26,7 @@ impl Ui {
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn create_vertical_axis<'ui>(&'ui self) -> Result<&'ui mut Axis, crate::Error> {
+    pub fn create_vertical_axis<'ui>(&'ui self) -> Result<&'ui Axis, crate::Error> {
         call_libui_new_fn!(
             ui: self,
             fn: uiNewVerticalBox() -> Axis,
@@ -45,6 +45,7 @@ def_subcontrol!(
         ```
     ",
     ty: Axis,
+    kind: Axis,
     handle: uiBox,
 );
 
@@ -60,7 +61,7 @@ impl Axis<'_> {
             ```no_run
             use boing::Axis;
 
-            let axis: &mut Axis;
+            let axis: &Axis;
             # let ui = boing::Ui::new().unwrap();
             # axis = ui.create_horizontal_axis().unwrap();
             assert!(!axis.is_padded());
@@ -96,13 +97,13 @@ impl Axis<'_> {
             ```no_run
             use boing::Axis;
 
-            let x_axis: &mut Axis;
+            let x_axis: &Axis;
             # let ui = boing::Ui::new().unwrap();
             # x_axis = ui.create_horizontal_axis().unwrap();
 
             // Axis-ception!
             for _ in 0..5 {
-                let y_axis: &mut Axis;
+                let y_axis: &Axis;
                 # y_axis = ui.create_vertical_axis().unwrap();
                 x_axis.push_new_child(y_axis, false);
             }
@@ -128,15 +129,15 @@ impl Axis<'_> {
             ```no_run
             use boing::Axis;
 
-            let axis: &mut Axis;
+            let axis: &Axis;
             # let ui = boing::Ui::new().unwrap();
             # axis = ui.create_horizontal_axis().unwrap();
 
-            let mut progress_bar: &mut boing::ProgressBar;
+            let progress_bar: &boing::ProgressBar;
             # progress_bar = ui.create_progress_bar().unwrap();
             let progress_bar_idx = axis.push_new_child(progress_bar, false);
 
-            let mut button: &mut boing::Pushbutton;
+            let button: &boing::Pushbutton;
             # button = ui.create_pushbutton("").unwrap();
             let button_idx = axis.push_new_child(button, false);
 
@@ -168,11 +169,16 @@ impl Axis<'_> {
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn push_new_child(
+    pub fn push_new_child<'c>(
         &self,
-        child: &mut impl DerefMut<Target = Control>,
+        child: &impl Deref<Target = Control<'c>>,
         can_stretch: bool,
     ) -> u16 {
+        self.append(child, can_stretch)
+    }
+
+    /// Like [`Axis::push_new_child`], but for a child that may still be referred to elsewhere.
+    pub(crate) fn append(&self, child: &Control, can_stretch: bool) -> u16 {
         let index = self.child_count();
 
         child.make_child();
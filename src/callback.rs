@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! [`Callback`].
+
+use std::{
+    cell::{Cell, RefCell},
+    mem,
+    ptr,
+};
+
+use crate::{prelude::*, MenuItem, Subcontrol};
+
+/// A callback set on a widget.
+///
+/// The callback is taken out while it runs, so if it causes the event that it handles again, e.g.,
+/// by changing the value of its control or by running the event loop, it isn't called
+/// re-entrantly.
+pub(crate) struct Callback<'ui, F: ?Sized>(Cell<Option<&'ui mut F>>);
+
+impl<F: ?Sized> Default for Callback<'_, F> {
+    fn default() -> Self {
+        Self(Cell::new(None))
+    }
+}
+
+impl<'ui, F: ?Sized> Callback<'ui, F> {
+    /// Replaces the callback.
+    pub(crate) fn set(&self, f: &'ui mut F) {
+        self.0.set(Some(f));
+    }
+
+    /// Unsets the callback, returning it.
+    pub(crate) fn take(&self) -> Option<&'ui mut F> {
+        self.0.take()
+    }
+
+    /// Calls `f` with the callback unset, so that any event caused by `f` isn't reported to it.
+    pub(crate) fn mute<R>(&self, f: impl FnOnce() -> R) -> R {
+        let _running = Running {
+            slot: &self.0,
+            f: self.0.take(),
+        };
+
+        f()
+    }
+
+    /// Passes the callback to `call`, returning its result, or `None` if the callback is unset or
+    /// already running.
+    pub(crate) fn call<R>(&self, call: impl FnOnce(&mut F) -> R) -> Option<R> {
+        let mut running = Running {
+            slot: &self.0,
+            f: Some(self.0.take()?),
+        };
+
+        running.f.as_deref_mut().map(call)
+    }
+}
+
+/// Puts a running or muted callback back once it returns or panics.
+struct Running<'a, 'ui, F: ?Sized> {
+    slot: &'a Cell<Option<&'ui mut F>>,
+    f: Option<&'ui mut F>,
+}
+
+impl<F: ?Sized> Drop for Running<'_, '_, F> {
+    fn drop(&mut self) {
+        // The callback may have been replaced while it ran, in which case the replacement is kept.
+        let replacement = self.slot.take();
+        self.slot.set(replacement.or_else(|| self.f.take()));
+    }
+}
+
+/// The functions registered to be called whenever something changes, e.g., the value of a
+/// [`State`](crate::state::State).
+pub(crate) struct Observers<F: ?Sized>(RefCell<Vec<Box<F>>>);
+
+impl<F: ?Sized> Default for Observers<F> {
+    fn default() -> Self {
+        Self(RefCell::default())
+    }
+}
+
+impl<F: ?Sized> Observers<F> {
+    /// Registers an observer.
+    pub(crate) fn push(&self, f: Box<F>) {
+        self.0.borrow_mut().push(f);
+    }
+
+    /// Passes each observer in turn to `call`.
+    ///
+    /// Observers may register other observers, so the list is moved out while they are called, and
+    /// restored even if one of them panics. Those registered meanwhile are first called next time.
+    pub(crate) fn notify(&self, mut call: impl FnMut(&mut F)) {
+        let mut notifying = Notifying {
+            slot: &self.0,
+            observers: mem::take(&mut *self.0.borrow_mut()),
+        };
+        for observer in notifying.observers.iter_mut() {
+            call(observer);
+        }
+    }
+}
+
+/// Puts notified observers back, ahead of those registered while they were called.
+struct Notifying<'a, F: ?Sized> {
+    slot: &'a RefCell<Vec<Box<F>>>,
+    observers: Vec<Box<F>>,
+}
+
+impl<F: ?Sized> Drop for Notifying<'_, F> {
+    fn drop(&mut self) {
+        let mut current = self.slot.borrow_mut();
+        self.observers.append(&mut current);
+        *current = mem::take(&mut self.observers);
+    }
+}
+
+/// Logs the failure to set the text of a widget from a callback, where there is no caller to
+/// return it to.
+///
+/// Setting text can only fail if it contains a NUL byte, which text that came from a widget never
+/// does.
+pub(crate) fn warn_on_error(result: Result<(), crate::Error>) {
+    if let Err(e) = result {
+        tracing::warn!("Failed to set text: {}", e);
+    }
+}
+
+/// A widget that receives events from the user.
+pub(crate) trait Target {
+    /// The top-level control containing this widget, e.g., its window, or null if there is none.
+    fn window(&self) -> *mut uiControl;
+}
+
+impl<'ui, T: Subcontrol<'ui>> Target for T {
+    fn window(&self) -> *mut uiControl {
+        let mut control = self.as_ptr();
+        loop {
+            let parent = unsafe { uiControlParent(control) };
+            if parent.is_null() {
+                return control;
+            }
+            control = parent;
+        }
+    }
+}
+
+impl Target for MenuItem<'_> {
+    fn window(&self) -> *mut uiControl {
+        // Menu items belong to the menubars of all windows at once.
+        ptr::null_mut()
+    }
+}
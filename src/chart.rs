@@ -0,0 +1,296 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Charts drawn on an [`Area`].
+//!
+//! A [`Chart`] is an area that plots one or more [`Series`] of data as lines, bars, or scattered
+//! points, or as a histogram, complete with axes, ticks, a legend, and a tooltip for the data under
+//! the mouse. What a chart shows is described by a [`Plot`], which is an [`AreaHandler`] in its own
+//! right, so plots may also be rendered without a window through a [`Canvas`] or
+//! [`DisplayList`].
+//!
+//! Modifying the data of a chart through its methods, like [`Chart::push_point`], asks the area to
+//! redraw itself.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> Result<(), boing::Error> {
+//! use boing::{
+//!     chart::{Series, PALETTE},
+//!     Ui,
+//! };
+//!
+//! let ui = Ui::new()?;
+//! let window = ui.create_window("Weather", 640, 480, false, true)?;
+//!
+//! let chart = ui.create_line_chart()?;
+//! chart.update(|plot| {
+//!     plot.title = "Temperature".into();
+//!     plot.x_label = "Hour".into();
+//!     plot.y_label = "°C".into();
+//! });
+//! let series = chart.push_series(Series::new("Today", PALETTE[0], []));
+//! for (hour, temperature) in [(0.0, 11.5), (6.0, 9.0), (12.0, 18.5), (18.0, 16.0)] {
+//!     chart.push_point(series, (hour, temperature));
+//! }
+//!
+//! window.set_child(chart);
+//! window.show();
+//! ui.run();
+//! #
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`AreaHandler`]: crate::AreaHandler
+//! [`Canvas`]: crate::draw::Canvas
+//! [`DisplayList`]: crate::draw::DisplayList
+
+mod axis;
+mod plot;
+
+pub use plot::{Hit, Plot};
+
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+use crate::{
+    area::{DrawParams, Handler as AreaHandler, MouseEvent},
+    draw::DrawContext,
+    prelude::*,
+    Area,
+    Color,
+};
+
+/// Distinct colors for series, in the order they are conventionally assigned.
+pub const PALETTE: [Color; 8] = [
+    Color::rgb(0.306, 0.475, 0.655),
+    Color::rgb(0.949, 0.557, 0.169),
+    Color::rgb(0.882, 0.341, 0.349),
+    Color::rgb(0.463, 0.718, 0.698),
+    Color::rgb(0.349, 0.631, 0.310),
+    Color::rgb(0.929, 0.788, 0.282),
+    Color::rgb(0.690, 0.478, 0.631),
+    Color::rgb(1.000, 0.616, 0.655),
+];
+
+/// The way a [`Plot`] shows its data.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Kind {
+    /// Points are joined by lines, in order.
+    Line,
+    /// Each point is a bar rising from, or falling to, zero. The bars of different series are
+    /// placed side by side.
+    Bar,
+    /// Each point is a dot.
+    Scatter,
+    /// The x values of points are counted into evenly-sized bins spanning all series, each
+    /// weighted by its y value.
+    ///
+    /// The number of bins is clamped to between 1 and 10,000.
+    Histogram { bin_count: usize },
+}
+
+/// A named sequence of `(x, y)` points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Series {
+    /// The name shown in the legend and tooltips, or empty for none.
+    pub name: String,
+    pub color: Color,
+    /// The points of this series. Points with non-finite coordinates are ignored.
+    pub points: Vec<(f64, f64)>,
+}
+
+impl Series {
+    /// Creates a new series.
+    pub fn new(
+        name: impl Into<String>,
+        color: Color,
+        points: impl IntoIterator<Item = (f64, f64)>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            color,
+            points: points.into_iter().collect(),
+        }
+    }
+
+    /// Creates a new series of samples for a histogram, each with a weight of 1.
+    pub fn from_samples(
+        name: impl Into<String>,
+        color: Color,
+        samples: impl IntoIterator<Item = f64>,
+    ) -> Self {
+        Self::new(name, color, samples.into_iter().map(|sample| (sample, 1.0)))
+    }
+}
+
+impl Ui {
+    /// Creates a new [`Chart`] of the given plot.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_chart<'ui>(&'ui self, plot: Plot) -> Result<&'ui Chart<'ui>, crate::Error> {
+        let plot = Rc::new(RefCell::new(plot));
+        let area = self.create_area(SharedPlot(Rc::clone(&plot)))?;
+
+        Ok(self.alloc_object(Chart { area, plot }))
+    }
+
+    /// Creates a new, empty line [`Chart`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_line_chart<'ui>(&'ui self) -> Result<&'ui Chart<'ui>, crate::Error> {
+        self.create_chart(Plot::new(Kind::Line))
+    }
+
+    /// Creates a new, empty bar [`Chart`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_bar_chart<'ui>(&'ui self) -> Result<&'ui Chart<'ui>, crate::Error> {
+        self.create_chart(Plot::new(Kind::Bar))
+    }
+
+    /// Creates a new, empty scatter [`Chart`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_scatter_chart<'ui>(&'ui self) -> Result<&'ui Chart<'ui>, crate::Error> {
+        self.create_chart(Plot::new(Kind::Scatter))
+    }
+
+    /// Creates a new, empty histogram [`Chart`] with the given number of bins, which is clamped to
+    /// between 1 and 10,000.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_histogram<'ui>(
+        &'ui self,
+        bin_count: usize,
+    ) -> Result<&'ui Chart<'ui>, crate::Error> {
+        self.create_chart(Plot::new(Kind::Histogram { bin_count }))
+    }
+}
+
+/// The area handler of a [`Chart`], which shares its plot with the chart.
+struct SharedPlot(Rc<RefCell<Plot>>);
+
+impl AreaHandler for SharedPlot {
+    fn draw(&mut self, ctx: &mut dyn DrawContext, params: &DrawParams) {
+        self.0.borrow_mut().draw(ctx, params);
+    }
+
+    fn mouse_event(&mut self, area: &Area, event: &MouseEvent) {
+        self.0.borrow_mut().mouse_event(area, event);
+    }
+
+    fn mouse_crossed(&mut self, area: &Area, has_left: bool) {
+        self.0.borrow_mut().mouse_crossed(area, has_left);
+    }
+}
+
+/// An [`Area`] that draws a [`Plot`].
+///
+/// # Examples
+///
+/// ```no_run
+/// // TODO
+/// ```
+pub struct Chart<'ui> {
+    area: &'ui Area<'ui>,
+    plot: Rc<RefCell<Plot>>,
+}
+
+impl<'ui> Chart<'ui> {
+    /// The area this chart draws on.
+    pub fn area(&self) -> &Area<'ui> {
+        self.area
+    }
+
+    /// The plot drawn by this chart.
+    ///
+    /// # Panics
+    ///
+    /// This panics if called from within [`Chart::update`].
+    pub fn plot(&self) -> Ref<'_, Plot> {
+        self.plot.borrow()
+    }
+
+    /// Modifies the plot drawn by this chart, then asks for the chart to be redrawn.
+    ///
+    /// # Panics
+    ///
+    /// This panics if called from within another call to [`Chart::update`].
+    pub fn update<T>(&self, f: impl FnOnce(&mut Plot) -> T) -> T {
+        let result = f(&mut self.plot.borrow_mut());
+        self.area.queue_redraw_all();
+
+        result
+    }
+
+    /// Appends a series, returning its index.
+    pub fn push_series(&self, series: Series) -> usize {
+        self.update(|plot| {
+            plot.series.push(series);
+
+            plot.series.len() - 1
+        })
+    }
+
+    /// Removes and returns the series at the given index, if any.
+    ///
+    /// Series after it move down by one index.
+    pub fn remove_series(&self, index: usize) -> Option<Series> {
+        self.update(|plot| (index < plot.series.len()).then(|| plot.series.remove(index)))
+    }
+
+    /// Replaces the points of the series at the given index.
+    ///
+    /// # Panics
+    ///
+    /// This panics if there is no series at `index`.
+    pub fn set_points(&self, index: usize, points: impl IntoIterator<Item = (f64, f64)>) {
+        self.update(|plot| plot.series[index].points = points.into_iter().collect());
+    }
+
+    /// Appends a point to the series at the given index.
+    ///
+    /// # Panics
+    ///
+    /// This panics if there is no series at `index`.
+    pub fn push_point(&self, index: usize, point: (f64, f64)) {
+        self.update(|plot| plot.series[index].points.push(point));
+    }
+
+    /// Removes all series.
+    pub fn clear(&self) {
+        self.update(|plot| plot.series.clear());
+    }
+}
+
+impl<'ui> std::ops::Deref for Chart<'ui> {
+    type Target = Control<'ui>;
+
+    fn deref(&self) -> &Self::Target {
+        self.area
+    }
+}
@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The scales and ticks of chart axes.
+
+/// The range of data shown along an axis, and the labeled ticks along it.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Axis {
+    pub min: f64,
+    pub max: f64,
+    /// The positions of ticks, in data units, and their labels.
+    pub ticks: Vec<(f64, String)>,
+}
+
+impl Axis {
+    /// Creates an axis spanning at least `min..=max` with at most about `max_ticks` ticks.
+    ///
+    /// Ticks are spaced 1, 2, or 5 times a power of ten apart. If `is_extended`, the axis is
+    /// widened to begin and end on a tick; otherwise, it spans exactly `min..=max`.
+    pub fn linear(min: f64, max: f64, max_ticks: usize, is_extended: bool) -> Self {
+        let (min, max) = widen(min, max);
+        let step = nice_step((max - min) / (max_ticks.max(1) as f64));
+        let (min, max) = if is_extended {
+            ((min / step).floor() * step, (max / step).ceil() * step)
+        } else {
+            (min, max)
+        };
+
+        let first = (min / step - 1e-9).ceil() as i64;
+        let last = (max / step + 1e-9).floor() as i64;
+        let ticks = (first..=last)
+            .map(|i| {
+                let value = (i as f64) * step;
+                (value, format_tick(value, step))
+            })
+            .collect();
+
+        Self { min, max, ticks }
+    }
+
+    /// Creates an axis with one tick per category, where category `i` is centered on `i`.
+    pub fn categorical(categories: &[String]) -> Self {
+        let count = categories.len().max(1);
+
+        Self {
+            min: -0.5,
+            max: (count as f64) - 0.5,
+            ticks: (0..count)
+                .map(|i| (i as f64, categories.get(i).cloned().unwrap_or_default()))
+                .collect(),
+        }
+    }
+
+    /// The fraction of the way along this axis that the given value lies.
+    pub fn fraction(&self, value: f64) -> f64 {
+        (value - self.min) / (self.max - self.min)
+    }
+}
+
+/// Widens an empty or invalid range so that it may be divided into ticks.
+fn widen(min: f64, max: f64) -> (f64, f64) {
+    if !(min.is_finite() && max.is_finite()) || min > max {
+        return (0.0, 1.0);
+    }
+    if max - min <= f64::EPSILON * min.abs().max(max.abs()).max(1.0) {
+        let margin = if min == 0.0 { 1.0 } else { min.abs() / 10.0 };
+        return (min - margin, max + margin);
+    }
+
+    (min, max)
+}
+
+/// The smallest step of 1, 2, or 5 times a power of ten that is at least `raw`.
+fn nice_step(raw: f64) -> f64 {
+    let magnitude = 10f64.powf(raw.log10().floor());
+
+    [1.0, 2.0, 5.0]
+        .into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|&step| step >= raw * (1.0 - 1e-9))
+        .unwrap_or(10.0 * magnitude)
+}
+
+/// Formats a tick value with as many decimal places as its step calls for.
+fn format_tick(value: f64, step: f64) -> String {
+    let decimals = (-(step.log10() + 1e-9).floor()).max(0.0) as usize;
+    // Avoid printing "-0".
+    let value = if value.abs() < step * 1e-9 { 0.0 } else { value };
+
+    format!("{:.*}", decimals, value)
+}
+
+/// Formats a data value for a tooltip, with at most four decimal places.
+pub(super) fn format_value(value: f64) -> String {
+    let text = format!("{:.4}", value);
+    let text = if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &text
+    };
+
+    if text == "-0" {
+        "0".into()
+    } else {
+        text.into()
+    }
+}
@@ -0,0 +1,847 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! [`Plot`].
+
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+use super::{
+    axis::{format_value, Axis},
+    Kind,
+    Series,
+};
+use crate::{
+    area::{DrawParams, Handler as AreaHandler, MouseEvent},
+    draw::{
+        Arc,
+        Brush,
+        DrawContext,
+        FillMode,
+        LineCap,
+        LineJoin,
+        Matrix,
+        Path,
+        StrokeParams,
+        TextAlign,
+        TextLayout,
+    },
+    font::StretchKind,
+    Area,
+    Color,
+    Font,
+};
+
+/// The space between the edge of the area and its contents, and between parts of the chart.
+const PADDING: f64 = 8.0;
+
+/// The length of tick marks.
+const TICK_LENGTH: f64 = 4.0;
+
+/// The distance from a point within which the mouse is considered to hover over it.
+const HOVER_RADIUS: f64 = 10.0;
+
+/// The radius of the dots of scatter charts.
+const DOT_RADIUS: f64 = 3.5;
+
+/// The share of the distance between bars at neighboring x values that bars fill.
+const BAR_FILL: f64 = 0.8;
+
+/// The most bins a histogram is divided into, beyond which bins would be narrower than a pixel
+/// on any display.
+const MAX_BIN_COUNT: usize = 10_000;
+
+const AXIS_COLOR: Color = Color::rgb(0.3, 0.3, 0.3);
+const GRID_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
+const TEXT_COLOR: Color = Color::rgb(0.15, 0.15, 0.15);
+
+/// The data and appearance of a chart.
+///
+/// A plot draws itself as an [`AreaHandler`], so it can be rendered without a [`Chart`], e.g.,
+/// into a [`Canvas`] or a [`DisplayList`]. Hovering is tracked by [`Plot::hover`], which the area
+/// handler implementation calls as the mouse moves.
+///
+/// # Examples
+///
+/// ```no_run
+/// use boing::{
+///     chart::{Kind, Plot, Series, PALETTE},
+///     draw::DisplayList,
+/// };
+///
+/// let mut plot = Plot::new(Kind::Scatter);
+/// plot.title = "Height vs. weight".into();
+/// plot.series.push(Series::new(
+///     "Patients",
+///     PALETTE[0],
+///     [(170.0, 65.0), (182.0, 80.0), (158.0, 52.0)],
+/// ));
+///
+/// let mut list = DisplayList::new(400.0, 300.0);
+/// list.render(&mut plot);
+/// println!("{}", list.to_svg());
+/// ```
+///
+/// [`Chart`]: super::Chart
+/// [`Canvas`]: crate::draw::Canvas
+/// [`DisplayList`]: crate::draw::DisplayList
+#[derive(Clone, Debug)]
+pub struct Plot {
+    pub kind: Kind,
+    /// The title shown above the chart, or empty for none.
+    pub title: String,
+    /// The label of the x-axis, or empty for none.
+    pub x_label: String,
+    /// The label of the y-axis, or empty for none.
+    pub y_label: String,
+    /// The labels of the x values `0`, `1`, `2`, and so on of a bar chart.
+    ///
+    /// When nonempty, these replace the numeric x-axis of a bar chart, and points whose x values
+    /// don't round to a category are ignored. Other kinds of charts ignore them.
+    pub categories: Vec<String>,
+    pub series: Vec<Series>,
+    /// The font of all text, which is enlarged and emboldened for the title.
+    pub font: Font,
+    /// Whether or not to list the named series in a legend.
+    pub shows_legend: bool,
+    /// Whether or not to draw grid lines at ticks.
+    pub shows_grid: bool,
+    /// The position of the mouse, if it is over the area.
+    cursor: Option<(f64, f64)>,
+    /// The data the mouse is over.
+    hit: Option<Hit>,
+    /// The layout of the last drawing, which hit testing is done against.
+    frame: Option<Frame>,
+}
+
+/// The data that the mouse is over, as returned by [`Plot::hovered`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Hit {
+    /// The index of the series in [`Plot::series`].
+    pub series: usize,
+    /// The index of the point in [`Series::points`], or, for histograms, the index of the bin.
+    pub index: usize,
+}
+
+/// Where the parts of a chart are placed within its area.
+#[derive(Clone, Debug, PartialEq)]
+struct Frame {
+    left: f64,
+    top: f64,
+    right: f64,
+    bottom: f64,
+    x: Axis,
+    y: Axis,
+}
+
+impl Frame {
+    fn x_to_px(&self, x: f64) -> f64 {
+        self.left + self.x.fraction(x) * (self.right - self.left)
+    }
+
+    fn y_to_px(&self, y: f64) -> f64 {
+        self.bottom - self.y.fraction(y) * (self.bottom - self.top)
+    }
+
+    fn contains(&self, (x, y): (f64, f64)) -> bool {
+        (self.left..=self.right).contains(&x) && (self.top..=self.bottom).contains(&y)
+    }
+}
+
+/// The bins of a histogram.
+struct Bins {
+    min: f64,
+    width: f64,
+    /// The total weight of the samples of each series that fall into each bin.
+    counts: Vec<Vec<f64>>,
+}
+
+impl Bins {
+    fn bounds(&self, index: usize) -> (f64, f64) {
+        let start = self.min + (index as f64) * self.width;
+
+        (start, start + self.width)
+    }
+}
+
+/// A rectangle, as `(x, y, width, height)`.
+type Rect = (f64, f64, f64, f64);
+
+impl Plot {
+    /// Creates a new, empty plot.
+    pub fn new(kind: Kind) -> Self {
+        Self {
+            kind,
+            title: String::new(),
+            x_label: String::new(),
+            y_label: String::new(),
+            categories: Vec::new(),
+            series: Vec::new(),
+            font: Font {
+                family: "sans-serif".into(),
+                size: 11.0,
+                weight: 400,
+                italic_kind: None,
+                stretch: StretchKind::Normal,
+            },
+            shows_legend: true,
+            shows_grid: true,
+            cursor: None,
+            hit: None,
+            frame: None,
+        }
+    }
+
+    /// The data that the mouse is over, if any.
+    pub fn hovered(&self) -> Option<Hit> {
+        self.hit
+    }
+
+    /// Informs this plot that the mouse moved to the given position.
+    ///
+    /// Positions are hit-tested against the layout of the last drawing. This returns `true` if the
+    /// hovered data changed, and so the plot needs to be redrawn.
+    pub fn hover(&mut self, x: f64, y: f64) -> bool {
+        self.cursor = Some((x, y));
+        let hit = self
+            .frame
+            .as_ref()
+            .and_then(|frame| self.hit_test(frame, (x, y)));
+
+        std::mem::replace(&mut self.hit, hit) != hit
+    }
+
+    /// Informs this plot that the mouse left it, returning `true` if data was hovered before.
+    pub fn unhover(&mut self) -> bool {
+        self.cursor = None;
+
+        self.hit.take().is_some()
+    }
+
+    /// The finite points of the series at the given index, excluding those outside the
+    /// categories of a bar chart.
+    fn points(&self, series: usize) -> impl Iterator<Item = (usize, (f64, f64))> + '_ {
+        self.series[series]
+            .points
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, (x, y))| x.is_finite() && y.is_finite() && self.is_in_categories(*x))
+    }
+
+    /// Determines if an x value rounds to a category, which it always does unless this is a bar
+    /// chart with categories.
+    fn is_in_categories(&self, x: f64) -> bool {
+        self.kind != Kind::Bar
+            || self.categories.is_empty()
+            || (-0.5..(self.categories.len() as f64) - 0.5).contains(&x)
+    }
+
+    fn all_points(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        (0..self.series.len()).flat_map(|i| self.points(i).map(|(_, point)| point))
+    }
+
+    /// The smallest and largest of the given values, if any.
+    fn bounds(values: impl Iterator<Item = f64>) -> Option<(f64, f64)> {
+        values.fold(None, |bounds, value| match bounds {
+            None => Some((value, value)),
+            Some((min, max)) => Some((value.min(min), value.max(max))),
+        })
+    }
+
+    /// The distance between the closest distinct x values of all series, or 1 if there are fewer
+    /// than two.
+    fn x_spacing(&self) -> f64 {
+        let mut xs: Vec<f64> = self.all_points().map(|(x, _)| x).collect();
+        xs.sort_by(f64::total_cmp);
+        xs.dedup();
+
+        xs.windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .fold(None, |min: Option<f64>, gap| Some(min.map_or(gap, |min| min.min(gap))))
+            .unwrap_or(1.0)
+    }
+
+    fn bins(&self) -> Option<Bins> {
+        let bin_count = match self.kind {
+            Kind::Histogram { bin_count } => bin_count.clamp(1, MAX_BIN_COUNT),
+            _ => return None,
+        };
+        let (mut min, mut max) = Self::bounds(self.all_points().map(|(x, _)| x))?;
+        if max <= min {
+            min -= 0.5;
+            max += 0.5;
+        }
+        let width = (max - min) / (bin_count as f64);
+
+        let counts = (0..self.series.len())
+            .map(|series| {
+                let mut counts = vec![0.0; bin_count];
+                for (_, (x, weight)) in self.points(series) {
+                    // The last bin includes its upper bound.
+                    let bin = (((x - min) / width) as usize).min(bin_count - 1);
+                    counts[bin] += weight;
+                }
+
+                counts
+            })
+            .collect();
+
+        Some(Bins { min, width, counts })
+    }
+
+    /// The rectangle, in data units, of the bar for a point of a bar chart.
+    fn bar_bounds(&self, series: usize, (x, y): (f64, f64), spacing: f64) -> Rect {
+        let group_width = BAR_FILL * spacing;
+        let bar_width = group_width / (self.series.len().max(1) as f64);
+        let start = x - group_width / 2.0 + (series as f64) * bar_width;
+
+        (start, y.min(0.0), bar_width, y.abs())
+    }
+
+    /// Converts a rectangle in data units to pixels.
+    fn rect_to_px(frame: &Frame, (x, y, width, height): Rect) -> Rect {
+        let (left, right) = (frame.x_to_px(x), frame.x_to_px(x + width));
+        let (top, bottom) = (frame.y_to_px(y + height), frame.y_to_px(y));
+
+        (left, top, right - left, bottom - top)
+    }
+
+    /// The data ranges of the x- and y-axes, before ticks are chosen.
+    fn data_ranges(&self) -> ((f64, f64), (f64, f64)) {
+        let unit = ((0.0, 1.0), (0.0, 1.0));
+
+        match self.kind {
+            Kind::Line | Kind::Scatter => {
+                let x = Self::bounds(self.all_points().map(|(x, _)| x));
+                let y = Self::bounds(self.all_points().map(|(_, y)| y));
+
+                x.zip(y).unwrap_or(unit)
+            }
+            Kind::Bar => {
+                let half = self.x_spacing() / 2.0;
+                let x = Self::bounds(self.all_points().map(|(x, _)| x))
+                    .map_or((-half, half), |(min, max)| (min - half, max + half));
+                let y = Self::bounds(self.all_points().map(|(_, y)| y).chain([0.0]));
+
+                (x, y.unwrap_or((0.0, 1.0)))
+            }
+            Kind::Histogram { .. } => match self.bins() {
+                Some(bins) => {
+                    let bin_count = bins.counts.first().map_or(0, Vec::len);
+                    let top = bins.counts.iter().flatten().copied().fold(0.0, f64::max);
+
+                    ((bins.bounds(0).0, bins.bounds(bin_count - 1).1), (0.0, top))
+                }
+                None => unit,
+            },
+        }
+    }
+
+    fn text(&self, text: impl Into<String>) -> TextLayout {
+        TextLayout {
+            text: text.into(),
+            font: self.font.clone(),
+            color: TEXT_COLOR,
+            width: None,
+            align: TextAlign::Left,
+        }
+    }
+
+    /// Draws this plot in an area of the given size.
+    fn render(&mut self, ctx: &mut dyn DrawContext, width: f64, height: f64) {
+        fill_rect(ctx, (0.0, 0.0, width, height), Color::WHITE);
+
+        let title = (!self.title.is_empty()).then(|| TextLayout {
+            font: Font {
+                size: self.font.size * 1.2,
+                weight: 700,
+                ..self.font.clone()
+            },
+            ..self.text(self.title.as_str())
+        });
+        let x_label = (!self.x_label.is_empty()).then(|| self.text(self.x_label.as_str()));
+        let y_label = (!self.y_label.is_empty()).then(|| self.text(self.y_label.as_str()));
+        let line_height = ctx.measure_text(&self.text("0")).1;
+
+        let mut top = PADDING;
+        if let Some(ref title) = title {
+            let (title_width, title_height) = ctx.measure_text(title);
+            ctx.draw_text(title, (width - title_width) / 2.0, top);
+            top += title_height + PADDING / 2.0;
+        }
+        let mut bottom = height - PADDING;
+        if let Some(ref x_label) = x_label {
+            bottom -= ctx.measure_text(x_label).1 + PADDING / 2.0;
+        }
+        bottom -= line_height + TICK_LENGTH + 2.0;
+        let mut left = PADDING;
+        if let Some(ref y_label) = y_label {
+            left += ctx.measure_text(y_label).1 + PADDING / 2.0;
+        }
+        let right = width - PADDING;
+
+        let ((x_min, x_max), (y_min, y_max)) = self.data_ranges();
+        let y_tick_count = ((bottom - top) / 40.0).floor().max(2.0) as usize;
+        let y = match self.kind {
+            Kind::Line | Kind::Scatter => Axis::linear(y_min, y_max, y_tick_count, true),
+            // Bars grow from zero, so the axis must not be widened below it.
+            Kind::Bar | Kind::Histogram { .. } => {
+                let mut axis = Axis::linear(y_min, y_max, y_tick_count, true);
+                if y_min >= 0.0 {
+                    axis.min = axis.min.max(0.0);
+                }
+                axis.ticks.retain(|&(tick, _)| (axis.min..=axis.max).contains(&tick));
+
+                axis
+            }
+        };
+        let y_label_width = y
+            .ticks
+            .iter()
+            .map(|(_, label)| ctx.measure_text(&self.text(label.as_str())).0)
+            .fold(0.0, f64::max);
+        left += y_label_width + TICK_LENGTH + 2.0;
+
+        if right - left < 1.0 || bottom - top < 1.0 {
+            self.frame = None;
+            self.hit = None;
+            return;
+        }
+
+        let x_tick_count = ((right - left) / 80.0).floor().max(2.0) as usize;
+        let x = match self.kind {
+            Kind::Bar if !self.categories.is_empty() => Axis::categorical(&self.categories),
+            Kind::Line | Kind::Scatter => Axis::linear(x_min, x_max, x_tick_count, true),
+            Kind::Bar | Kind::Histogram { .. } => Axis::linear(x_min, x_max, x_tick_count, false),
+        };
+
+        let frame = Frame {
+            left,
+            top,
+            right,
+            bottom,
+            x,
+            y,
+        };
+
+        self.draw_grid(ctx, &frame);
+        ctx.save();
+        // Dots and lines at the edges of the axes are left whole.
+        let margin = match self.kind {
+            Kind::Line | Kind::Scatter => DOT_RADIUS + 1.0,
+            Kind::Bar | Kind::Histogram { .. } => 0.0,
+        };
+        let mut clip = Path::new(FillMode::Winding);
+        clip.add_rectangle(
+            left - margin,
+            top - margin,
+            right - left + 2.0 * margin,
+            bottom - top + 2.0 * margin,
+        );
+        ctx.clip(&clip);
+        self.draw_series(ctx, &frame);
+        ctx.restore();
+        self.draw_axes(ctx, &frame, line_height);
+
+        if let Some(ref x_label) = x_label {
+            let (label_width, label_height) = ctx.measure_text(x_label);
+            ctx.draw_text(
+                x_label,
+                (left + right - label_width) / 2.0,
+                height - PADDING - label_height,
+            );
+        }
+        if let Some(ref y_label) = y_label {
+            let label_width = ctx.measure_text(y_label).0;
+            // Rotate the label to read upwards.
+            let mut matrix = Matrix::IDENTITY;
+            matrix.rotate(0.0, 0.0, -FRAC_PI_2);
+            matrix.translate(PADDING, (top + bottom + label_width) / 2.0);
+            ctx.save();
+            ctx.transform(&matrix);
+            ctx.draw_text(y_label, 0.0, 0.0);
+            ctx.restore();
+        }
+        if self.shows_legend {
+            self.draw_legend(ctx, &frame, line_height);
+        }
+
+        self.hit = self.cursor.and_then(|cursor| self.hit_test(&frame, cursor));
+        if let Some(hit) = self.hit {
+            self.draw_tooltip(ctx, &frame, hit, (width, height));
+        }
+        self.frame = Some(frame);
+    }
+
+    fn draw_grid(&self, ctx: &mut dyn DrawContext, frame: &Frame) {
+        if !self.shows_grid {
+            return;
+        }
+
+        let mut path = Path::new(FillMode::Winding);
+        for &(tick, _) in &frame.y.ticks {
+            let y = crisp(frame.y_to_px(tick));
+            path.new_figure(frame.left, y).line_to(frame.right, y);
+        }
+        // Vertical grid lines would run through bars.
+        if matches!(self.kind, Kind::Line | Kind::Scatter) {
+            for &(tick, _) in &frame.x.ticks {
+                let x = crisp(frame.x_to_px(tick));
+                path.new_figure(x, frame.top).line_to(x, frame.bottom);
+            }
+        }
+        ctx.stroke(&path, &Brush::Solid(GRID_COLOR), &StrokeParams::default());
+    }
+
+    fn draw_axes(&self, ctx: &mut dyn DrawContext, frame: &Frame, line_height: f64) {
+        let (left, bottom) = (crisp(frame.left), crisp(frame.bottom));
+        let mut path = Path::new(FillMode::Winding);
+        path.new_figure(left, frame.top)
+            .line_to(left, bottom)
+            .line_to(frame.right, bottom);
+
+        for (tick, label) in &frame.y.ticks {
+            let y = crisp(frame.y_to_px(*tick));
+            path.new_figure(left - TICK_LENGTH, y).line_to(left, y);
+
+            let layout = self.text(label.as_str());
+            let label_width = ctx.measure_text(&layout).0;
+            ctx.draw_text(
+                &layout,
+                left - TICK_LENGTH - 2.0 - label_width,
+                y - line_height / 2.0,
+            );
+        }
+        for (tick, label) in &frame.x.ticks {
+            let x = crisp(frame.x_to_px(*tick));
+            path.new_figure(x, bottom).line_to(x, bottom + TICK_LENGTH);
+
+            let layout = self.text(label.as_str());
+            let label_width = ctx.measure_text(&layout).0;
+            // Keep the labels at either end from running off the area.
+            let label_x = (x - label_width / 2.0).min(frame.right + PADDING - label_width);
+            ctx.draw_text(&layout, label_x.max(0.0), bottom + TICK_LENGTH + 2.0);
+        }
+
+        ctx.stroke(&path, &Brush::Solid(AXIS_COLOR), &StrokeParams::default());
+    }
+
+    fn draw_series(&self, ctx: &mut dyn DrawContext, frame: &Frame) {
+        match self.kind {
+            Kind::Line => {
+                let params = StrokeParams {
+                    cap: LineCap::Round,
+                    join: LineJoin::Round,
+                    thickness: 2.0,
+                    ..StrokeParams::default()
+                };
+                for (i, series) in self.series.iter().enumerate() {
+                    let mut path = Path::new(FillMode::Winding);
+                    let mut points = self
+                        .points(i)
+                        .map(|(_, (x, y))| (frame.x_to_px(x), frame.y_to_px(y)));
+                    let Some((x, y)) = points.next() else {
+                        continue;
+                    };
+                    path.new_figure(x, y);
+                    let mut is_single = true;
+                    for (x, y) in points {
+                        path.line_to(x, y);
+                        is_single = false;
+                    }
+
+                    if is_single {
+                        fill_dot(ctx, (x, y), 2.0, series.color);
+                    } else {
+                        ctx.stroke(&path, &Brush::Solid(series.color), &params);
+                    }
+                }
+            }
+            Kind::Scatter => {
+                for (i, series) in self.series.iter().enumerate() {
+                    let mut path = Path::new(FillMode::Winding);
+                    for (_, (x, y)) in self.points(i) {
+                        let center = (frame.x_to_px(x), frame.y_to_px(y));
+                        path.new_figure_with_arc(circle(center, DOT_RADIUS))
+                            .close_figure();
+                    }
+                    ctx.fill(&path, &Brush::Solid(series.color));
+                }
+            }
+            Kind::Bar => {
+                let spacing = self.x_spacing();
+                for (i, series) in self.series.iter().enumerate() {
+                    let mut path = Path::new(FillMode::Winding);
+                    for (_, point) in self.points(i) {
+                        let (x, y, width, height) =
+                            Self::rect_to_px(frame, self.bar_bounds(i, point, spacing));
+                        path.add_rectangle(x, y, width, height);
+                    }
+                    ctx.fill(&path, &Brush::Solid(series.color));
+                }
+            }
+            Kind::Histogram { .. } => {
+                let Some(bins) = self.bins() else {
+                    return;
+                };
+                // Overlapping series are made translucent so that all of them stay visible.
+                let alpha = if self.series.len() > 1 { 0.6 } else { 1.0 };
+                for (series, counts) in self.series.iter().zip(&bins.counts) {
+                    let mut path = Path::new(FillMode::Winding);
+                    for (bin, &count) in counts.iter().enumerate() {
+                        let (x, y, width, height) =
+                            Self::rect_to_px(frame, bin_rect(&bins, bin, count));
+                        // Leave a hairline gap between bins.
+                        path.add_rectangle(x + 0.5, y, (width - 1.0).max(0.0), height);
+                    }
+                    let color = series.color.with_alpha(series.color.alpha * alpha);
+                    ctx.fill(&path, &Brush::Solid(color));
+                }
+            }
+        }
+    }
+
+    fn draw_legend(&self, ctx: &mut dyn DrawContext, frame: &Frame, line_height: f64) {
+        let entries: Vec<(TextLayout, Color)> = self
+            .series
+            .iter()
+            .filter(|series| !series.name.is_empty())
+            .map(|series| (self.text(series.name.as_str()), series.color))
+            .collect();
+        if entries.is_empty() {
+            return;
+        }
+
+        const SWATCH: f64 = 10.0;
+        let text_width = entries
+            .iter()
+            .map(|(layout, _)| ctx.measure_text(layout).0)
+            .fold(0.0, f64::max);
+        let row_height = line_height.max(SWATCH) + 2.0;
+        let width = PADDING + SWATCH + 4.0 + text_width + PADDING;
+        let height = PADDING / 2.0 + row_height * (entries.len() as f64) + PADDING / 2.0;
+        let x = frame.right - PADDING - width;
+        let y = frame.top + PADDING;
+
+        draw_box(ctx, (x, y, width, height));
+        for (i, (layout, color)) in entries.iter().enumerate() {
+            let row = y + PADDING / 2.0 + row_height * (i as f64);
+            fill_rect(
+                ctx,
+                (x + PADDING, row + (row_height - SWATCH) / 2.0, SWATCH, SWATCH),
+                *color,
+            );
+            ctx.draw_text(
+                layout,
+                x + PADDING + SWATCH + 4.0,
+                row + (row_height - line_height) / 2.0,
+            );
+        }
+    }
+
+    /// Finds the data under the given position.
+    fn hit_test(&self, frame: &Frame, cursor: (f64, f64)) -> Option<Hit> {
+        if !frame.contains(cursor) {
+            return None;
+        }
+
+        match self.kind {
+            Kind::Line | Kind::Scatter => {
+                let mut nearest = None;
+                let mut nearest_distance = HOVER_RADIUS;
+                for series in 0..self.series.len() {
+                    for (index, (x, y)) in self.points(series) {
+                        let distance =
+                            (frame.x_to_px(x) - cursor.0).hypot(frame.y_to_px(y) - cursor.1);
+                        if distance <= nearest_distance {
+                            nearest = Some(Hit { series, index });
+                            nearest_distance = distance;
+                        }
+                    }
+                }
+
+                nearest
+            }
+            Kind::Bar => {
+                let spacing = self.x_spacing();
+                (0..self.series.len()).find_map(|series| {
+                    self.points(series).find_map(|(index, point)| {
+                        let rect = Self::rect_to_px(frame, self.bar_bounds(series, point, spacing));
+                        rect_contains(rect, cursor).then_some(Hit { series, index })
+                    })
+                })
+            }
+            Kind::Histogram { .. } => {
+                let bins = self.bins()?;
+                // Later series are drawn over earlier ones.
+                bins.counts.iter().enumerate().rev().find_map(|(series, counts)| {
+                    counts.iter().enumerate().find_map(|(index, &count)| {
+                        let rect = Self::rect_to_px(frame, bin_rect(&bins, index, count));
+                        rect_contains(rect, cursor).then_some(Hit { series, index })
+                    })
+                })
+            }
+        }
+    }
+
+    fn draw_tooltip(
+        &self,
+        ctx: &mut dyn DrawContext,
+        frame: &Frame,
+        hit: Hit,
+        (width, height): (f64, f64),
+    ) {
+        let series = &self.series[hit.series];
+        let (anchor, text) = match self.kind {
+            Kind::Line | Kind::Scatter => {
+                let (x, y) = series.points[hit.index];
+                let anchor = (frame.x_to_px(x), frame.y_to_px(y));
+                fill_dot(ctx, anchor, 5.0, series.color);
+                let mut ring = Path::new(FillMode::Winding);
+                ring.new_figure_with_arc(circle(anchor, 5.0)).close_figure();
+                ctx.stroke(
+                    &ring,
+                    &Brush::Solid(Color::WHITE),
+                    &StrokeParams {
+                        thickness: 1.5,
+                        ..StrokeParams::default()
+                    },
+                );
+
+                (anchor, format!("({}, {})", format_value(x), format_value(y)))
+            }
+            Kind::Bar => {
+                let point = series.points[hit.index];
+                let bounds = self.bar_bounds(hit.series, point, self.x_spacing());
+                let rect = Self::rect_to_px(frame, bounds);
+                highlight_rect(ctx, rect);
+                let x = match self.categories.get(point.0.round() as usize) {
+                    Some(category) if point.0 >= -0.5 => category.clone(),
+                    _ => format_value(point.0),
+                };
+
+                ((rect.0 + rect.2 / 2.0, rect.1), format!("{}: {}", x, format_value(point.1)))
+            }
+            Kind::Histogram { .. } => {
+                let Some(bins) = self.bins() else {
+                    return;
+                };
+                let count = bins.counts[hit.series][hit.index];
+                let rect = Self::rect_to_px(frame, bin_rect(&bins, hit.index, count));
+                highlight_rect(ctx, rect);
+                let (start, end) = bins.bounds(hit.index);
+
+                (
+                    (rect.0 + rect.2 / 2.0, rect.1),
+                    format!(
+                        "[{}, {}): {}",
+                        format_value(start),
+                        format_value(end),
+                        format_value(count),
+                    ),
+                )
+            }
+        };
+        let text = if series.name.is_empty() {
+            text
+        } else {
+            format!("{}\n{}", series.name, text)
+        };
+
+        let layout = self.text(text);
+        let (text_width, text_height) = ctx.measure_text(&layout);
+        let (box_width, box_height) = (text_width + PADDING, text_height + PADDING);
+        // Prefer placing the tooltip above and to the right of the data, but keep it in view.
+        let mut x = anchor.0 + PADDING;
+        if x + box_width > width {
+            x = anchor.0 - PADDING - box_width;
+        }
+        let mut y = anchor.1 - PADDING - box_height;
+        if y < 0.0 {
+            y = anchor.1 + PADDING;
+        }
+        let x = x.clamp(0.0, (width - box_width).max(0.0));
+        let y = y.clamp(0.0, (height - box_height).max(0.0));
+
+        draw_box(ctx, (x, y, box_width, box_height));
+        ctx.draw_text(&layout, x + PADDING / 2.0, y + PADDING / 2.0);
+    }
+}
+
+impl AreaHandler for Plot {
+    fn draw(&mut self, ctx: &mut dyn DrawContext, params: &DrawParams) {
+        self.render(ctx, params.area_width, params.area_height);
+    }
+
+    fn mouse_event(&mut self, area: &Area, event: &MouseEvent) {
+        if self.hover(event.x, event.y) {
+            area.queue_redraw_all();
+        }
+    }
+
+    fn mouse_crossed(&mut self, area: &Area, has_left: bool) {
+        if has_left && self.unhover() {
+            area.queue_redraw_all();
+        }
+    }
+}
+
+/// The rectangle, in data units, of a histogram bar.
+fn bin_rect(bins: &Bins, index: usize, count: f64) -> Rect {
+    let (start, end) = bins.bounds(index);
+
+    (start, count.min(0.0), end - start, count.abs())
+}
+
+/// Moves a coordinate to the center of a pixel, which keeps 1-pixel lines sharp.
+fn crisp(value: f64) -> f64 {
+    value.floor() + 0.5
+}
+
+fn circle((x, y): (f64, f64), radius: f64) -> Arc {
+    Arc {
+        x_center: x,
+        y_center: y,
+        radius,
+        start_angle: 0.0,
+        sweep: TAU,
+        is_negative: false,
+    }
+}
+
+fn rect_contains((x, y, width, height): Rect, (px, py): (f64, f64)) -> bool {
+    (x..=x + width).contains(&px) && (y..=y + height).contains(&py)
+}
+
+fn fill_rect(ctx: &mut dyn DrawContext, (x, y, width, height): Rect, color: Color) {
+    let mut path = Path::new(FillMode::Winding);
+    path.add_rectangle(x, y, width, height);
+    ctx.fill(&path, &Brush::Solid(color));
+}
+
+fn fill_dot(ctx: &mut dyn DrawContext, center: (f64, f64), radius: f64, color: Color) {
+    let mut path = Path::new(FillMode::Winding);
+    path.new_figure_with_arc(circle(center, radius)).close_figure();
+    ctx.fill(&path, &Brush::Solid(color));
+}
+
+/// Outlines a hovered bar.
+fn highlight_rect(ctx: &mut dyn DrawContext, (x, y, width, height): Rect) {
+    let mut path = Path::new(FillMode::Winding);
+    path.add_rectangle(x, y, width, height);
+    ctx.stroke(
+        &path,
+        &Brush::Solid(Color::BLACK.with_alpha(0.6)),
+        &StrokeParams {
+            thickness: 2.0,
+            ..StrokeParams::default()
+        },
+    );
+}
+
+/// Draws the background of a legend or tooltip.
+fn draw_box(ctx: &mut dyn DrawContext, (x, y, width, height): Rect) {
+    fill_rect(ctx, (x, y, width, height), Color::WHITE.with_alpha(0.9));
+    let mut path = Path::new(FillMode::Winding);
+    path.add_rectangle(crisp(x), crisp(y), width.round(), height.round());
+    ctx.stroke(&path, &Brush::Solid(GRID_COLOR.mix(AXIS_COLOR, 0.3)), &StrokeParams::default());
+}
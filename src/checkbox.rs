@@ -8,7 +8,7 @@ impl Ui {
     pub fn create_checkbox<'ui>(
         &'ui self,
         text: impl AsRef<str>,
-    ) -> Result<&'ui mut Checkbox, crate::Error> {
+    ) -> Result<&'ui Checkbox, crate::Error> {
         // SAFETY: `uiNewCheckbox` `strdup`s `text`, so it's OK to drop at the end of scope.
         let text = make_cstring!(text.as_ref());
 
@@ -38,6 +38,7 @@ def_subcontrol!(
         ## Linux
     ",
     ty: Checkbox,
+    kind: Checkbox,
     handle: uiCheckbox,
     cb_fns: [ on_toggled() ],
 );
@@ -91,6 +92,7 @@ impl<'ui> Checkbox<'ui> {
             ty: Checkbox<'ui>,
             handle: uiCheckbox,
             fn: on_toggled(),
+            emit: emit_toggled(),
             cb: { sig: f -> () },
         },
         libui: {
@@ -99,6 +101,19 @@ impl<'ui> Checkbox<'ui> {
         },
     );
 
+    /// Simulates a toggle, checking or unchecking this checkbox and then calling the callback set by
+    /// [`Checkbox::on_toggled`] as though the user had clicked it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn simulate_toggle(&self) {
+        self.set_checked(!self.checked());
+        self.emit_toggled();
+    }
+
     bind_bool_fn!(
         docs: "
             Determines if this checkbox is checked.
@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use std::ptr::addr_of_mut;
+//! [`Picker`] and the [`Color`] it selects.
+
+use std::{fmt, ptr::addr_of_mut, str::FromStr};
 
 use crate::prelude::*;
 
@@ -12,7 +14,7 @@ impl Ui {
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn create_color_picker<'ui>(&'ui self) -> Result<&'ui mut Picker, crate::Error> {
+    pub fn create_color_picker<'ui>(&'ui self) -> Result<&'ui Picker, crate::Error> {
         call_libui_new_fn!(
             ui: self,
             fn: uiNewColorButton() -> Picker,
@@ -31,12 +33,33 @@ def_subcontrol!(
         ```
     ",
     ty: Picker,
+    kind: ColorPicker,
     handle: uiColorButton,
     cb_fns: [ on_selected() ],
 );
 
 /// An RGBA color.
-#[derive(Clone, Copy, Default)]
+///
+/// Each component is nominally in the range `0.0..=1.0`, and the color components are in the sRGB
+/// color space unless stated otherwise. Components are not clamped automatically; see
+/// [`Color::clamp`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), boing::color::ParseColorError> {
+/// use boing::Color;
+///
+/// let accent: Color = "#3366ccff".parse()?;
+/// assert_eq!("#3366cc", accent.to_hex());
+///
+/// let shadow = Color::BLACK.with_alpha(0.5).over(Color::WHITE);
+/// assert_eq!([128, 128, 128, 255], shadow.to_rgba8());
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Color {
     pub red: f64,
     pub green: f64,
@@ -44,6 +67,489 @@ pub struct Color {
     pub alpha: f64,
 }
 
+impl Color {
+    /// Opaque black.
+    pub const BLACK: Self = Self::rgb(0.0, 0.0, 0.0);
+
+    /// Opaque white.
+    pub const WHITE: Self = Self::rgb(1.0, 1.0, 1.0);
+
+    /// Fully-transparent black.
+    pub const TRANSPARENT: Self = Self::new(0.0, 0.0, 0.0, 0.0);
+
+    /// Creates a new [`Color`] from its components.
+    pub const fn new(red: f64, green: f64, blue: f64, alpha: f64) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    /// Creates a new opaque [`Color`] from its color components.
+    pub const fn rgb(red: f64, green: f64, blue: f64) -> Self {
+        Self::new(red, green, blue, 1.0)
+    }
+
+    /// This color with the given alpha component.
+    pub const fn with_alpha(self, alpha: f64) -> Self {
+        Self { alpha, ..self }
+    }
+
+    /// Creates a new [`Color`] from 8-bit RGBA components.
+    pub fn from_rgba8([red, green, blue, alpha]: [u8; 4]) -> Self {
+        let unit = |value: u8| f64::from(value) / 255.0;
+
+        Self::new(unit(red), unit(green), unit(blue), unit(alpha))
+    }
+
+    /// This color as 8-bit RGBA components.
+    ///
+    /// Components are clamped and then rounded to the nearest 8-bit value.
+    pub fn to_rgba8(&self) -> [u8; 4] {
+        let byte = |value: f64| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        [byte(self.red), byte(self.green), byte(self.blue), byte(self.alpha)]
+    }
+
+    /// Parses a hexadecimal color of the form `#rrggbb` or `#rrggbbaa`.
+    ///
+    /// The leading `#` is optional, and the CSS shorthands `#rgb` and `#rgba` are accepted too.
+    pub fn from_hex(hex: &str) -> Result<Self, ParseColorError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ParseColorError::InvalidHex);
+        }
+
+        let nibble = |i: usize| u8::from_str_radix(&digits[i..=i], 16).unwrap();
+        let byte = |i: usize| u8::from_str_radix(&digits[(2 * i)..(2 * i + 2)], 16).unwrap();
+        let rgba = match digits.len() {
+            3 => [nibble(0) * 0x11, nibble(1) * 0x11, nibble(2) * 0x11, 0xff],
+            4 => [nibble(0) * 0x11, nibble(1) * 0x11, nibble(2) * 0x11, nibble(3) * 0x11],
+            6 => [byte(0), byte(1), byte(2), 0xff],
+            8 => [byte(0), byte(1), byte(2), byte(3)],
+            _ => return Err(ParseColorError::InvalidHex),
+        };
+
+        Ok(Self::from_rgba8(rgba))
+    }
+
+    /// This color as a hexadecimal string of the form `#rrggbb`, or `#rrggbbaa` if it is not
+    /// opaque.
+    pub fn to_hex(&self) -> String {
+        let [red, green, blue, alpha] = self.to_rgba8();
+        if alpha == 0xff {
+            format!("#{:02x}{:02x}{:02x}", red, green, blue)
+        } else {
+            format!("#{:02x}{:02x}{:02x}{:02x}", red, green, blue, alpha)
+        }
+    }
+
+    /// Looks up an opaque color by its [CSS name], e.g., `cornflowerblue`.
+    ///
+    /// Names are case-insensitive. The special name `transparent` is also recognized.
+    ///
+    /// [CSS name]: https://www.w3.org/TR/css-color-4/#named-colors
+    pub fn from_css_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        if name == "transparent" {
+            return Some(Self::TRANSPARENT);
+        }
+
+        CSS_NAMES
+            .binary_search_by_key(&name.as_str(), |(name, _)| name)
+            .ok()
+            .map(|i| {
+                let [_, red, green, blue] = CSS_NAMES[i].1.to_be_bytes();
+
+                Self::from_rgba8([red, green, blue, 0xff])
+            })
+    }
+
+    /// Creates a new [`Color`] from hue, saturation, and value components.
+    ///
+    /// `hue` is in degrees and wraps around; the other components are in the range `0.0..=1.0`.
+    pub fn from_hsv(hue: f64, saturation: f64, value: f64, alpha: f64) -> Self {
+        let chroma = value * saturation;
+        let [red, green, blue] = hue_to_rgb(hue, chroma);
+        let min = value - chroma;
+
+        Self::new(red + min, green + min, blue + min, alpha)
+    }
+
+    /// The hue, saturation, and value components of this color.
+    ///
+    /// The hue is in degrees in the range `0.0..360.0`, and is 0 for grays.
+    pub fn to_hsv(&self) -> (f64, f64, f64) {
+        let (max, _, chroma) = self.extrema();
+        let saturation = if max == 0.0 { 0.0 } else { chroma / max };
+
+        (self.hue(), saturation, max)
+    }
+
+    /// Creates a new [`Color`] from hue, saturation, and lightness components.
+    ///
+    /// `hue` is in degrees and wraps around; the other components are in the range `0.0..=1.0`.
+    pub fn from_hsl(hue: f64, saturation: f64, lightness: f64, alpha: f64) -> Self {
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let [red, green, blue] = hue_to_rgb(hue, chroma);
+        let min = lightness - chroma / 2.0;
+
+        Self::new(red + min, green + min, blue + min, alpha)
+    }
+
+    /// The hue, saturation, and lightness components of this color.
+    ///
+    /// The hue is in degrees in the range `0.0..360.0`, and is 0 for grays.
+    pub fn to_hsl(&self) -> (f64, f64, f64) {
+        let (max, min, chroma) = self.extrema();
+        let lightness = (max + min) / 2.0;
+        let saturation = if lightness == 0.0 || lightness == 1.0 {
+            0.0
+        } else {
+            chroma / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+
+        (self.hue(), saturation, lightness)
+    }
+
+    /// This color with all components clamped to the range `0.0..=1.0`.
+    ///
+    /// NaN components become 0.
+    pub fn clamp(self) -> Self {
+        let clamp = |value: f64| if value.is_nan() { 0.0 } else { value.clamp(0.0, 1.0) };
+
+        Self::new(clamp(self.red), clamp(self.green), clamp(self.blue), clamp(self.alpha))
+    }
+
+    /// Converts the color components of this color from sRGB to linear RGB.
+    ///
+    /// The alpha component is unchanged.
+    pub fn to_linear(self) -> Self {
+        let linear = |value: f64| {
+            if value <= 0.04045 {
+                value / 12.92
+            } else {
+                ((value + 0.055) / 1.055).powf(2.4)
+            }
+        };
+
+        Self::new(linear(self.red), linear(self.green), linear(self.blue), self.alpha)
+    }
+
+    /// Converts the color components of this color from linear RGB to sRGB.
+    ///
+    /// This is the inverse of [`Color::to_linear`]. The alpha component is unchanged.
+    pub fn to_srgb(self) -> Self {
+        let srgb = |value: f64| {
+            if value <= 0.0031308 {
+                value * 12.92
+            } else {
+                1.055 * value.powf(1.0 / 2.4) - 0.055
+            }
+        };
+
+        Self::new(srgb(self.red), srgb(self.green), srgb(self.blue), self.alpha)
+    }
+
+    /// Composites this color over the given background color.
+    ///
+    /// This is the Porter-Duff *source-over* operator on non-premultiplied colors.
+    pub fn over(self, background: Self) -> Self {
+        let alpha = self.alpha + background.alpha * (1.0 - self.alpha);
+        if alpha == 0.0 {
+            return Self::TRANSPARENT;
+        }
+
+        let blend = |fg: f64, bg: f64| {
+            (fg * self.alpha + bg * background.alpha * (1.0 - self.alpha)) / alpha
+        };
+
+        Self::new(
+            blend(self.red, background.red),
+            blend(self.green, background.green),
+            blend(self.blue, background.blue),
+            alpha,
+        )
+    }
+
+    /// Linearly interpolates between this color and another.
+    ///
+    /// A `t` of 0 gives this color and a `t` of 1 gives `other`. Interpolation happens
+    /// component-wise in whichever space the colors are in; convert them with
+    /// [`Color::to_linear`] first for physically-accurate mixing.
+    pub fn mix(self, other: Self, t: f64) -> Self {
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+
+        Self::new(
+            lerp(self.red, other.red),
+            lerp(self.green, other.green),
+            lerp(self.blue, other.blue),
+            lerp(self.alpha, other.alpha),
+        )
+    }
+
+    /// The largest and smallest color components and the difference between them.
+    fn extrema(&self) -> (f64, f64, f64) {
+        let max = self.red.max(self.green).max(self.blue);
+        let min = self.red.min(self.green).min(self.blue);
+
+        (max, min, max - min)
+    }
+
+    fn hue(&self) -> f64 {
+        let (max, _, chroma) = self.extrema();
+        if chroma == 0.0 {
+            return 0.0;
+        }
+
+        let sector = if max == self.red {
+            ((self.green - self.blue) / chroma).rem_euclid(6.0)
+        } else if max == self.green {
+            (self.blue - self.red) / chroma + 2.0
+        } else {
+            (self.red - self.green) / chroma + 4.0
+        };
+
+        sector * 60.0
+    }
+}
+
+/// The color components of a fully-saturated color with the given hue and chroma, before the
+/// minimum component is added.
+fn hue_to_rgb(hue: f64, chroma: f64) -> [f64; 3] {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+
+    match sector as u8 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x],
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    /// Parses a hexadecimal color, as with [`Color::from_hex`], or a CSS color name, as with
+    /// [`Color::from_css_name`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.starts_with('#') {
+            return Self::from_hex(s);
+        }
+
+        Self::from_css_name(s)
+            .or_else(|| Self::from_hex(s).ok())
+            .ok_or(ParseColorError::UnknownName)
+    }
+}
+
+impl fmt::Display for Color {
+    /// Formats this color as with [`Color::to_hex`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+/// Serializes as a hexadecimal string, as with [`Color::to_hex`].
+///
+/// Components are rounded to 8 bits.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Color {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hex())
+    }
+}
+
+/// Deserializes from a hexadecimal string or CSS color name, as with [`Color::from_str`].
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Color {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// The error returned when a [`Color`] fails to be parsed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseColorError {
+    /// The string is not a hexadecimal color of a supported length.
+    InvalidHex,
+    /// The string is not a known CSS color name.
+    UnknownName,
+}
+
+impl std::error::Error for ParseColorError {}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHex => {
+                write!(f, "expected a hexadecimal color like #rrggbb or #rrggbbaa")
+            }
+            Self::UnknownName => write!(f, "unknown color name"),
+        }
+    }
+}
+
+/// The CSS named colors, sorted by name, as 0xRRGGBB.
+const CSS_NAMES: &[(&str, u32)] = &[
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
 impl<'ui> Picker<'ui> {
     /// The color currently selected by this picker.
     ///
@@ -80,7 +586,7 @@ impl<'ui> Picker<'ui> {
         unsafe {
             uiColorButtonSetColor(
                 self.as_ptr(),
-                color.alpha,
+                color.red,
                 color.green,
                 color.blue,
                 color.alpha,
@@ -104,6 +610,7 @@ impl<'ui> Picker<'ui> {
             ty: Picker<'ui>,
             handle: uiColorButton,
             fn: on_selected(),
+            emit: emit_selected(),
             cb: { sig: f -> () },
         },
         libui: {
@@ -111,4 +618,17 @@ impl<'ui> Picker<'ui> {
             cb: { sig: () -> () },
         },
     );
+
+    /// Simulates a selection, selecting the given color and then calling the callback set by
+    /// [`Picker::on_selected`] as though the user had picked it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn simulate_selection(&self, color: Color) {
+        self.select_color(color);
+        self.emit_selected();
+    }
 }
@@ -2,6 +2,8 @@
 
 //! [`Combobox`].
 
+use std::{cell::RefCell, fmt, rc::Rc};
+
 use crate::prelude::*;
 
 impl Ui {
@@ -12,12 +14,43 @@ impl Ui {
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn create_combobox<'ui>(&'ui self) -> Result<&'ui mut Combobox, crate::Error> {
+    pub fn create_combobox<'ui>(&'ui self) -> Result<&'ui Combobox, crate::Error> {
         call_libui_new_fn!(
             ui: self,
             fn: uiNewCombobox() -> Combobox,
         )
     }
+
+    /// Creates a new [`TypedCombobox`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), boing::Error> {
+    /// use boing::Ui;
+    ///
+    /// let ui: Ui;
+    /// # ui = Ui::new()?;
+    ///
+    /// let mut baud_rate = ui.create_typed_combobox::<u32>()?;
+    /// baud_rate.replace_items([9600, 19200, 38400, 57600, 115200])?;
+    /// baud_rate.on_item_selected(|rate| {
+    ///     if let Some(rate) = rate {
+    ///         println!("Selected {} baud", rate);
+    ///     }
+    /// });
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_typed_combobox<'ui, T: fmt::Display>(
+        &'ui self,
+    ) -> Result<TypedCombobox<'ui, T>, crate::Error> {
+        Ok(TypedCombobox {
+            control: self.create_combobox()?,
+            items: Rc::default(),
+        })
+    }
 }
 
 def_subcontrol!(
@@ -31,8 +64,9 @@ def_subcontrol!(
         ```
     ",
     ty: Combobox,
+    kind: Combobox,
     handle: uiCombobox,
-    cb_fns: [ on_item_selected() ],
+    cb_fns: [ on_item_selected(Option<u16>) ],
 );
 
 impl<'ui> Combobox<'ui> {
@@ -141,7 +175,9 @@ impl<'ui> Combobox<'ui> {
 
     bind_fn!(
         docs: "
-            The index of the currently-selected item.
+            The index of the currently-selected item, or `None` if no item is selected.
+
+            No item is selected by default.
 
             # Examples
 
@@ -150,15 +186,15 @@ impl<'ui> Combobox<'ui> {
             ```
         ",
         self: {
-            fn: selected_item() -> u16,
-            map_out: |_, index| to_u16!(index),
+            fn: selected_item() -> Option<u16>,
+            map_out: |_, index| selection_from_param(index),
         },
         libui: { fn: uiComboboxSelected() },
     );
 
     bind_fn!(
         docs: "
-            Selects the item with the given index.
+            Selects the item with the given index, or clears the selection if `item` is `None`.
 
             # Examples
 
@@ -166,13 +202,16 @@ impl<'ui> Combobox<'ui> {
             // TODO
             ```
         ",
-        self: { fn: select_item(item: u16) },
+        self: { fn: select_item(item: Option<u16> => selection_into_param) },
         libui: { fn: uiComboboxSetSelected() },
     );
 
     bind_callback_fn!(
         docs: "
-            Sets a callback for an item is selected.
+            Sets a callback for when an item is selected.
+
+            The callback receives the index of the newly-selected item, or `None` if the selection
+            was cleared.
 
             This callback is unset by default.
 
@@ -186,11 +225,103 @@ impl<'ui> Combobox<'ui> {
             ty: Combobox<'ui>,
             handle: uiCombobox,
             fn: on_item_selected(),
-            cb: { sig: f -> () },
+            emit: emit_item_selected(),
+            cb: {
+                sig: f(Option<u16>) -> (),
+                arg: |this| this.selected_item(),
+            },
         },
         libui: {
             fn: uiComboboxOnSelected(),
             cb: { sig: () -> () },
         },
     );
+
+    /// Simulates a selection, selecting the given item and then calling the callback set by
+    /// [`Combobox::on_item_selected`] as though the user had picked it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn simulate_selection(&self, item: Option<u16>) {
+        self.select_item(item);
+        self.emit_item_selected();
+    }
+}
+
+/// A [`Combobox`] whose items are values of type `T`.
+///
+/// Items are displayed with their [`Display`](fmt::Display) implementation, and the values
+/// themselves are kept alongside the combobox so that selections may be mapped back to them.
+///
+/// This is created with [`Ui::create_typed_combobox`].
+pub struct TypedCombobox<'ui, T> {
+    control: &'ui Combobox<'ui>,
+    items: Rc<RefCell<Vec<T>>>,
+}
+
+impl_typed_selection!(
+    ty: TypedCombobox,
+    control: Combobox { select: select_item() },
+);
+
+impl<'ui, T: 'ui + fmt::Display> TypedCombobox<'ui, T> {
+    /// The underlying combobox.
+    pub fn combobox(&self) -> &Combobox<'ui> {
+        self.control
+    }
+
+    /// Inserts a new item before the item at the given index, returning the index of the new item.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `before` is greater than the number of items.
+    pub fn insert(&self, before: u16, item: T) -> Result<u16, crate::Error> {
+        assert!(usize::from(before) <= self.items.borrow().len());
+
+        let index = self.control.insert_new_item(before, item.to_string())?;
+        self.items.borrow_mut().insert(before.into(), item);
+
+        Ok(index)
+    }
+
+    /// Removes and returns the item at the given index.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `index` is out of bounds.
+    pub fn remove(&self, index: u16) -> T {
+        let item = self.items.borrow_mut().remove(index.into());
+        self.control.remove_item(index);
+
+        item
+    }
+
+    /// Removes all items.
+    pub fn clear(&self) {
+        self.control.clear();
+        self.items.borrow_mut().clear();
+    }
+
+    /// Replaces all items with those of the given iterator.
+    ///
+    /// The selection is cleared.
+    pub fn replace_items(&self, items: impl IntoIterator<Item = T>) -> Result<(), crate::Error> {
+        self.clear();
+        self.extend(items)
+    }
+}
+
+/// Converts a selection index returned by *libui-ng* to an `Option`.
+///
+/// *libui-ng* represents an empty selection as -1.
+pub(crate) fn selection_from_param(index: i32) -> Option<u16> {
+    u16::try_from(index).ok()
+}
+
+/// Converts an `Option` selection index to one accepted by *libui-ng*.
+pub(crate) fn selection_into_param(index: Option<u16>) -> i32 {
+    index.map_or(-1, i32::from)
 }
@@ -2,57 +2,108 @@
 
 //! A type-erased control.
 
-use std::{cell::Cell, os::raw::c_void};
+use std::{cell::Cell, fmt, marker::PhantomData, os::raw::c_void};
 
 use crate::prelude::*;
 
-impl Control {
+impl<'ui> Control<'ui> {
     /// Creates a new [`Control`].
     ///
-    /// Contrary to intuition, the created control is not bound to any lifetime. This is because
-    /// *libui-ng* permits controls to be accessed as long as they are not destroyed and *libui-ng*
-    /// is initialized. Therefore, the following is perfectly valid:
-    ///
-    /// (Unfortunately this example must be ignored because it uses a private method.)
-    /// ```ignore
-    /// # fn main() -> Result<(), boing::Error> {
-    /// use boing::{Control, Ui};
-    /// use libui_ng_sys::uiControl;
-    ///
-    /// let ui: Ui;
-    /// # ui = Ui::new()?;
-    ///
-    /// let control: *mut uiControl;
-    /// # control = std::ptr::null_mut();
-    /// let control = Control::new(control);
-    ///
-    /// // The [`Ui`] [`Drop`] implementation is actually a stub, so this doesn't even do anything!
-    /// drop(ui);
-    ///
-    /// // Free the control's resources.
-    /// drop(control);
-    /// #
-    /// # Ok(())
-    /// # }
-    /// ```
+    /// The created control borrows the [`Ui`] that created it, through whose registry it may be
+    /// downcast with [`Control::downcast_ref`].
     ///
     /// # Safety
     ///
-    /// `ptr` must point to a valid `uiControl`.
+    /// `ptr` must point to a valid `uiControl` of the given kind.
     ///
     /// # Examples
     ///
     /// ```no_run
     /// // TODO
     /// ```
-    pub(crate) unsafe fn new(ptr: *mut uiControl) -> Self {
+    pub(crate) unsafe fn new(ui: &'ui Ui, ptr: *mut uiControl, kind: Kind) -> Self {
         Self {
+            ui,
             ptr,
+            kind,
             is_child: Cell::new(false),
+            _invariant: PhantomData,
         }
     }
 }
 
+/// The concrete type of a [`Control`].
+///
+/// This is returned by [`Control::kind`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Kind {
+    /// An [`Area`](crate::Area).
+    Area,
+    /// An [`Axis`](crate::Axis).
+    Axis,
+    /// A [`Checkbox`](crate::Checkbox).
+    Checkbox,
+    /// A [color `Picker`](crate::color::Picker).
+    ColorPicker,
+    /// A [`Combobox`](crate::Combobox).
+    Combobox,
+    /// A [`Custom`](crate::Custom) control.
+    Custom,
+    /// A [`DateTimePicker`](crate::DateTimePicker).
+    DateTimePicker,
+    /// An [`EditableCombobox`](crate::EditableCombobox).
+    EditableCombobox,
+    /// A [`FontPicker`](crate::FontPicker).
+    FontPicker,
+    /// A [`Form`](crate::Form).
+    Form,
+    /// A [`Grid`](crate::Grid).
+    Grid,
+    /// A [`Group`](crate::Group).
+    Group,
+    /// A [`Label`](crate::Label).
+    Label,
+    /// A [`MultilineTextEntry`](crate::MultilineTextEntry).
+    MultilineTextEntry,
+    /// A [`Path`](crate::Path).
+    Path,
+    /// A [`ProgressBar`](crate::ProgressBar).
+    ProgressBar,
+    /// A [`Pushbutton`](crate::Pushbutton).
+    Pushbutton,
+    /// A set of [`RadioButtons`](crate::RadioButtons).
+    RadioButtons,
+    /// A [`Separator`](crate::Separator).
+    Separator,
+    /// A [`Slider`](crate::Slider).
+    Slider,
+    /// A [`Spinbox`](crate::Spinbox).
+    Spinbox,
+    /// A [`Tab`](crate::Tab).
+    Tab,
+    /// A [`Table`](crate::Table).
+    Table,
+    /// A [`TextEntry`](crate::TextEntry), including password and search entries.
+    TextEntry,
+    /// A [`Window`](crate::Window).
+    Window,
+}
+
+/// A concrete control type that a [`Control`] may be downcast to.
+///
+/// This trait is implemented by all controls, whose lifetime `'ui` it names, and cannot be
+/// implemented outside of *boing*.
+pub trait Subcontrol<'ui>: sealed::Sealed + Deref<Target = Control<'ui>> {
+    /// The kind recorded by controls of this type.
+    const KIND: Kind;
+}
+
+pub(crate) mod sealed {
+    /// Prevents [`Subcontrol`](super::Subcontrol) from being implemented outside of *boing*, as
+    /// [`Control::downcast_ref`](super::Control::downcast_ref) trusts its kind.
+    pub trait Sealed {}
+}
+
 /// A type-erased control.
 ///
 /// This type provides the underlying features that all controls must provide. When this type is
@@ -63,13 +114,35 @@ impl Control {
 /// ```no_run
 /// // TODO
 /// ```
-#[derive(Debug, Eq, PartialEq)]
-pub struct Control {
+pub struct Control<'ui> {
+    ui: &'ui Ui,
     ptr: *mut uiControl,
+    kind: Kind,
     is_child: Cell<bool>,
+    /// Ties the lifetime `'ui` to exactly that of the concrete control, so that a downcast can't
+    /// shorten it and set a callback that doesn't live long enough.
+    _invariant: PhantomData<fn(&'ui ()) -> &'ui ()>,
 }
 
-impl Drop for Control {
+impl fmt::Debug for Control<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Control")
+            .field("ptr", &self.ptr)
+            .field("kind", &self.kind)
+            .field("is_child", &self.is_child)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Control<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.ptr, self.kind, &self.is_child) == (other.ptr, other.kind, &other.is_child)
+    }
+}
+
+impl Eq for Control<'_> {}
+
+impl Drop for Control<'_> {
     fn drop(&mut self) {
         if !self.is_child.get() {
             let ptr = self.as_ptr();
@@ -79,7 +152,7 @@ impl Drop for Control {
     }
 }
 
-impl Control {
+impl<'ui> Control<'ui> {
     bind_bool_fn!(
         docs: "
             Determines if this control is visible.
@@ -204,6 +277,100 @@ impl Control {
         libui: { fn: uiControlDisable() },
     );
 
+    /// The concrete type of this control.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), boing::Error> {
+    /// use boing::{ControlKind, Ui};
+    ///
+    /// let ui: Ui;
+    /// # ui = Ui::new()?;
+    /// let checkbox = ui.create_checkbox("Checkbox")?;
+    ///
+    /// assert_eq!(ControlKind::Checkbox, checkbox.kind());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Determines if this control is of type `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn is<'a, T: Subcontrol<'a>>(&self) -> bool {
+        self.kind == T::KIND
+    }
+
+    /// Returns a reference to the concrete control if it is of type `T`, or `None` if it isn't or
+    /// has been destroyed.
+    ///
+    /// This is the handle that was returned when the control was created, so callbacks set on it
+    /// must live for as long as that handle.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), boing::Error> {
+    /// use boing::{Checkbox, Control, Slider, Ui};
+    ///
+    /// let ui: Ui;
+    /// # ui = Ui::new()?;
+    /// let control: &Control = ui.create_checkbox("Checkbox")?;
+    ///
+    /// assert!(control.downcast_ref::<Slider>().is_none());
+    /// if let Some(checkbox) = control.downcast_ref::<Checkbox>() {
+    ///     println!("Checked: {}", checkbox.checked());
+    /// }
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// A callback set through the concrete control can't borrow anything that the control
+    /// outlives:
+    ///
+    /// ```compile_fail
+    /// # fn main() -> Result<(), boing::Error> {
+    /// use boing::{Checkbox, Control, Ui};
+    ///
+    /// let ui: Ui;
+    /// # ui = Ui::new()?;
+    /// let checkbox = ui.create_checkbox("Checkbox")?;
+    /// let control: &Control = checkbox;
+    /// {
+    ///     let message = String::from("Toggled!");
+    ///     control
+    ///         .downcast_ref::<Checkbox>()
+    ///         .unwrap()
+    ///         .on_toggled(|_| println!("{}", message));
+    /// }
+    /// checkbox.simulate_toggle();
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn downcast_ref<T: Subcontrol<'ui>>(&self) -> Option<&T> {
+        if !self.is::<T>() {
+            return None;
+        }
+
+        let handle = self.ui.registry.concrete_handle(self.as_ptr())?;
+
+        // SAFETY: The registered handle is the one allocated by `Ui::alloc_control` when this
+        // control was created, which is of the concrete type that its kind names, and `Subcontrol`
+        // is sealed, so that type is `T`. As `'ui` is invariant, it is that of the handle this
+        // control was reached through, and can't be shortened to let a callback escape.
+        Some(unsafe { &*handle.cast::<T>() })
+    }
+
     /// A handle to the underlying *libui-ng* control object.
     ///
     /// # Safety
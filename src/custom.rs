@@ -0,0 +1,304 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! A control implemented in Rust that wraps an arbitrary native widget.
+
+use std::{
+    cell::{Ref, RefCell, RefMut},
+    mem,
+    os::raw::{c_int, c_void},
+    ptr,
+};
+
+use crate::prelude::*;
+
+// The platform-specific extension of `uiControl` that the containers of each backend call into.
+#[cfg_attr(all(not(feature = "mock"), windows), path = "custom/windows.rs")]
+#[cfg_attr(all(not(feature = "mock"), target_os = "macos"), path = "custom/macos.rs")]
+#[cfg_attr(
+    all(not(feature = "mock"), unix, not(target_os = "macos")),
+    path = "custom/unix.rs"
+)]
+#[cfg_attr(feature = "mock", path = "custom/mock.rs")]
+mod platform;
+
+/// The OS signature given to all [`Custom`] controls.
+///
+/// *libui-ng* identifies controls of different platforms with a four-character code. As [`Custom`]
+/// controls are not tied to any particular platform implementation, they are branded with a
+/// *boing*-specific code instead.
+const OS_SIGNATURE: u32 = u32::from_be_bytes(*b"Boin");
+
+/// The type signature given to all [`Custom`] controls.
+const TYPE_SIGNATURE: u32 = u32::from_be_bytes(*b"Cstm");
+
+/// The type name given to all [`Custom`] controls.
+///
+/// *libui-ng* holds onto this pointer for the lifetime of the control, so it must be static.
+const TYPE_NAME: &[u8] = b"boing::Custom\0";
+
+/// The functions a [`Custom`] control delegates to.
+///
+/// This trait is effectively the Rust counterpart to the function table at the head of every
+/// `uiControl`. *boing* takes care of the bookkeeping that *libui-ng* expects from all
+/// controls---namely, tracking the parent, visibility, and interactability of the control---and
+/// forwards the remaining work to implementors of this trait.
+///
+/// When the control is destroyed, either because its [`Custom`] handle was dropped or because its
+/// parent was destroyed, the implementor is dropped. This is the place to release the native
+/// widget.
+///
+/// # Examples
+///
+/// ```no_run
+/// // TODO
+/// ```
+pub trait CustomControl {
+    /// A handle to the native widget wrapped by this control.
+    ///
+    /// The meaning of this handle depends on the platform: it is an `HWND` on Windows, an
+    /// `NSView *` on macOS, and a `GtkWidget *` on Linux.
+    fn handle(&self) -> *mut c_void;
+
+    /// Determines if this control is a top-level control, i.e., a window.
+    ///
+    /// Top-level controls cannot be given a parent. This is `false` by default.
+    fn is_toplevel(&self) -> bool {
+        false
+    }
+
+    /// Informs this control that it was given a new parent, or that its parent was removed if
+    /// `parent` is null.
+    ///
+    /// This does nothing by default.
+    fn set_parent(&mut self, _parent: *mut uiControl) {}
+
+    /// Shows or hides the native widget.
+    fn set_visible(&mut self, value: bool);
+
+    /// Makes the native widget interactable or uninteractable.
+    fn set_enabled(&mut self, value: bool);
+
+    /// The least width and height, in pixels, that the native widget may be laid out with.
+    ///
+    /// This is only consulted by the containers of the Windows backend; on other platforms, the
+    /// native widget reports its own size. This is `(0, 0)` by default.
+    fn minimum_size(&self) -> (i32, i32) {
+        (0, 0)
+    }
+}
+
+impl Ui {
+    /// Creates a new [`Custom`] control backed by the given implementation.
+    ///
+    /// The returned control may be used wherever a built-in control may, e.g., as the child of an
+    /// [`Axis`](crate::Axis) or [`Window`](crate::Window). The platform-specific parts of
+    /// `uiControl` that containers rely on are implemented on behalf of `control` in terms of its
+    /// native handle.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_custom_control(
+        &self,
+        control: impl 'static + CustomControl,
+    ) -> Result<&Custom, crate::Error> {
+        let ptr: *mut uiControl = call_fallible_libui_fn!(uiAllocControl(
+            mem::size_of::<RawCustom>(),
+            OS_SIGNATURE,
+            TYPE_SIGNATURE,
+            TYPE_NAME.as_ptr().cast(),
+        ))?;
+
+        // SAFETY: `uiAllocControl` allocated enough memory for a `RawCustom`, and `RawCustom`
+        // begins with a `uiControl`.
+        let raw: &mut RawCustom = unsafe { &mut *ptr.cast() };
+        // The platform extension of `uiControl` is laid out directly after it, as the backend
+        // expects.
+        raw.ext.init();
+        // `uiAllocControl` only initializes the signatures, so we must fill out the function table
+        // ourselves.
+        raw.control.Destroy = Some(destroy);
+        raw.control.Handle = Some(handle);
+        raw.control.Parent = Some(parent);
+        raw.control.SetParent = Some(set_parent);
+        raw.control.Toplevel = Some(toplevel);
+        raw.control.Visible = Some(visible);
+        raw.control.Show = Some(show);
+        raw.control.Hide = Some(hide);
+        raw.control.Enabled = Some(enabled);
+        raw.control.Enable = Some(enable);
+        raw.control.Disable = Some(disable);
+        raw.parent = ptr::null_mut();
+        raw.is_visible = true;
+        raw.is_enabled = true;
+        // The implementation is double-boxed so that it may be referred to by a thin pointer.
+        raw.imp = Box::into_raw(Box::new(RefCell::new(Box::new(control))));
+
+        Ok(self.alloc_control(Custom::new(self, ptr)))
+    }
+}
+
+/// The memory layout of a [`Custom`] control as seen by *libui-ng*.
+#[repr(C)]
+struct RawCustom {
+    control: uiControl,
+    ext: platform::Extension,
+    parent: *mut uiControl,
+    is_visible: bool,
+    is_enabled: bool,
+    imp: *mut RefCell<Box<dyn CustomControl>>,
+}
+
+impl RawCustom {
+    /// Reinterprets a `uiControl` created by [`Ui::create_custom_control`] as a [`RawCustom`].
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a live control created by [`Ui::create_custom_control`].
+    unsafe fn from_ptr<'a>(ptr: *mut uiControl) -> &'a mut Self {
+        debug_assert!(!ptr.is_null());
+        debug_assert_eq!(TYPE_SIGNATURE, (*ptr).TypeSignature);
+
+        &mut *ptr.cast()
+    }
+
+    /// The implementation, which is borrowed through a [`RefCell`] as all handles to the control
+    /// share it.
+    fn imp(&self) -> &RefCell<Box<dyn CustomControl>> {
+        // SAFETY: `imp` is only null after the control is destroyed, after which *libui-ng* must
+        // not call into it.
+        unsafe { &*self.imp }
+    }
+}
+
+unsafe extern "C" fn destroy(ptr: *mut uiControl) {
+    let raw = RawCustom::from_ptr(ptr);
+
+    // Drop the implementation, which releases the native widget, and then whatever the platform
+    // extension holds onto.
+    let handle = raw.imp().borrow().handle();
+    drop(Box::from_raw(mem::replace(&mut raw.imp, ptr::null_mut())));
+    raw.ext.release(handle);
+
+    uiFreeControl(ptr);
+}
+
+unsafe extern "C" fn handle(ptr: *mut uiControl) -> usize {
+    RawCustom::from_ptr(ptr).imp().borrow().handle() as usize
+}
+
+unsafe extern "C" fn parent(ptr: *mut uiControl) -> *mut uiControl {
+    RawCustom::from_ptr(ptr).parent
+}
+
+unsafe extern "C" fn set_parent(ptr: *mut uiControl, parent: *mut uiControl) {
+    // This reports a user bug if `ptr` is top-level or is being reparented without first being
+    // removed from its previous parent.
+    uiControlVerifySetParent(ptr, parent);
+
+    let raw = RawCustom::from_ptr(ptr);
+    raw.parent = parent;
+    raw.ext.set_parent(parent);
+    raw.imp().borrow_mut().set_parent(parent);
+}
+
+unsafe extern "C" fn toplevel(ptr: *mut uiControl) -> c_int {
+    RawCustom::from_ptr(ptr).imp().borrow().is_toplevel().into()
+}
+
+unsafe extern "C" fn visible(ptr: *mut uiControl) -> c_int {
+    RawCustom::from_ptr(ptr).is_visible.into()
+}
+
+unsafe extern "C" fn show(ptr: *mut uiControl) {
+    let raw = RawCustom::from_ptr(ptr);
+    raw.is_visible = true;
+    raw.ext.set_visible(true);
+    raw.imp().borrow_mut().set_visible(true);
+}
+
+unsafe extern "C" fn hide(ptr: *mut uiControl) {
+    let raw = RawCustom::from_ptr(ptr);
+    raw.is_visible = false;
+    raw.ext.set_visible(false);
+    raw.imp().borrow_mut().set_visible(false);
+}
+
+unsafe extern "C" fn enabled(ptr: *mut uiControl) -> c_int {
+    RawCustom::from_ptr(ptr).is_enabled.into()
+}
+
+unsafe extern "C" fn enable(ptr: *mut uiControl) {
+    let raw = RawCustom::from_ptr(ptr);
+    raw.is_enabled = true;
+    raw.ext.set_enabled(true);
+    raw.imp().borrow_mut().set_enabled(true);
+}
+
+unsafe extern "C" fn disable(ptr: *mut uiControl) {
+    let raw = RawCustom::from_ptr(ptr);
+    raw.is_enabled = false;
+    raw.ext.set_enabled(false);
+    raw.imp().borrow_mut().set_enabled(false);
+}
+
+def_subcontrol!(
+    docs: "
+        A control implemented in Rust that wraps an arbitrary native widget.
+
+        See [`CustomControl`] for more information.
+
+        # Examples
+
+        ```no_run
+        // TODO
+        ```
+    ",
+    ty: Custom,
+    kind: Custom,
+    handle: uiControl,
+);
+
+impl Custom<'_> {
+    /// The implementation backing this control.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the implementation is borrowed by [`Custom::implementation_mut`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn implementation(&self) -> Ref<'_, dyn CustomControl> {
+        Ref::map(self.raw().imp().borrow(), |imp| &**imp)
+    }
+
+    /// The mutable implementation backing this control.
+    ///
+    /// Showing, hiding, enabling, disabling, or reparenting this control calls into the
+    /// implementation, so none of these may be done until the returned borrow is dropped.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the implementation is already borrowed, including by *boing* as it calls
+    /// into the implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn implementation_mut(&self) -> RefMut<'_, dyn CustomControl> {
+        RefMut::map(self.raw().imp().borrow_mut(), |imp| &mut **imp)
+    }
+
+    fn raw(&self) -> &RawCustom {
+        // SAFETY: This control was created by [`Ui::create_custom_control`] and is still alive.
+        // Only shared references are derived here, as other handles may read it concurrently.
+        unsafe { &*self.as_ptr().cast() }
+    }
+}
@@ -0,0 +1,139 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! `uiDarwinControl`, which the Cocoa containers of *libui-ng* call into.
+
+use std::{
+    mem,
+    os::raw::{c_char, c_int, c_void},
+};
+
+use super::RawCustom;
+use crate::prelude::*;
+
+type Id = *mut c_void;
+type Sel = *mut c_void;
+/// An Objective-C `BOOL`, which is one byte wide on every Apple target.
+type Bool = i8;
+/// `NSLayoutPriority`.
+type Priority = f32;
+/// `NSLayoutConstraintOrientation`.
+type Orientation = isize;
+
+// `uiDarwinShouldStopSyncEnableState` is exported by *libui-ng*, and the Objective-C runtime is
+// resolved from the frameworks that *libui-ng* itself links to.
+extern "C" {
+    fn uiDarwinShouldStopSyncEnableState(control: *mut uiControl, enabled: c_int) -> c_int;
+    fn sel_registerName(name: *const c_char) -> Sel;
+    fn objc_msgSend();
+}
+
+/// Sends a message to `receiver` through `objc_msgSend`, cast to the signature `F` of the method.
+macro_rules! msg_send {
+    ($receiver:expr, $sel:literal as $f:ty $(, $arg:expr)* $(,)?) => {{
+        let send: $f = mem::transmute(objc_msgSend as unsafe extern "C" fn());
+        send($receiver, sel_registerName(concat!($sel, "\0").as_ptr().cast()) $(, $arg)*)
+    }};
+}
+
+/// The fields of `uiDarwinControl` that follow its `uiControl`.
+#[repr(C)]
+pub(super) struct Extension {
+    parent: *mut uiControl,
+    enabled: Bool,
+    visible: Bool,
+    sync_enable_state: Option<unsafe extern "C" fn(*mut uiControl, c_int)>,
+    set_superview: Option<unsafe extern "C" fn(*mut uiControl, Id)>,
+    hugs_trailing_edge: Option<unsafe extern "C" fn(*mut uiControl) -> Bool>,
+    hugs_bottom: Option<unsafe extern "C" fn(*mut uiControl) -> Bool>,
+    child_edge_cruft_changed: Option<unsafe extern "C" fn(*mut uiControl)>,
+    hugging_priority: Option<unsafe extern "C" fn(*mut uiControl, Orientation) -> Priority>,
+    set_hugging_priority: Option<unsafe extern "C" fn(*mut uiControl, Priority, Orientation)>,
+    child_visibility_changed: Option<unsafe extern "C" fn(*mut uiControl)>,
+}
+
+impl Extension {
+    pub(super) fn init(&mut self) {
+        self.enabled = 1;
+        self.visible = 1;
+        self.sync_enable_state = Some(sync_enable_state);
+        self.set_superview = Some(set_superview);
+        self.hugs_trailing_edge = Some(hugs);
+        self.hugs_bottom = Some(hugs);
+        self.child_edge_cruft_changed = Some(child_edge_cruft_changed);
+        self.hugging_priority = Some(hugging_priority);
+        self.set_hugging_priority = Some(set_hugging_priority);
+        self.child_visibility_changed = Some(child_visibility_changed);
+    }
+
+    pub(super) fn set_parent(&mut self, parent: *mut uiControl) {
+        self.parent = parent;
+    }
+
+    pub(super) fn set_visible(&mut self, value: bool) {
+        self.visible = value.into();
+    }
+
+    pub(super) fn set_enabled(&mut self, value: bool) {
+        self.enabled = value.into();
+    }
+
+    pub(super) unsafe fn release(&mut self, _handle: *mut c_void) {}
+}
+
+// The following mirror the `uiDarwinControlDefault*` implementations of *libui-ng*.
+
+unsafe extern "C" fn sync_enable_state(ptr: *mut uiControl, enabled: c_int) {
+    if uiDarwinShouldStopSyncEnableState(ptr, enabled) != 0 {
+        return;
+    }
+    RawCustom::from_ptr(ptr).imp().borrow_mut().set_enabled(enabled != 0);
+}
+
+unsafe extern "C" fn set_superview(ptr: *mut uiControl, superview: Id) {
+    let view = RawCustom::from_ptr(ptr).imp().borrow().handle();
+    msg_send!(
+        view,
+        "setTranslatesAutoresizingMaskIntoConstraints:" as unsafe extern "C" fn(Id, Sel, Bool),
+        0,
+    );
+    if superview.is_null() {
+        msg_send!(view, "removeFromSuperview" as unsafe extern "C" fn(Id, Sel));
+    } else {
+        msg_send!(superview, "addSubview:" as unsafe extern "C" fn(Id, Sel, Id), view);
+    }
+}
+
+unsafe extern "C" fn hugs(_: *mut uiControl) -> Bool {
+    1
+}
+
+unsafe extern "C" fn child_edge_cruft_changed(_: *mut uiControl) {
+    // Leaf controls have no children.
+}
+
+unsafe extern "C" fn hugging_priority(ptr: *mut uiControl, orientation: Orientation) -> Priority {
+    msg_send!(
+        RawCustom::from_ptr(ptr).imp().borrow().handle(),
+        "contentHuggingPriorityForOrientation:"
+            as unsafe extern "C" fn(Id, Sel, Orientation) -> Priority,
+        orientation,
+    )
+}
+
+unsafe extern "C" fn set_hugging_priority(
+    ptr: *mut uiControl,
+    priority: Priority,
+    orientation: Orientation,
+) {
+    msg_send!(
+        RawCustom::from_ptr(ptr).imp().borrow().handle(),
+        "setContentHuggingPriority:forOrientation:"
+            as unsafe extern "C" fn(Id, Sel, Priority, Orientation),
+        priority,
+        orientation,
+    );
+}
+
+unsafe extern "C" fn child_visibility_changed(_: *mut uiControl) {
+    // Leaf controls have no children.
+}
@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The mock backend, whose containers don't extend `uiControl`.
+
+use std::os::raw::c_void;
+
+use crate::prelude::*;
+
+#[repr(C)]
+pub(super) struct Extension;
+
+impl Extension {
+    pub(super) fn init(&mut self) {}
+
+    pub(super) fn set_parent(&mut self, _parent: *mut uiControl) {}
+
+    pub(super) fn set_visible(&mut self, _value: bool) {}
+
+    pub(super) fn set_enabled(&mut self, _value: bool) {}
+
+    pub(super) unsafe fn release(&mut self, _handle: *mut c_void) {}
+}
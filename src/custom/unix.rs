@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! `uiUnixControl`, which the GTK containers of *libui-ng* call into.
+
+use std::os::raw::{c_int, c_void};
+
+use super::RawCustom;
+use crate::prelude::*;
+
+// These are resolved from the GTK libraries that *libui-ng* itself links to.
+extern "C" {
+    fn g_object_ref_sink(object: *mut c_void) -> *mut c_void;
+    fn g_object_unref(object: *mut c_void);
+    fn gtk_container_add(container: *mut c_void, widget: *mut c_void);
+    fn gtk_container_remove(container: *mut c_void, widget: *mut c_void);
+    fn gtk_widget_show(widget: *mut c_void);
+}
+
+/// The fields of `uiUnixControl` that follow its `uiControl`.
+#[repr(C)]
+pub(super) struct Extension {
+    parent: *mut uiControl,
+    /// A `gboolean`.
+    added_before: c_int,
+    /// A `gboolean`.
+    explicitly_hidden: c_int,
+    set_container: Option<unsafe extern "C" fn(*mut uiControl, *mut c_void, c_int)>,
+}
+
+impl Extension {
+    pub(super) fn init(&mut self) {
+        self.set_container = Some(set_container);
+    }
+
+    pub(super) fn set_parent(&mut self, parent: *mut uiControl) {
+        self.parent = parent;
+    }
+
+    pub(super) fn set_visible(&mut self, value: bool) {
+        self.explicitly_hidden = (!value).into();
+    }
+
+    pub(super) fn set_enabled(&mut self, _value: bool) {}
+
+    /// Releases the reference to the native widget taken when it was first added to a container.
+    pub(super) unsafe fn release(&mut self, handle: *mut c_void) {
+        if self.added_before != 0 {
+            g_object_unref(handle);
+        }
+    }
+}
+
+/// This mirrors `uiUnixControlDefaultSetContainer`.
+unsafe extern "C" fn set_container(ptr: *mut uiControl, container: *mut c_void, remove: c_int) {
+    let raw = RawCustom::from_ptr(ptr);
+    let widget = raw.imp().borrow().handle();
+    if raw.ext.added_before == 0 {
+        // Containers hold the widget weakly, so the control keeps a reference of its own.
+        g_object_ref_sink(widget);
+        if raw.ext.explicitly_hidden == 0 {
+            gtk_widget_show(widget);
+        }
+        raw.ext.added_before = 1;
+    }
+    if remove != 0 {
+        gtk_container_remove(container, widget);
+    } else {
+        gtk_container_add(container, widget);
+    }
+}
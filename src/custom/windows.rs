@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! `uiWindowsControl`, which the Win32 containers of *libui-ng* call into.
+
+use std::os::raw::{c_int, c_long, c_void};
+
+use super::RawCustom;
+use crate::prelude::*;
+
+type Hwnd = *mut c_void;
+
+#[repr(C)]
+struct Rect {
+    left: c_long,
+    top: c_long,
+    right: c_long,
+    bottom: c_long,
+}
+
+// The `uiWindows*` helpers are exported by *libui-ng*, and `EnableWindow` is resolved from the
+// system libraries that *libui-ng* itself links to.
+extern "C" {
+    fn uiWindowsEnsureAssignControlIDZOrder(hwnd: Hwnd, control_id: *mut isize, after: *mut Hwnd);
+    fn uiWindowsEnsureGetWindowRect(hwnd: Hwnd, rect: *mut Rect);
+    fn uiWindowsEnsureSetParentHWND(hwnd: Hwnd, parent: Hwnd);
+    fn uiWindowsShouldStopSyncEnableState(control: *mut uiControl, enabled: c_int) -> c_int;
+    fn uiWindowsControlTooSmall(control: *mut uiControl) -> c_int;
+    fn uiWindowsControlContinueMinimumSizeChanged(control: *mut uiControl);
+}
+
+extern "system" {
+    fn EnableWindow(hwnd: Hwnd, enable: c_int) -> c_int;
+}
+
+/// The fields of `uiWindowsControl` that follow its `uiControl`.
+#[repr(C)]
+pub(super) struct Extension {
+    parent: *mut uiControl,
+    /// A `BOOL`.
+    enabled: c_int,
+    /// A `BOOL`.
+    visible: c_int,
+    sync_enable_state: Option<unsafe extern "C" fn(*mut uiControl, c_int)>,
+    set_parent_hwnd: Option<unsafe extern "C" fn(*mut uiControl, Hwnd)>,
+    minimum_size: Option<unsafe extern "C" fn(*mut uiControl, *mut c_int, *mut c_int)>,
+    minimum_size_changed: Option<unsafe extern "C" fn(*mut uiControl)>,
+    layout_rect: Option<unsafe extern "C" fn(*mut uiControl, *mut Rect)>,
+    assign_control_id_z_order: Option<unsafe extern "C" fn(*mut uiControl, *mut isize, *mut Hwnd)>,
+    child_visibility_changed: Option<unsafe extern "C" fn(*mut uiControl)>,
+}
+
+impl Extension {
+    pub(super) fn init(&mut self) {
+        self.enabled = 1;
+        self.visible = 1;
+        self.sync_enable_state = Some(sync_enable_state);
+        self.set_parent_hwnd = Some(set_parent_hwnd);
+        self.minimum_size = Some(minimum_size);
+        self.minimum_size_changed = Some(minimum_size_changed);
+        self.layout_rect = Some(layout_rect);
+        self.assign_control_id_z_order = Some(assign_control_id_z_order);
+        self.child_visibility_changed = Some(child_visibility_changed);
+    }
+
+    pub(super) fn set_parent(&mut self, parent: *mut uiControl) {
+        self.parent = parent;
+    }
+
+    pub(super) fn set_visible(&mut self, value: bool) {
+        self.visible = value.into();
+    }
+
+    pub(super) fn set_enabled(&mut self, value: bool) {
+        self.enabled = value.into();
+    }
+
+    pub(super) unsafe fn release(&mut self, _handle: *mut c_void) {}
+}
+
+// The following mirror the `uiWindowsControlDefault*` implementations of *libui-ng*.
+
+unsafe extern "C" fn sync_enable_state(ptr: *mut uiControl, enabled: c_int) {
+    if uiWindowsShouldStopSyncEnableState(ptr, enabled) != 0 {
+        return;
+    }
+    EnableWindow(RawCustom::from_ptr(ptr).imp().borrow().handle(), enabled);
+}
+
+unsafe extern "C" fn set_parent_hwnd(ptr: *mut uiControl, parent: Hwnd) {
+    uiWindowsEnsureSetParentHWND(RawCustom::from_ptr(ptr).imp().borrow().handle(), parent);
+}
+
+unsafe extern "C" fn minimum_size(ptr: *mut uiControl, width: *mut c_int, height: *mut c_int) {
+    (*width, *height) = RawCustom::from_ptr(ptr).imp().borrow().minimum_size();
+}
+
+unsafe extern "C" fn minimum_size_changed(ptr: *mut uiControl) {
+    // Leaf controls only need to inform their ancestors.
+    if uiWindowsControlTooSmall(ptr) != 0 {
+        uiWindowsControlContinueMinimumSizeChanged(ptr);
+    }
+}
+
+unsafe extern "C" fn layout_rect(ptr: *mut uiControl, rect: *mut Rect) {
+    uiWindowsEnsureGetWindowRect(RawCustom::from_ptr(ptr).imp().borrow().handle(), rect);
+}
+
+unsafe extern "C" fn assign_control_id_z_order(
+    ptr: *mut uiControl,
+    control_id: *mut isize,
+    after: *mut Hwnd,
+) {
+    let hwnd = RawCustom::from_ptr(ptr).imp().borrow().handle();
+    uiWindowsEnsureAssignControlIDZOrder(hwnd, control_id, after);
+}
+
+unsafe extern "C" fn child_visibility_changed(_: *mut uiControl) {
+    // Leaf controls have no children.
+}
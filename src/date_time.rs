@@ -0,0 +1,347 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! [`Picker`] and the [`DateTime`] it displays.
+
+use std::{fmt, mem, os::raw::c_int};
+
+use crate::prelude::*;
+
+macro_rules! impl_picker {
+    (
+        docs: $docs:literal,
+        self: { fn: $self_fn:ident() $(,)? },
+        libui: { fn: $libui_fn:ident() $(,)? } $(,)?
+    ) => {
+        impl Ui {
+            #[doc = indoc::indoc!($docs)]
+            pub fn $self_fn(&self) -> Result<&Picker, crate::Error> {
+                call_libui_new_fn!(
+                    ui: self,
+                    fn: $libui_fn() -> Picker,
+                )
+            }
+        }
+    };
+}
+
+impl_picker!(
+    docs: "
+        Creates a new [`Picker`] of both a date and a time of day.
+
+        # Examples
+
+        ```no_run
+        // TODO
+        ```
+    ",
+    self: { fn: create_date_time_picker() },
+    libui: { fn: uiNewDateTimePicker() },
+);
+
+impl_picker!(
+    docs: "
+        Creates a new [`Picker`] of a date.
+
+        The time of day of [`Picker::time`] is unspecified.
+
+        # Examples
+
+        ```no_run
+        # fn main() -> Result<(), boing::Error> {
+        use boing::{DateTime, Ui};
+
+        let ui: Ui;
+        # ui = Ui::new()?;
+
+        let due = ui.create_date_picker()?;
+        due.set_time(DateTime::from_date(2022, 3, 14).unwrap());
+        due.on_changed(|due| {
+            let date = due.time();
+            println!(\"Due on {}-{:02}-{:02}\", date.year(), date.month(), date.day());
+        });
+        #
+        # Ok(())
+        # }
+        ```
+    ",
+    self: { fn: create_date_picker() },
+    libui: { fn: uiNewDatePicker() },
+);
+
+impl_picker!(
+    docs: "
+        Creates a new [`Picker`] of a time of day.
+
+        The date of [`Picker::time`] is unspecified.
+
+        # Examples
+
+        ```no_run
+        // TODO
+        ```
+    ",
+    self: { fn: create_time_picker() },
+    libui: { fn: uiNewTimePicker() },
+);
+
+def_subcontrol!(
+    docs: "
+        A control for picking a date, a time of day, or both.
+
+        # Examples
+
+        ```no_run
+        // TODO
+        ```
+    ",
+    ty: Picker,
+    kind: DateTimePicker,
+    handle: uiDateTimePicker,
+    cb_fns: [ on_changed() ],
+);
+
+impl<'ui> Picker<'ui> {
+    /// The date and time displayed by this picker.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn time(&self) -> DateTime {
+        // SAFETY: `struct tm` is plain old data, so all-zeroes is a valid value.
+        let mut tm: tm = unsafe { mem::zeroed() };
+        unsafe { uiDateTimePickerTime(self.as_ptr(), &mut tm) };
+
+        DateTime::from_tm(&tm)
+    }
+
+    /// Sets the date and time displayed by this picker.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn set_time(&self, value: DateTime) {
+        let tm = value.to_tm();
+        unsafe { uiDateTimePickerSetTime(self.as_ptr(), &tm) };
+    }
+
+    bind_callback_fn!(
+        docs: "
+            Sets a callback for when the date or time displayed by this picker changes.
+
+            This callback is unset by default.
+
+            # Examples
+
+            ```no_run
+            // TODO
+            ```
+        ",
+        self: {
+            ty: Picker<'ui>,
+            handle: uiDateTimePicker,
+            fn: on_changed(),
+            emit: emit_changed(),
+            cb: { sig: f -> () },
+        },
+        libui: {
+            fn: uiDateTimePickerOnChanged(),
+            cb: { sig: () -> () },
+        },
+    );
+
+    /// Simulates a change, setting the date and time of this picker and then calling the callback
+    /// set by [`Picker::on_changed`] as though the user had picked `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn simulate_change(&self, value: DateTime) {
+        self.set_time(value);
+        self.emit_changed();
+    }
+}
+
+/// A calendar date and time of day, without a time zone.
+///
+/// Every [`DateTime`] is valid: months are in `1..=12`, days exist in their month, hours are in
+/// `0..=23`, and minutes and seconds are in `0..=59`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct DateTime {
+    year: i32,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+}
+
+impl Default for DateTime {
+    /// Midnight on January 1, 1970.
+    fn default() -> Self {
+        Self {
+            year: 1970,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 0,
+        }
+    }
+}
+
+impl DateTime {
+    /// Creates a new [`DateTime`], or `None` if any component is out of range.
+    ///
+    /// `month` and `day` are one-based.
+    pub fn new(year: i32, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> Option<Self> {
+        let is_valid = (1..=12).contains(&month)
+            && (1..=days_in_month(year, month)).contains(&day)
+            && hour < 24
+            && minute < 60
+            && second < 60;
+
+        is_valid.then_some(Self {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        })
+    }
+
+    /// Creates a new [`DateTime`] at midnight of the given date, or `None` if the date does not
+    /// exist.
+    pub fn from_date(year: i32, month: u8, day: u8) -> Option<Self> {
+        Self::new(year, month, day, 0, 0, 0)
+    }
+
+    /// The year, e.g., 2022.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// The month, in `1..=12`.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// The day of the month, starting at 1.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// The hour, in `0..=23`.
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// The minute, in `0..=59`.
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// The second, in `0..=59`.
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// The day of the year, in `1..=366`.
+    pub fn ordinal(&self) -> u16 {
+        let preceding: u16 = (1..self.month)
+            .map(|month| u16::from(days_in_month(self.year, month)))
+            .sum();
+
+        preceding + u16::from(self.day)
+    }
+
+    /// The day of the week, where Sunday is 0 and Saturday is 6.
+    pub fn weekday(&self) -> u8 {
+        // Sakamoto's method.
+        const OFFSETS: [i64; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+
+        let mut year = i64::from(self.year);
+        if self.month < 3 {
+            year -= 1;
+        }
+        let day = year + year.div_euclid(4) - year.div_euclid(100)
+            + year.div_euclid(400)
+            + OFFSETS[usize::from(self.month - 1)]
+            + i64::from(self.day);
+
+        day.rem_euclid(7) as u8
+    }
+
+    /// Converts a `struct tm` from *libui-ng*.
+    ///
+    /// Out-of-range components are clamped rather than normalized, as *libui-ng* only ever returns
+    /// normalized values.
+    fn from_tm(tm: &tm) -> Self {
+        let year = tm.tm_year.saturating_add(1900);
+        let month = clamp_component(tm.tm_mon.saturating_add(1), 1, 12);
+        let day = clamp_component(tm.tm_mday, 1, days_in_month(year, month));
+
+        Self {
+            year,
+            month,
+            day,
+            hour: clamp_component(tm.tm_hour, 0, 23),
+            minute: clamp_component(tm.tm_min, 0, 59),
+            // `tm_sec` may be 60 to account for leap seconds.
+            second: clamp_component(tm.tm_sec, 0, 59),
+        }
+    }
+
+    /// Converts this date and time to a `struct tm` for *libui-ng*.
+    pub(crate) fn to_tm(self) -> tm {
+        // SAFETY: `struct tm` is plain old data, so all-zeroes is a valid value. This also leaves
+        // any platform-specific fields, like `tm_gmtoff` and `tm_zone`, in a sensible state.
+        let mut tm: tm = unsafe { mem::zeroed() };
+        tm.tm_year = self.year.saturating_sub(1900);
+        tm.tm_mon = c_int::from(self.month) - 1;
+        tm.tm_mday = self.day.into();
+        tm.tm_hour = self.hour.into();
+        tm.tm_min = self.minute.into();
+        tm.tm_sec = self.second.into();
+        tm.tm_wday = self.weekday().into();
+        tm.tm_yday = c_int::from(self.ordinal()) - 1;
+        // Let the platform decide whether or not daylight saving time is in effect.
+        tm.tm_isdst = -1;
+
+        tm
+    }
+}
+
+impl fmt::Display for DateTime {
+    /// Formats this date and time in ISO 8601 format, e.g., `2022-03-14T15:09:26`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second,
+        )
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn clamp_component(value: c_int, min: u8, max: u8) -> u8 {
+    value.clamp(min.into(), max.into()) as u8
+}
@@ -0,0 +1,522 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Modal dialogs that return what the user chose.
+//!
+//! A [`Dialog`] is a temporary [`Window`] that asks a question on behalf of a parent window. While
+//! it is open, the parent is disabled and the event loop runs within the call that opened the
+//! dialog, which returns once the user has answered. Closing the dialog through its title bar is
+//! the same as clicking [`Button::Cancel`].
+//!
+//! While a dialog is open, every other window is disabled, and events from outside the dialog,
+//! e.g., from menu items, are ignored, so no other callback can run in the middle of the one that
+//! opened the dialog. A dialog may still be opened from within another, e.g., by a button in the
+//! content of [`Dialog::custom`].
+//!
+//! While a dialog is open, its buttons have the IDs returned by [`Button::id`], and the entry of
+//! [`Dialog::input`] has the ID `"dialog.input"`, so that they may be found with [`Ui::find`].
+//!
+//! The windows of closed dialogs are kept and reused by later dialogs, so opening a dialog doesn't
+//! allocate new controls every time.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> Result<(), boing::Error> {
+//! use boing::{
+//!     dialog::{Button, Dialog},
+//!     Ui,
+//! };
+//!
+//! let ui = Ui::new()?;
+//! let window = ui.create_window("Notes", 640, 480, false, false)?;
+//! window.on_closing(|window| {
+//!     let answer = Dialog::new(window, "Unsaved Changes")
+//!         .message("Save changes before closing?")
+//!         .button_text(Button::Yes, "Save")
+//!         .button_text(Button::No, "Don't Save")
+//!         .confirm();
+//!     match answer {
+//!         Ok(Button::Yes) => { /* Save, then quit. */ }
+//!         Ok(Button::No) => { /* Quit. */ }
+//!         _ => { /* Keep editing. */ }
+//!     }
+//! });
+//!
+//! let rename = ui.create_pushbutton("Rename...")?;
+//! rename.on_clicked(|_| {
+//!     let name = Dialog::new(window, "Rename")
+//!         .message("New name:")
+//!         .input("Untitled");
+//!     if let Ok(Some(name)) = name {
+//!         println!("Renamed to {}", name);
+//!     }
+//! });
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Window`]: crate::Window
+//! [`Ui::find`]: crate::Ui::find
+
+use std::{
+    cell::{Cell, RefCell},
+    ptr::{self, NonNull},
+    rc::Rc,
+};
+
+use crate::{callback::Target, prelude::*, Axis, ControlKind, Label, Pushbutton, TextEntry, Window};
+
+/// The ID of the entry of [`Dialog::input`] while it is open.
+const INPUT_ID: &str = "dialog.input";
+
+/// A button at the bottom of a [`Dialog`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Button {
+    Ok,
+    Cancel,
+    Yes,
+    No,
+}
+
+impl Button {
+    /// The ID of this button while a dialog is open, e.g., `"dialog.ok"`.
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::Ok => "dialog.ok",
+            Self::Cancel => "dialog.cancel",
+            Self::Yes => "dialog.yes",
+            Self::No => "dialog.no",
+        }
+    }
+
+    /// The text of this button, unless overridden with [`Dialog::button_text`].
+    pub fn default_text(self) -> &'static str {
+        match self {
+            Self::Ok => "OK",
+            Self::Cancel => "Cancel",
+            Self::Yes => "Yes",
+            Self::No => "No",
+        }
+    }
+}
+
+/// A builder for a modal dialog.
+///
+/// A dialog is configured with the chainable methods, and then opened with one of the methods that
+/// return a result: [`Dialog::confirm`], [`Dialog::choose`], [`Dialog::input`], or
+/// [`Dialog::custom`]. See the [module documentation](crate::dialog) for an example.
+#[derive(Clone)]
+pub struct Dialog<'a, 'ui> {
+    parent: &'a Window<'ui>,
+    title: String,
+    message: String,
+    width: u16,
+    height: u16,
+    button_texts: Vec<(Button, String)>,
+}
+
+impl<'a, 'ui> Dialog<'a, 'ui> {
+    /// Creates a new dialog with the given title, which belongs to `parent`.
+    pub fn new(parent: &'a Window<'ui>, title: impl Into<String>) -> Self {
+        Self {
+            parent,
+            title: title.into(),
+            message: String::new(),
+            width: 360,
+            height: 120,
+            button_texts: Vec::new(),
+        }
+    }
+
+    /// Sets the text shown above the content and buttons of the dialog.
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Sets the initial size of the dialog.
+    ///
+    /// The default is 360x120 pixels.
+    pub fn size(mut self, width: u16, height: u16) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Sets the text of a button, e.g., to translate it or to name the action it takes.
+    pub fn button_text(mut self, button: Button, text: impl Into<String>) -> Self {
+        self.button_texts.retain(|(other, _)| *other != button);
+        self.button_texts.push((button, text.into()));
+        self
+    }
+
+    /// Asks a question that may be answered with [`Button::Yes`], [`Button::No`], or
+    /// [`Button::Cancel`], returning the answer.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn confirm(self) -> Result<Button, crate::Error> {
+        self.choose(&[Button::Yes, Button::No, Button::Cancel])
+            .map(|button| button.unwrap_or(Button::Cancel))
+    }
+
+    /// Shows the given buttons, in order, returning the one that was clicked, or `None` if the
+    /// dialog was closed instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn choose(self, buttons: &[Button]) -> Result<Option<Button>, crate::Error> {
+        self.run(Content::None, buttons, |_, button| Some(Some(button)))
+    }
+
+    /// Asks for a line of text, which is initially `initial`, returning it if the user clicks
+    /// [`Button::Ok`], or `None` if the dialog was canceled.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn input(self, initial: impl AsRef<str>) -> Result<Option<String>, crate::Error> {
+        self.run(
+            Content::Input(initial.as_ref()),
+            &[Button::Ok, Button::Cancel],
+            |shell, button| match button {
+                Button::Ok => Some(Some(shell.entry.text())),
+                _ => Some(None),
+            },
+        )
+    }
+
+    /// Shows the given control above [`Button::Ok`] and [`Button::Cancel`], returning the result
+    /// of `on_ok` if the user clicks OK, or `None` if the dialog was canceled.
+    ///
+    /// `on_ok` is called when OK is clicked, and may return `None` to keep the dialog open, e.g.,
+    /// because the content is invalid.
+    ///
+    /// The content is detached from the dialog when it closes, so it may be reused.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), boing::Error> {
+    /// use boing::{dialog::Dialog, Ui};
+    ///
+    /// let ui = Ui::new()?;
+    /// let window = ui.create_window("Stopwatch", 320, 240, false, true)?;
+    ///
+    /// let slider = ui.create_slider(1, 60)?;
+    /// let minutes = Dialog::new(window, "Set Timer")
+    ///     .message("Minutes:")
+    ///     .custom(slider, || Some(slider.value()))?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn custom<T>(
+        self,
+        content: &impl Deref<Target = Control<'ui>>,
+        mut on_ok: impl FnMut() -> Option<T>,
+    ) -> Result<Option<T>, crate::Error> {
+        self.run(
+            Content::Custom(content),
+            &[Button::Ok, Button::Cancel],
+            |_, button| match button {
+                Button::Ok => on_ok().map(Some),
+                _ => Some(None),
+            },
+        )
+    }
+
+    /// Opens the dialog and runs the event loop until `on_click` returns a result for a button
+    /// click, or the dialog is closed.
+    fn run<T>(
+        self,
+        content: Content<'_, 'ui>,
+        buttons: &[Button],
+        mut on_click: impl FnMut(&Shell<'ui>, Button) -> Option<Option<T>>,
+    ) -> Result<Option<T>, crate::Error> {
+        let ui = self.parent.ui();
+        let mut open = Open::new(ui, Shell::take(ui)?);
+        open.show(&self, content, buttons)?;
+
+        let shell = open.shell;
+        let result = loop {
+            match shell.event.take() {
+                Some(Some(button)) => {
+                    if let Some(result) = on_click(shell, button) {
+                        break result;
+                    }
+                }
+                Some(None) => break None,
+                None => {}
+            }
+            // The loop must also end if the application quits.
+            if !ui.step_blocking() {
+                break None;
+            }
+        };
+
+        Ok(result)
+    }
+}
+
+/// What a [`Dialog`] shows between its message and its buttons.
+enum Content<'a, 'ui> {
+    None,
+    /// The entry of the shell, with the given text.
+    Input(&'a str),
+    Custom(&'a Control<'ui>),
+}
+
+/// The dialogs of a [`Ui`].
+pub(crate) struct Dialogs {
+    // Shells are allocated in the arena of the [`Ui`], so they live for as long as it does. Their
+    // lifetime is erased, as a [`Ui`] can't name its own.
+    shells: RefCell<Vec<NonNull<Shell<'static>>>>,
+    /// The window of the innermost open dialog, or null if no dialog is open.
+    modal: Cell<*mut uiControl>,
+}
+
+impl Default for Dialogs {
+    fn default() -> Self {
+        Self {
+            shells: RefCell::default(),
+            modal: Cell::new(ptr::null_mut()),
+        }
+    }
+}
+
+impl Ui {
+    /// Determines if events from the given widget are ignored because a dialog that doesn't
+    /// contain it is open.
+    pub(crate) fn is_blocked_by_dialog(&self, target: &impl Target) -> bool {
+        let modal = self.dialogs.modal.get();
+
+        !modal.is_null() && target.window() != modal
+    }
+}
+
+/// The controls of a dialog, which are reused by later dialogs once it closes.
+///
+/// Controls live for as long as the [`Ui`] that created them, so creating new ones for every dialog
+/// would use more memory with every dialog.
+pub(crate) struct Shell<'ui> {
+    window: &'ui Window<'ui>,
+    message: &'ui Label<'ui>,
+    /// Holds the content of the dialog, if any.
+    content: &'ui Axis<'ui>,
+    /// The content of [`Dialog::input`].
+    entry: &'ui TextEntry<'ui>,
+    /// Holds a spacer followed by the buttons that the dialog shows.
+    row: &'ui Axis<'ui>,
+    buttons: Vec<(Button, &'ui Pushbutton<'ui>)>,
+    /// The last button clicked, or `Some(None)` if the dialog was closed.
+    event: Rc<Cell<Option<Option<Button>>>>,
+    is_open: Cell<bool>,
+}
+
+impl<'ui> Shell<'ui> {
+    /// Takes a closed shell, creating one if there is none.
+    fn take(ui: &'ui Ui) -> Result<&'ui Self, crate::Error> {
+        let closed = ui
+            .dialogs
+            .shells
+            .borrow()
+            .iter()
+            // SAFETY: See [`Dialogs::shells`].
+            .map(|shell| unsafe { shell.cast::<Self>().as_ref() })
+            .find(|shell| !shell.is_open.get());
+        let shell = match closed {
+            Some(shell) => shell,
+            None => {
+                let shell: &Self = ui.alloc_object(Self::new(ui)?);
+                ui.dialogs
+                    .shells
+                    .borrow_mut()
+                    .push(NonNull::from(shell).cast());
+
+                shell
+            }
+        };
+        shell.is_open.set(true);
+
+        Ok(shell)
+    }
+
+    fn new(ui: &'ui Ui) -> Result<Self, crate::Error> {
+        let event = Rc::new(Cell::new(None));
+
+        let window = ui.create_window("", 0, 0, false, false)?;
+        window.set_margined(true);
+        window.on_closing({
+            let event = Rc::clone(&event);
+            move |_| event.set(Some(None))
+        });
+
+        let layout = ui.create_vertical_axis()?;
+        layout.set_padded(true);
+        let message = ui.create_label("")?;
+        layout.push_new_child(message, false);
+        let content = ui.create_vertical_axis()?;
+        layout.push_new_child(content, true);
+        let row = ui.create_horizontal_axis()?;
+        row.set_padded(true);
+        // Buttons are aligned to the right.
+        row.push_new_child(ui.create_label("")?, true);
+        layout.push_new_child(row, false);
+        window.set_child(layout);
+
+        let mut buttons = Vec::new();
+        for button in [Button::Ok, Button::Cancel, Button::Yes, Button::No] {
+            let pushbutton = ui.create_pushbutton(button.default_text())?;
+            pushbutton.on_clicked({
+                let event = Rc::clone(&event);
+                move |_| event.set(Some(Some(button)))
+            });
+            buttons.push((button, pushbutton));
+        }
+
+        Ok(Self {
+            window,
+            message,
+            content,
+            entry: ui.create_text_entry()?,
+            row,
+            buttons,
+            event,
+            is_open: Cell::new(false),
+        })
+    }
+
+    fn pushbutton(&self, button: Button) -> &'ui Pushbutton<'ui> {
+        self.buttons
+            .iter()
+            .find(|(other, _)| *other == button)
+            .map(|(_, pushbutton)| *pushbutton)
+            .expect("shell has every button")
+    }
+}
+
+/// An open dialog, which is closed when this is dropped.
+struct Open<'ui> {
+    ui: &'ui Ui,
+    shell: &'ui Shell<'ui>,
+    /// The window of the dialog that was open before this one, if any.
+    outer: *mut uiControl,
+    /// The windows that were disabled when this dialog opened.
+    ///
+    /// They are looked up again when the dialog closes, as any of them may have been destroyed
+    /// while it was open.
+    disabled: Vec<*mut uiControl>,
+    has_content: bool,
+    ids: Vec<&'static str>,
+}
+
+impl<'ui> Open<'ui> {
+    fn new(ui: &'ui Ui, shell: &'ui Shell<'ui>) -> Self {
+        shell.event.set(None);
+
+        Self {
+            ui,
+            shell,
+            outer: ui.dialogs.modal.get(),
+            disabled: Vec::new(),
+            has_content: false,
+            ids: Vec::new(),
+        }
+    }
+
+    /// Shows the dialog, disabling every other window.
+    fn show(
+        &mut self,
+        dialog: &Dialog<'_, 'ui>,
+        content: Content<'_, 'ui>,
+        buttons: &[Button],
+    ) -> Result<(), crate::Error> {
+        let shell = self.shell;
+        shell.window.set_title(&dialog.title)?;
+        shell.window.set_content_size(dialog.width, dialog.height);
+        shell.message.set_text(&dialog.message)?;
+        if dialog.message.is_empty() {
+            shell.message.hide();
+        } else {
+            shell.message.show();
+        }
+
+        let content = match content {
+            Content::None => None,
+            Content::Input(initial) => {
+                shell.entry.set_text(initial)?;
+                self.ids.push(INPUT_ID);
+                self.ui.set_id(INPUT_ID, shell.entry)?;
+
+                Some(&**shell.entry)
+            }
+            Content::Custom(content) => Some(content),
+        };
+        if let Some(content) = content {
+            shell.content.append(content, true);
+            self.has_content = true;
+        }
+
+        // The buttons of the previous dialog are removed, leaving the spacer.
+        for _ in 1..shell.row.child_count() {
+            shell.row.remove_child(1);
+        }
+        for &button in buttons {
+            let text = dialog
+                .button_texts
+                .iter()
+                .find(|(other, _)| *other == button)
+                .map_or(button.default_text(), |(_, text)| text.as_str());
+            let pushbutton = shell.pushbutton(button);
+            pushbutton.set_text(text)?;
+            shell.row.append(pushbutton, false);
+            self.ids.push(button.id());
+            self.ui.set_id(button.id(), pushbutton)?;
+        }
+
+        // Every window is registered, including the parent.
+        let own: &Control<'ui> = shell.window;
+        for window in self.ui.registry.controls_of_kind(ControlKind::Window) {
+            let Some(window) = self.ui.handle_of(window) else {
+                continue;
+            };
+            if window.as_ptr() != own.as_ptr() && window.is_enabled() {
+                window.disable();
+                self.disabled.push(window.as_ptr());
+            }
+        }
+        self.ui.dialogs.modal.set(own.as_ptr());
+        shell.window.show();
+
+        Ok(())
+    }
+}
+
+impl Drop for Open<'_> {
+    fn drop(&mut self) {
+        let shell = self.shell;
+        shell.window.hide();
+        self.ui.dialogs.modal.set(self.outer);
+        for &window in &self.disabled {
+            if let Some(window) = self.ui.handle_of(window) {
+                window.enable();
+            }
+        }
+        for id in &self.ids {
+            self.ui.remove_id(id);
+        }
+        if self.has_content {
+            shell.content.remove_child(0);
+        }
+        shell.is_open.set(false);
+    }
+}
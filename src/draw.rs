@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Vector drawing, as performed by an [`AreaHandler`].
+//!
+//! Drawing is expressed in terms of [`Path`]s, which are filled or stroked with a [`Brush`] through
+//! a [`DrawContext`]. A [`Canvas`] is a [`DrawContext`] that rasterizes into an in-memory bitmap,
+//! which allows drawing code to run without a window, e.g., to render thumbnails or to compare
+//! the output of custom widgets against golden images in tests. A [`DisplayList`] instead records
+//! drawing operations, which may be inspected, replayed, or exported to SVG.
+//!
+//! Text is laid out with a [`TextLayout`]. Only the contexts backed by *libui-ng* shape text
+//! properly; the others estimate its size.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use boing::{
+//!     draw::{Brush, Canvas, DrawContext, FillMode, Path, StrokeParams},
+//!     Color,
+//! };
+//!
+//! let mut path = Path::new(FillMode::Winding);
+//! path.add_rectangle(8.0, 8.0, 48.0, 48.0);
+//!
+//! let mut canvas = Canvas::new(64, 64);
+//! canvas.fill(&path, &Brush::Solid(Color::WHITE));
+//! canvas.stroke(&path, &Brush::Solid(Color::BLACK), &StrokeParams::default());
+//!
+//! let bitmap = canvas.to_bitmap();
+//! assert_eq!([0xff, 0xff, 0xff, 0xff], bitmap.pixels[4 * (32 * 64 + 32)..][..4]);
+//! ```
+//!
+//! [`AreaHandler`]: crate::AreaHandler
+
+mod canvas;
+mod display_list;
+mod matrix;
+pub(crate) mod native;
+mod path;
+mod text;
+
+pub use canvas::Canvas;
+pub use display_list::{DisplayList, Op};
+pub use matrix::Matrix;
+pub use path::{Arc, Command, FillMode, Path};
+pub use text::{TextAlign, TextLayout};
+
+use crate::Color;
+
+/// A target for drawing operations.
+///
+/// The methods of this trait correspond to the drawing functions of *libui-ng*.
+pub trait DrawContext {
+    /// Fills the interior of a path.
+    fn fill(&mut self, path: &Path, brush: &Brush);
+
+    /// Strokes the outline of a path.
+    fn stroke(&mut self, path: &Path, brush: &Brush, params: &StrokeParams);
+
+    /// Multiplies the current transformation by the given matrix.
+    ///
+    /// The given transformation is applied to the coordinates of subsequent operations before the
+    /// current transformation.
+    fn transform(&mut self, matrix: &Matrix);
+
+    /// Intersects the current clipping region with the interior of a path.
+    fn clip(&mut self, path: &Path);
+
+    /// Pushes the current transformation and clipping region onto a stack.
+    fn save(&mut self);
+
+    /// Pops the transformation and clipping region most recently pushed by
+    /// [`DrawContext::save`].
+    fn restore(&mut self);
+
+    /// Draws text with the top-left corner of its layout at `(x, y)`.
+    fn draw_text(&mut self, layout: &TextLayout, x: f64, y: f64);
+
+    /// The width and height of the given text, as it would be drawn by
+    /// [`DrawContext::draw_text`].
+    ///
+    /// By default, this is [`TextLayout::estimated_size`].
+    fn measure_text(&mut self, layout: &TextLayout) -> (f64, f64) {
+        layout.estimated_size()
+    }
+}
+
+/// The paint with which paths are filled and stroked.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Brush {
+    /// A single color.
+    Solid(Color),
+    /// A gradient that varies along the line from `start` to `end`.
+    LinearGradient {
+        start: (f64, f64),
+        end: (f64, f64),
+        stops: Vec<GradientStop>,
+    },
+    /// A gradient that varies from the point `start` to the circle centered at `outer_center`.
+    RadialGradient {
+        start: (f64, f64),
+        outer_center: (f64, f64),
+        outer_radius: f64,
+        stops: Vec<GradientStop>,
+    },
+}
+
+impl Brush {
+    /// The color of this brush at the given position along its gradient.
+    ///
+    /// `t` is clamped to the range `0.0..=1.0`, and solid brushes have the same color everywhere.
+    pub fn color_at(&self, t: f64) -> Color {
+        let stops = match self {
+            Self::Solid(color) => return *color,
+            Self::LinearGradient { stops, .. } | Self::RadialGradient { stops, .. } => stops,
+        };
+
+        let t = t.clamp(0.0, 1.0);
+        let after = match stops.iter().position(|stop| stop.pos >= t) {
+            Some(0) => return stops[0].color,
+            Some(after) => after,
+            None => return stops.last().map_or(Color::TRANSPARENT, |stop| stop.color),
+        };
+        let (before, after) = (&stops[after - 1], &stops[after]);
+        let span = after.pos - before.pos;
+        if span <= 0.0 {
+            return after.color;
+        }
+
+        before.color.mix(after.color, (t - before.pos) / span)
+    }
+}
+
+/// A color at a position along a gradient.
+///
+/// Stops must be sorted by position, which is nominally in the range `0.0..=1.0`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GradientStop {
+    pub pos: f64,
+    pub color: Color,
+}
+
+/// Parameters that control how paths are stroked.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StrokeParams {
+    pub cap: LineCap,
+    pub join: LineJoin,
+    /// The width of the stroke.
+    pub thickness: f64,
+    /// The ratio of the length of a miter to [`StrokeParams::thickness`] beyond which a miter join
+    /// is drawn as a bevel instead.
+    pub miter_limit: f64,
+    /// Alternating lengths of dashes and gaps, or empty for a solid stroke.
+    pub dashes: Vec<f64>,
+    /// The distance into [`StrokeParams::dashes`] at which strokes start.
+    pub dash_phase: f64,
+}
+
+impl StrokeParams {
+    /// The miter limit used by *libui-ng* by default.
+    pub const DEFAULT_MITER_LIMIT: f64 = 10.0;
+}
+
+impl Default for StrokeParams {
+    fn default() -> Self {
+        Self {
+            cap: LineCap::Flat,
+            join: LineJoin::Miter,
+            thickness: 1.0,
+            miter_limit: Self::DEFAULT_MITER_LIMIT,
+            dashes: Vec::new(),
+            dash_phase: 0.0,
+        }
+    }
+}
+
+/// The shape of the ends of open strokes.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LineCap {
+    /// Strokes end exactly at their endpoints.
+    Flat,
+    /// Strokes end in a semicircle centered at their endpoints.
+    Round,
+    /// Strokes end in a square extending half the thickness beyond their endpoints.
+    Square,
+}
+
+/// The shape of the corners of strokes.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LineJoin {
+    /// Corners are sharp, up to [`StrokeParams::miter_limit`].
+    Miter,
+    /// Corners are rounded.
+    Round,
+    /// Corners are cut off.
+    Bevel,
+}
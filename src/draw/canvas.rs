@@ -0,0 +1,787 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! [`Canvas`].
+
+use std::f64::consts::TAU;
+
+use super::{
+    path::{segment_count, Polyline},
+    Brush,
+    DrawContext,
+    FillMode,
+    LineCap,
+    LineJoin,
+    Matrix,
+    Path,
+    StrokeParams,
+    TextLayout,
+};
+use crate::{
+    area::{DrawParams, Handler as AreaHandler},
+    image::{self, Bitmap},
+    Color,
+    Image,
+    Ui,
+};
+
+/// The maximum distance, in pixels, between a curve and the lines that approximate it.
+const TOLERANCE: f64 = 0.1;
+
+/// The most times a dash pattern is repeated along one polyline, beyond which it is stroked solid
+/// instead.
+const MAX_DASH_PERIODS: f64 = 65536.0;
+
+/// The number of rows sampled within each row of pixels for anti-aliasing.
+const SUBSCANLINES: usize = 8;
+
+/// A [`DrawContext`] that rasterizes into an in-memory bitmap.
+///
+/// One unit of drawing space is one pixel, and the canvas starts out transparent. Drawing is
+/// anti-aliased, and colors are composited with the *source-over* operator.
+///
+/// The output of a canvas is deterministic, but it won't match that of *libui-ng* pixel-for-pixel,
+/// so golden images should be rendered by a canvas as well. Canvases don't rasterize text, which
+/// would make their output depend on the fonts installed; [`DrawContext::draw_text`] does nothing.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use boing::{
+///     area::DrawParams,
+///     draw::{Brush, Canvas, DrawContext, FillMode, Path},
+///     AreaHandler,
+///     Color,
+///     Ui,
+/// };
+///
+/// struct Swatch(Color);
+///
+/// impl AreaHandler for Swatch {
+///     fn draw(&mut self, ctx: &mut dyn DrawContext, params: &DrawParams) {
+///         let mut path = Path::new(FillMode::Winding);
+///         path.add_rectangle(0.0, 0.0, params.area_width, params.area_height);
+///         ctx.fill(&path, &Brush::Solid(self.0));
+///     }
+/// }
+///
+/// let ui: Ui;
+/// # ui = Ui::new()?;
+///
+/// let mut canvas = Canvas::new(16, 16);
+/// canvas.render(&mut Swatch(Color::WHITE));
+/// let icon = canvas.to_image(&ui)?;
+///
+/// // The same handler draws an area on screen.
+/// let area = ui.create_area(Swatch(Color::WHITE))?;
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Canvas {
+    width: u32,
+    height: u32,
+    /// Premultiplied RGBA pixels.
+    pixels: Vec<[f64; 4]>,
+    state: State,
+    saved: Vec<State>,
+}
+
+/// The state saved and restored by [`DrawContext::save`] and [`DrawContext::restore`].
+#[derive(Clone, Debug)]
+struct State {
+    matrix: Matrix,
+    /// The coverage of each pixel by the clipping region, or `None` if nothing is clipped.
+    clip: Option<Vec<f64>>,
+}
+
+impl Canvas {
+    /// Creates a new, transparent canvas of the given size in pixels.
+    pub fn new(width: u32, height: u32) -> Self {
+        let len = (width as usize) * (height as usize);
+
+        Self {
+            width,
+            height,
+            pixels: vec![[0.0; 4]; len],
+            state: State {
+                matrix: Matrix::IDENTITY,
+                clip: None,
+            },
+            saved: Vec::new(),
+        }
+    }
+
+    /// The width of this canvas in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of this canvas in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Replaces every pixel with the given color, ignoring the clipping region.
+    pub fn clear(&mut self, color: Color) {
+        let color = premultiply(color);
+        self.pixels.iter_mut().for_each(|pixel| *pixel = color);
+    }
+
+    /// Has an area handler draw onto this canvas as though it were an area of the same size.
+    pub fn render(&mut self, handler: &mut impl AreaHandler) {
+        let (width, height) = (f64::from(self.width), f64::from(self.height));
+        handler.draw(
+            self,
+            &DrawParams {
+                area_width: width,
+                area_height: height,
+                clip_x: 0.0,
+                clip_y: 0.0,
+                clip_width: width,
+                clip_height: height,
+            },
+        );
+    }
+
+    /// The color of the pixel at the given position, or `None` if it is out of bounds.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some(unpremultiply(self.pixels[self.index(x as usize, y as usize)]))
+    }
+
+    /// Copies the contents of this canvas into a bitmap.
+    pub fn to_bitmap(&self) -> Bitmap {
+        Bitmap {
+            width: self.width,
+            height: self.height,
+            pixels: self
+                .pixels
+                .iter()
+                .flat_map(|&pixel| unpremultiply(pixel).to_rgba8())
+                .collect(),
+        }
+    }
+
+    /// Creates an [`Image`] of the same size as this canvas, in points, from its contents.
+    pub fn to_image<'ui>(&self, ui: &'ui Ui) -> Result<Image<'ui>, image::Error> {
+        let image = ui.create_image(self.width.into(), self.height.into())?;
+        image.push_bitmap(&self.to_bitmap())?;
+
+        Ok(image)
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        y * (self.width as usize) + x
+    }
+
+    /// Flattens a path and transforms it into pixel space.
+    fn device_polylines(&self, path: &Path) -> Vec<Polyline> {
+        let matrix = self.state.matrix;
+        let mut polylines = path.flatten(self.user_tolerance());
+        transform(&mut polylines, &matrix);
+
+        polylines
+    }
+
+    /// [`TOLERANCE`] in the units of drawing space.
+    fn user_tolerance(&self) -> f64 {
+        let scale = self.state.matrix.max_scale();
+        if scale > 0.0 && scale.is_finite() {
+            TOLERANCE / scale
+        } else {
+            TOLERANCE
+        }
+    }
+
+    /// Paints the given coverage with a brush.
+    fn paint(&mut self, coverage: &Coverage, brush: &Brush) {
+        let inverse = self.state.matrix.inverse();
+        for (x, y, alpha) in coverage.iter() {
+            let index = self.index(x, y);
+            let alpha = match &self.state.clip {
+                Some(clip) => alpha * clip[index],
+                None => alpha,
+            };
+            if alpha <= 0.0 {
+                continue;
+            }
+
+            let color = match (brush, inverse) {
+                (Brush::Solid(color), _) => *color,
+                (_, Some(inverse)) => {
+                    let (x, y) = inverse.transform_point(x as f64 + 0.5, y as f64 + 0.5);
+                    brush.color_at(gradient_pos(brush, x, y))
+                }
+                // Nothing can be painted through a degenerate transformation anyway.
+                (_, None) => continue,
+            };
+
+            let source = premultiply(color.with_alpha(color.alpha * alpha));
+            let pixel = &mut self.pixels[index];
+            let remaining = 1.0 - source[3];
+            for (dest, source) in pixel.iter_mut().zip(source) {
+                *dest = source + *dest * remaining;
+            }
+        }
+    }
+}
+
+impl DrawContext for Canvas {
+    fn fill(&mut self, path: &Path, brush: &Brush) {
+        let polylines = self.device_polylines(path);
+        let coverage = Coverage::new(&polylines, path.fill_mode(), self.width, self.height);
+        self.paint(&coverage, brush);
+    }
+
+    fn stroke(&mut self, path: &Path, brush: &Brush, params: &StrokeParams) {
+        // Strokes are outlined in drawing space so that their thickness is transformed as well.
+        let tolerance = self.user_tolerance();
+        let mut outline = stroke_outline(&path.flatten(tolerance), params, tolerance);
+        transform(&mut outline, &self.state.matrix);
+
+        let coverage = Coverage::new(&outline, FillMode::Winding, self.width, self.height);
+        self.paint(&coverage, brush);
+    }
+
+    fn transform(&mut self, matrix: &Matrix) {
+        let mut combined = *matrix;
+        combined.multiply(&self.state.matrix);
+        self.state.matrix = combined;
+    }
+
+    fn clip(&mut self, path: &Path) {
+        let polylines = self.device_polylines(path);
+        let coverage = Coverage::new(&polylines, path.fill_mode(), self.width, self.height);
+
+        let mut clip = vec![0.0; self.pixels.len()];
+        for (x, y, alpha) in coverage.iter() {
+            clip[self.index(x, y)] = alpha;
+        }
+        if let Some(existing) = &self.state.clip {
+            clip.iter_mut().zip(existing).for_each(|(new, old)| *new *= old);
+        }
+        self.state.clip = Some(clip);
+    }
+
+    fn save(&mut self) {
+        self.saved.push(self.state.clone());
+    }
+
+    fn restore(&mut self) {
+        if let Some(state) = self.saved.pop() {
+            self.state = state;
+        }
+    }
+
+    fn draw_text(&mut self, _layout: &TextLayout, _x: f64, _y: f64) {}
+}
+
+fn premultiply(color: Color) -> [f64; 4] {
+    let alpha = color.alpha.clamp(0.0, 1.0);
+
+    [color.red * alpha, color.green * alpha, color.blue * alpha, alpha]
+}
+
+fn unpremultiply([red, green, blue, alpha]: [f64; 4]) -> Color {
+    if alpha <= 0.0 {
+        return Color::TRANSPARENT;
+    }
+
+    Color::new(red / alpha, green / alpha, blue / alpha, alpha).clamp()
+}
+
+/// The position along the gradient of a brush at the given point in drawing space.
+fn gradient_pos(brush: &Brush, x: f64, y: f64) -> f64 {
+    match *brush {
+        Brush::Solid(_) => 0.0,
+        Brush::LinearGradient {
+            start: (x0, y0),
+            end: (x1, y1),
+            ..
+        } => {
+            let (dx, dy) = (x1 - x0, y1 - y0);
+            let len_sq = dx * dx + dy * dy;
+            if len_sq == 0.0 {
+                return 0.0;
+            }
+
+            ((x - x0) * dx + (y - y0) * dy) / len_sq
+        }
+        Brush::RadialGradient {
+            start: (x0, y0),
+            outer_center: (x1, y1),
+            outer_radius,
+            ..
+        } => {
+            // Solve for the largest `t` such that the point lies on the circle centered at
+            // `start + t * (outer_center - start)` with radius `t * outer_radius`.
+            let (dx, dy) = (x1 - x0, y1 - y0);
+            let (qx, qy) = (x - x0, y - y0);
+            let a = dx * dx + dy * dy - outer_radius * outer_radius;
+            let b = -2.0 * (qx * dx + qy * dy);
+            let c = qx * qx + qy * qy;
+            if a.abs() < f64::EPSILON {
+                return if b == 0.0 { 0.0 } else { -c / b };
+            }
+
+            let discriminant = b * b - 4.0 * a * c;
+            if discriminant < 0.0 {
+                return 0.0;
+            }
+            let root = discriminant.sqrt();
+
+            ((-b + root) / (2.0 * a)).max((-b - root) / (2.0 * a))
+        }
+    }
+}
+
+fn transform(polylines: &mut [Polyline], matrix: &Matrix) {
+    for point in polylines.iter_mut().flat_map(|polyline| &mut polyline.points) {
+        *point = matrix.transform_point(point.0, point.1);
+    }
+}
+
+/// The fraction of each pixel covered by a set of polygons.
+struct Coverage {
+    x: usize,
+    y: usize,
+    width: usize,
+    values: Vec<f64>,
+}
+
+impl Coverage {
+    /// Scan-converts polylines, which are implicitly closed, in pixel space.
+    fn new(polylines: &[Polyline], fill_mode: FillMode, width: u32, height: u32) -> Self {
+        let edges: Vec<Edge> = polylines
+            .iter()
+            .flat_map(|polyline| {
+                let points = &polyline.points;
+                let next = points.iter().skip(1).chain(points.first());
+
+                points.iter().zip(next).filter_map(|(&a, &b)| Edge::new(a, b))
+            })
+            .collect();
+
+        let (mut top, mut bottom, mut left, mut right) =
+            (f64::INFINITY, f64::NEG_INFINITY, f64::INFINITY, f64::NEG_INFINITY);
+        for edge in &edges {
+            top = top.min(edge.y0);
+            bottom = bottom.max(edge.y1);
+            left = left.min(edge.x0.min(edge.x1));
+            right = right.max(edge.x0.max(edge.x1));
+        }
+
+        let clamp = |value: f64, max: u32| value.clamp(0.0, f64::from(max)) as usize;
+        let (x, y) = (clamp(left.floor(), width), clamp(top.floor(), height));
+        let (x_end, y_end) = (clamp(right.ceil(), width), clamp(bottom.ceil(), height));
+        let mut coverage = Self {
+            x,
+            y,
+            width: x_end.saturating_sub(x),
+            values: vec![0.0; x_end.saturating_sub(x) * y_end.saturating_sub(y)],
+        };
+        if coverage.values.is_empty() {
+            return coverage;
+        }
+
+        let weight = 1.0 / (SUBSCANLINES as f64);
+        let mut crossings = Vec::new();
+        for row in y..y_end {
+            let offset = (row - y) * coverage.width;
+            let values = &mut coverage.values[offset..(offset + coverage.width)];
+
+            for sample in 0..SUBSCANLINES {
+                let scan_y = row as f64 + (sample as f64 + 0.5) * weight;
+
+                crossings.clear();
+                crossings.extend(edges.iter().filter_map(|edge| edge.crossing(scan_y)));
+                crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+                let mut winding = 0;
+                for pair in crossings.windows(2) {
+                    winding += pair[0].1;
+                    let is_inside = match fill_mode {
+                        FillMode::Winding => winding != 0,
+                        FillMode::Alternate => winding % 2 != 0,
+                    };
+                    if is_inside {
+                        add_span(values, pair[0].0 - x as f64, pair[1].0 - x as f64, weight);
+                    }
+                }
+            }
+        }
+
+        coverage
+    }
+
+    /// The position and coverage of each pixel that is at least partially covered.
+    fn iter(&self) -> impl '_ + Iterator<Item = (usize, usize, f64)> {
+        self.values
+            .iter()
+            .enumerate()
+            .filter(|(_, &value)| value > 0.0)
+            .map(move |(i, &value)| {
+                (self.x + i % self.width, self.y + i / self.width, value.min(1.0))
+            })
+    }
+}
+
+/// Adds the horizontal span from `start` to `end` to a row of coverage.
+fn add_span(values: &mut [f64], start: f64, end: f64, weight: f64) {
+    let len = values.len() as f64;
+    let (start, end) = (start.clamp(0.0, len), end.clamp(0.0, len));
+    if start >= end {
+        return;
+    }
+
+    let (first, last) = (start.floor(), end.floor());
+    if first == last {
+        values[first as usize] += (end - start) * weight;
+        return;
+    }
+
+    values[first as usize] += (first + 1.0 - start) * weight;
+    for value in &mut values[(first as usize + 1)..(last as usize)] {
+        *value += weight;
+    }
+    if let Some(value) = values.get_mut(last as usize) {
+        *value += (end - last) * weight;
+    }
+}
+
+/// A non-horizontal line segment of a polygon, oriented downwards.
+struct Edge {
+    x0: f64,
+    y0: f64,
+    x1: f64,
+    y1: f64,
+    /// `1` if the segment originally pointed downwards, or `-1` otherwise.
+    direction: i32,
+}
+
+impl Edge {
+    fn new(a: (f64, f64), b: (f64, f64)) -> Option<Self> {
+        let is_finite = [a.0, a.1, b.0, b.1].iter().all(|value| value.is_finite());
+        if a.1 == b.1 || !is_finite {
+            return None;
+        }
+
+        let (top, bottom, direction) = if a.1 < b.1 { (a, b, 1) } else { (b, a, -1) };
+
+        Some(Self {
+            x0: top.0,
+            y0: top.1,
+            x1: bottom.0,
+            y1: bottom.1,
+            direction,
+        })
+    }
+
+    /// The position at which this edge crosses the horizontal line at `y`, if it does.
+    fn crossing(&self, y: f64) -> Option<(f64, i32)> {
+        (self.y0 <= y && y < self.y1).then(|| {
+            let t = (y - self.y0) / (self.y1 - self.y0);
+
+            (self.x0 + t * (self.x1 - self.x0), self.direction)
+        })
+    }
+}
+
+/// Builds polygons that, filled with [`FillMode::Winding`], cover the stroke of the given
+/// polylines.
+///
+/// Every polygon is oriented the same way so that overlapping polygons don't cancel each other
+/// out.
+fn stroke_outline(polylines: &[Polyline], params: &StrokeParams, tolerance: f64) -> Vec<Polyline> {
+    let half = params.thickness / 2.0;
+    if half.is_nan() || half <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut outline = Stroker {
+        params,
+        half,
+        tolerance,
+        polygons: Vec::new(),
+    };
+    for polyline in polylines {
+        if params.dashes.iter().any(|&len| len > 0.0) {
+            for dash in dash(polyline, &params.dashes, params.dash_phase, tolerance) {
+                outline.stroke(&dash);
+            }
+        } else {
+            outline.stroke(polyline);
+        }
+    }
+
+    outline.polygons
+}
+
+struct Stroker<'a> {
+    params: &'a StrokeParams,
+    /// Half of the thickness of the stroke.
+    half: f64,
+    tolerance: f64,
+    polygons: Vec<Polyline>,
+}
+
+impl Stroker<'_> {
+    fn stroke(&mut self, polyline: &Polyline) {
+        let points = &polyline.points;
+        let (first, last) = match (points.first(), points.last()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => return,
+        };
+        if points.len() == 1 {
+            self.dot(first);
+            return;
+        }
+
+        for pair in points.windows(2) {
+            self.segment(pair[0], pair[1]);
+        }
+
+        if polyline.is_closed {
+            self.segment(last, first);
+            let count = points.len();
+            for i in 0..count {
+                let prev = points[(i + count - 1) % count];
+                let next = points[(i + 1) % count];
+                self.join(prev, points[i], next);
+            }
+        } else {
+            for triple in points.windows(3) {
+                self.join(triple[0], triple[1], triple[2]);
+            }
+            self.cap(first, direction(points[1], first));
+            self.cap(last, direction(points[points.len() - 2], last));
+        }
+    }
+
+    fn push(&mut self, mut points: Vec<(f64, f64)>) {
+        if signed_area(&points) < 0.0 {
+            points.reverse();
+        }
+        self.polygons.push(Polyline {
+            points,
+            is_closed: true,
+        });
+    }
+
+    fn segment(&mut self, a: (f64, f64), b: (f64, f64)) {
+        let (nx, ny) = normal(direction(a, b), self.half);
+        self.push(vec![
+            (a.0 + nx, a.1 + ny),
+            (b.0 + nx, b.1 + ny),
+            (b.0 - nx, b.1 - ny),
+            (a.0 - nx, a.1 - ny),
+        ]);
+    }
+
+    fn join(&mut self, prev: (f64, f64), at: (f64, f64), next: (f64, f64)) {
+        let d0 = direction(prev, at);
+        let d1 = direction(at, next);
+        let cross = d0.0 * d1.1 - d0.1 * d1.0;
+        let dot = d0.0 * d1.0 + d0.1 * d1.1;
+        if cross.abs() < 1e-12 && dot > 0.0 {
+            // The segments are collinear, so they already meet seamlessly.
+            return;
+        }
+
+        // Joins are drawn on the outside of the turn.
+        let side = if cross > 0.0 { -1.0 } else { 1.0 };
+        let (n0x, n0y) = normal(d0, side * self.half);
+        let (n1x, n1y) = normal(d1, side * self.half);
+        let a = (at.0 + n0x, at.1 + n0y);
+        let b = (at.0 + n1x, at.1 + n1y);
+
+        match self.params.join {
+            LineJoin::Round => self.circle(at),
+            LineJoin::Bevel => self.push(vec![at, a, b]),
+            LineJoin::Miter => {
+                // The ratio of the length of the miter to the thickness of the stroke.
+                let ratio = 1.0 / ((1.0 + dot) / 2.0).sqrt();
+                if ratio > self.params.miter_limit || !ratio.is_finite() {
+                    self.push(vec![at, a, b]);
+                    return;
+                }
+
+                let (mx, my) = (n0x + n1x, n0y + n1y);
+                let len = mx.hypot(my);
+                let tip = (
+                    at.0 + mx / len * self.half * ratio,
+                    at.1 + my / len * self.half * ratio,
+                );
+                self.push(vec![at, a, tip, b]);
+            }
+        }
+    }
+
+    /// Caps the end of a stroke at `at` that extends in the direction `outward`.
+    fn cap(&mut self, at: (f64, f64), outward: (f64, f64)) {
+        match self.params.cap {
+            LineCap::Flat => {}
+            LineCap::Round => self.circle(at),
+            LineCap::Square => {
+                let (nx, ny) = normal(outward, self.half);
+                let (ox, oy) = (outward.0 * self.half, outward.1 * self.half);
+                self.push(vec![
+                    (at.0 + nx, at.1 + ny),
+                    (at.0 + nx + ox, at.1 + ny + oy),
+                    (at.0 - nx + ox, at.1 - ny + oy),
+                    (at.0 - nx, at.1 - ny),
+                ]);
+            }
+        }
+    }
+
+    /// Strokes a figure of zero length, which is only visible with round or square caps.
+    fn dot(&mut self, at: (f64, f64)) {
+        match self.params.cap {
+            LineCap::Flat => {}
+            LineCap::Round => self.circle(at),
+            LineCap::Square => {
+                let half = self.half;
+                self.push(vec![
+                    (at.0 - half, at.1 - half),
+                    (at.0 + half, at.1 - half),
+                    (at.0 + half, at.1 + half),
+                    (at.0 - half, at.1 + half),
+                ]);
+            }
+        }
+    }
+
+    fn circle(&mut self, at: (f64, f64)) {
+        let count = segment_count(self.half, TAU, self.tolerance).max(8);
+        let points = (0..count)
+            .map(|i| {
+                let angle = TAU * (i as f64) / (count as f64);
+                (at.0 + self.half * angle.cos(), at.1 + self.half * angle.sin())
+            })
+            .collect();
+        self.push(points);
+    }
+}
+
+/// The unit vector pointing from `a` to `b`.
+fn direction(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let len = dx.hypot(dy);
+    if len == 0.0 {
+        return (1.0, 0.0);
+    }
+
+    (dx / len, dy / len)
+}
+
+/// The vector of the given length perpendicular to `direction`.
+fn normal(direction: (f64, f64), len: f64) -> (f64, f64) {
+    (-direction.1 * len, direction.0 * len)
+}
+
+fn signed_area(points: &[(f64, f64)]) -> f64 {
+    let next = points.iter().skip(1).chain(points.first());
+
+    points
+        .iter()
+        .zip(next)
+        .map(|(a, b)| a.0 * b.1 - b.0 * a.1)
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Splits a polyline into dashes.
+///
+/// As in SVG, a pattern with an odd number of lengths is repeated to make it even.
+///
+/// A pattern that repeats within `tolerance`, or too many times along the polyline, couldn't be
+/// told apart from a solid line, so the polyline is left whole.
+fn dash(polyline: &Polyline, pattern: &[f64], phase: f64, tolerance: f64) -> Vec<Polyline> {
+    let pattern: Vec<f64> = if pattern.len().is_multiple_of(2) {
+        pattern.iter().map(|len| len.max(0.0)).collect()
+    } else {
+        pattern.iter().chain(pattern).map(|len| len.max(0.0)).collect()
+    };
+    let period: f64 = pattern.iter().sum();
+
+    let mut len: f64 = polyline
+        .points
+        .windows(2)
+        .map(|pair| (pair[1].0 - pair[0].0).hypot(pair[1].1 - pair[0].1))
+        .sum();
+    if polyline.is_closed {
+        if let (Some(first), Some(last)) = (polyline.points.first(), polyline.points.last()) {
+            len += (first.0 - last.0).hypot(first.1 - last.1);
+        }
+    }
+    // Besides bounding the number of dashes, this keeps the whole pattern longer than the
+    // precision of the distance travelled along a segment, so that dashing always advances.
+    if !(period >= tolerance && len / period <= MAX_DASH_PERIODS) {
+        return vec![polyline.clone()];
+    }
+
+    // Find where in the pattern the polyline starts.
+    let mut index = 0;
+    let mut remaining = pattern[0];
+    let mut phase = phase.rem_euclid(period);
+    while phase > 0.0 {
+        if phase < remaining {
+            remaining -= phase;
+            break;
+        }
+        phase -= remaining;
+        index = (index + 1) % pattern.len();
+        remaining = pattern[index];
+    }
+
+    let mut points = polyline.points.clone();
+    if polyline.is_closed {
+        points.extend(polyline.points.first());
+    }
+
+    let mut dashes = Vec::new();
+    let mut current = (index % 2 == 0).then(|| Polyline {
+        points: vec![points[0]],
+        is_closed: false,
+    });
+    for pair in points.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let len = (b.0 - a.0).hypot(b.1 - a.1);
+        let mut travelled = 0.0;
+
+        while len - travelled > remaining {
+            travelled += remaining;
+            let t = travelled / len;
+            let point = (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+
+            match current.take() {
+                Some(mut dash) => {
+                    dash.points.push(point);
+                    dashes.push(dash);
+                }
+                None => {
+                    current = Some(Polyline {
+                        points: vec![point],
+                        is_closed: false,
+                    });
+                }
+            }
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        }
+
+        remaining -= len - travelled;
+        if let Some(dash) = &mut current {
+            dash.points.push(b);
+        }
+    }
+    dashes.extend(current);
+
+    dashes
+}
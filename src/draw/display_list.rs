@@ -0,0 +1,646 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! [`DisplayList`].
+
+use std::{
+    f64::consts::PI,
+    fmt::{self, Write as _},
+    fs,
+    io,
+    path::Path as FsPath,
+};
+
+use super::{
+    Arc,
+    Brush,
+    Command,
+    DrawContext,
+    FillMode,
+    GradientStop,
+    LineCap,
+    LineJoin,
+    Matrix,
+    Path,
+    StrokeParams,
+    TextAlign,
+    TextLayout,
+};
+use crate::{
+    area::{DrawParams, Handler as AreaHandler},
+    Color,
+};
+
+/// A [`DrawContext`] that records drawing operations for later inspection, replay, or export to
+/// SVG.
+///
+/// Comparing display lists is a way to test drawing code that, unlike comparing the pixels of a
+/// [`Canvas`](super::Canvas), doesn't depend on how shapes are rasterized.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> {
+/// use boing::{
+///     area::DrawParams,
+///     draw::{Brush, DisplayList, DrawContext, FillMode, Op, Path},
+///     AreaHandler,
+///     Color,
+/// };
+///
+/// struct Dot;
+///
+/// impl AreaHandler for Dot {
+///     fn draw(&mut self, ctx: &mut dyn DrawContext, params: &DrawParams) {
+///         let mut path = Path::new(FillMode::Winding);
+///         path.add_rectangle(params.area_width / 2.0, params.area_height / 2.0, 1.0, 1.0);
+///         ctx.fill(&path, &Brush::Solid(Color::BLACK));
+///     }
+/// }
+///
+/// let mut list = DisplayList::new(64.0, 64.0);
+/// list.render(&mut Dot);
+/// assert!(matches!(list.ops[..], [Op::Fill { .. }]));
+///
+/// list.save_svg("dot.svg")?;
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisplayList {
+    /// The width of the drawing.
+    pub width: f64,
+    /// The height of the drawing.
+    pub height: f64,
+    /// The recorded operations, in order.
+    pub ops: Vec<Op>,
+}
+
+/// A drawing operation recorded by a [`DisplayList`].
+///
+/// Each variant corresponds to a method of [`DrawContext`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Op {
+    Fill {
+        path: Path,
+        brush: Brush,
+    },
+    Stroke {
+        path: Path,
+        brush: Brush,
+        params: StrokeParams,
+    },
+    Transform(Matrix),
+    Clip(Path),
+    Save,
+    Restore,
+    Text {
+        layout: TextLayout,
+        x: f64,
+        y: f64,
+    },
+}
+
+impl DisplayList {
+    /// Creates a new, empty display list for a drawing of the given size.
+    pub fn new(width: f64, height: f64) -> Self {
+        Self {
+            width,
+            height,
+            ops: Vec::new(),
+        }
+    }
+
+    /// Records the drawing of an area handler as though it were drawing an area of the same size
+    /// as this display list.
+    pub fn render(&mut self, handler: &mut impl AreaHandler) {
+        let (width, height) = (self.width, self.height);
+        handler.draw(
+            self,
+            &DrawParams {
+                area_width: width,
+                area_height: height,
+                clip_x: 0.0,
+                clip_y: 0.0,
+                clip_width: width,
+                clip_height: height,
+            },
+        );
+    }
+
+    /// Issues the recorded operations to another drawing context.
+    pub fn replay(&self, ctx: &mut dyn DrawContext) {
+        for op in &self.ops {
+            match op {
+                Op::Fill { path, brush } => ctx.fill(path, brush),
+                Op::Stroke {
+                    path,
+                    brush,
+                    params,
+                } => ctx.stroke(path, brush, params),
+                Op::Transform(matrix) => ctx.transform(matrix),
+                Op::Clip(path) => ctx.clip(path),
+                Op::Save => ctx.save(),
+                Op::Restore => ctx.restore(),
+                Op::Text { layout, x, y } => ctx.draw_text(layout, *x, *y),
+            }
+        }
+    }
+
+    /// Converts the recorded operations to an SVG document.
+    pub fn to_svg(&self) -> String {
+        let mut svg = Svg::default();
+        // Writing to a `String` never fails.
+        let _ = svg.write_document(self);
+
+        svg.out
+    }
+
+    /// Saves the recorded operations to an SVG file.
+    pub fn save_svg(&self, path: impl AsRef<FsPath>) -> io::Result<()> {
+        fs::write(path, self.to_svg())
+    }
+}
+
+impl DrawContext for DisplayList {
+    fn fill(&mut self, path: &Path, brush: &Brush) {
+        self.ops.push(Op::Fill {
+            path: path.clone(),
+            brush: brush.clone(),
+        });
+    }
+
+    fn stroke(&mut self, path: &Path, brush: &Brush, params: &StrokeParams) {
+        self.ops.push(Op::Stroke {
+            path: path.clone(),
+            brush: brush.clone(),
+            params: params.clone(),
+        });
+    }
+
+    fn transform(&mut self, matrix: &Matrix) {
+        self.ops.push(Op::Transform(*matrix));
+    }
+
+    fn clip(&mut self, path: &Path) {
+        self.ops.push(Op::Clip(path.clone()));
+    }
+
+    fn save(&mut self) {
+        self.ops.push(Op::Save);
+    }
+
+    fn restore(&mut self) {
+        self.ops.push(Op::Restore);
+    }
+
+    fn draw_text(&mut self, layout: &TextLayout, x: f64, y: f64) {
+        self.ops.push(Op::Text {
+            layout: layout.clone(),
+            x,
+            y,
+        });
+    }
+}
+
+/// The state of an SVG conversion.
+#[derive(Default)]
+struct Svg {
+    out: String,
+    /// The number of `<clipPath>` and gradient elements written so far, used to generate IDs.
+    id_count: usize,
+    state: SvgState,
+    saved: Vec<SvgState>,
+}
+
+#[derive(Clone, Copy, Default)]
+struct SvgState {
+    matrix: Matrix,
+    /// The ID of the `<clipPath>` of the current clipping region, if any.
+    clip: Option<usize>,
+}
+
+impl Svg {
+    fn write_document(&mut self, list: &DisplayList) -> fmt::Result {
+        writeln!(
+            self.out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+            list.width, list.height,
+        )?;
+        for op in &list.ops {
+            self.write_op(op)?;
+        }
+        writeln!(self.out, "</svg>")
+    }
+
+    fn next_id(&mut self) -> usize {
+        self.id_count += 1;
+
+        self.id_count
+    }
+
+    fn write_op(&mut self, op: &Op) -> fmt::Result {
+        // SVG has no syntax for non-finite numbers, so operations with any are left out.
+        if !is_finite_op(op) {
+            return Ok(());
+        }
+
+        match op {
+            Op::Fill { path, brush } => {
+                let paint = self.write_paint("fill", brush)?;
+                let rule = fill_rule(path.fill_mode());
+                self.write_shape(path, &format!(r#"{} fill-rule="{}""#, paint, rule))
+            }
+            Op::Stroke {
+                path,
+                brush,
+                params,
+            } => {
+                let paint = self.write_paint("stroke", brush)?;
+                let attrs = format!(r#"fill="none" {}{}"#, paint, stroke_attrs(params));
+                self.write_shape(path, &attrs)
+            }
+            Op::Transform(matrix) => {
+                let mut combined = *matrix;
+                combined.multiply(&self.state.matrix);
+                self.state.matrix = combined;
+
+                Ok(())
+            }
+            Op::Clip(path) => {
+                let id = self.next_id();
+                write!(self.out, r#"<clipPath id="clip{}""#, id)?;
+                // Nesting the previous clipping region intersects it with the new one.
+                if let Some(previous) = self.state.clip {
+                    write!(self.out, r#" clip-path="url(#clip{})""#, previous)?;
+                }
+                writeln!(
+                    self.out,
+                    r#"><path d="{}"{} clip-rule="{}"/></clipPath>"#,
+                    path_data(path),
+                    transform_attr(&self.state.matrix),
+                    fill_rule(path.fill_mode()),
+                )?;
+                self.state.clip = Some(id);
+
+                Ok(())
+            }
+            Op::Save => {
+                self.saved.push(self.state);
+
+                Ok(())
+            }
+            Op::Restore => {
+                if let Some(state) = self.saved.pop() {
+                    self.state = state;
+                }
+
+                Ok(())
+            }
+            Op::Text { layout, x, y } => self.write_text(layout, *x, *y),
+        }
+    }
+
+    /// Writes a `<text>` element with one `<tspan>` per line.
+    ///
+    /// SVG 1.1 can't wrap text, so [`TextLayout::width`] only serves to align lines.
+    fn write_text(&mut self, layout: &TextLayout, x: f64, y: f64) -> fmt::Result {
+        let (anchor, x) = match (layout.align, layout.width) {
+            (TextAlign::Center, Some(width)) => ("middle", x + width / 2.0),
+            (TextAlign::Right, Some(width)) => ("end", x + width),
+            _ => ("start", x),
+        };
+        let font = &layout.font;
+
+        if let Some(clip) = self.state.clip {
+            write!(self.out, r#"<g clip-path="url(#clip{})">"#, clip)?;
+        }
+        write!(
+            self.out,
+            r#"<text x="{}" y="{}"{} font-family="{}" font-size="{}" font-weight="{}""#,
+            x,
+            y,
+            transform_attr(&self.state.matrix),
+            escape(&font.family),
+            font.size,
+            font.weight,
+        )?;
+        if font.italic_kind.is_some() {
+            write!(self.out, r#" font-style="italic""#)?;
+        }
+        write!(
+            self.out,
+            r#" {} text-anchor="{}" dominant-baseline="hanging">"#,
+            paint_attrs("fill", layout.color),
+            anchor,
+        )?;
+        for (i, line) in layout.text.lines().enumerate() {
+            let dy = if i == 0 { 0.0 } else { TextLayout::LINE_HEIGHT };
+            write!(self.out, r#"<tspan x="{}" dy="{}em">{}</tspan>"#, x, dy, escape(line))?;
+        }
+        write!(self.out, "</text>")?;
+        if self.state.clip.is_some() {
+            write!(self.out, "</g>")?;
+        }
+
+        writeln!(self.out)
+    }
+
+    /// Writes a `<path>` with the current transformation and clipping region.
+    fn write_shape(&mut self, path: &Path, attrs: &str) -> fmt::Result {
+        // The clipping region is applied by a group so that it isn't affected by the
+        // transformation of the path.
+        if let Some(clip) = self.state.clip {
+            write!(self.out, r#"<g clip-path="url(#clip{})">"#, clip)?;
+        }
+        write!(
+            self.out,
+            r#"<path d="{}"{} {}/>"#,
+            path_data(path),
+            transform_attr(&self.state.matrix),
+            attrs,
+        )?;
+        if self.state.clip.is_some() {
+            write!(self.out, "</g>")?;
+        }
+
+        writeln!(self.out)
+    }
+
+    /// Writes any definitions needed by a brush, returning the attributes that paint with it as
+    /// the given property, i.e., `fill` or `stroke`.
+    fn write_paint(&mut self, property: &str, brush: &Brush) -> Result<String, fmt::Error> {
+        let id = match brush {
+            Brush::Solid(color) => return Ok(paint_attrs(property, *color)),
+            Brush::LinearGradient {
+                start: (x1, y1),
+                end: (x2, y2),
+                stops,
+            } => {
+                let id = self.next_id();
+                write!(
+                    self.out,
+                    r#"<linearGradient id="paint{}" gradientUnits="userSpaceOnUse" x1="{}" y1="{}" x2="{}" y2="{}">"#,
+                    id, x1, y1, x2, y2,
+                )?;
+                self.write_stops(stops)?;
+                writeln!(self.out, "</linearGradient>")?;
+
+                id
+            }
+            Brush::RadialGradient {
+                start: (fx, fy),
+                outer_center: (cx, cy),
+                outer_radius,
+                stops,
+            } => {
+                let id = self.next_id();
+                write!(
+                    self.out,
+                    r#"<radialGradient id="paint{}" gradientUnits="userSpaceOnUse" cx="{}" cy="{}" r="{}" fx="{}" fy="{}">"#,
+                    id, cx, cy, outer_radius, fx, fy,
+                )?;
+                self.write_stops(stops)?;
+                writeln!(self.out, "</radialGradient>")?;
+
+                id
+            }
+        };
+
+        Ok(format!(r#"{}="url(#paint{})""#, property, id))
+    }
+
+    fn write_stops(&mut self, stops: &[GradientStop]) -> fmt::Result {
+        for stop in stops {
+            let [red, green, blue, _] = stop.color.to_rgba8();
+            write!(
+                self.out,
+                r##"<stop offset="{}" stop-color="#{:02x}{:02x}{:02x}" stop-opacity="{}"/>"##,
+                stop.pos,
+                red,
+                green,
+                blue,
+                stop.color.alpha.clamp(0.0, 1.0),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The attributes that paint a solid color as the given property, i.e., `fill` or `stroke`.
+///
+/// SVG 1.1 colors have no alpha, so translucent colors are given an opacity attribute, as
+/// gradient stops are.
+fn paint_attrs(property: &str, color: Color) -> String {
+    let [red, green, blue, _] = color.to_rgba8();
+    let mut attrs = format!(r##"{}="#{:02x}{:02x}{:02x}""##, property, red, green, blue);
+    let alpha = color.alpha.clamp(0.0, 1.0);
+    if alpha < 1.0 {
+        let _ = write!(attrs, r#" {}-opacity="{}""#, property, alpha);
+    }
+
+    attrs
+}
+
+/// Determines if every number that an operation would write to SVG is finite.
+fn is_finite_op(op: &Op) -> bool {
+    match op {
+        Op::Fill { path, brush } => is_finite_path(path) && is_finite_brush(brush),
+        Op::Stroke {
+            path,
+            brush,
+            params,
+        } => {
+            is_finite_path(path)
+                && is_finite_brush(brush)
+                && [params.thickness, params.miter_limit, params.dash_phase]
+                    .iter()
+                    .chain(&params.dashes)
+                    .all(|value| value.is_finite())
+        }
+        Op::Transform(matrix) => {
+            let values = [matrix.m11, matrix.m12, matrix.m21, matrix.m22, matrix.m31, matrix.m32];
+
+            values.iter().all(|value| value.is_finite())
+        }
+        Op::Clip(path) => is_finite_path(path),
+        Op::Save | Op::Restore => true,
+        Op::Text { layout, x, y } => {
+            [*x, *y, layout.font.size, layout.width.unwrap_or(0.0)]
+                .iter()
+                .all(|value| value.is_finite())
+        }
+    }
+}
+
+fn is_finite_path(path: &Path) -> bool {
+    let is_finite_arc = |arc: &Arc| {
+        [arc.x_center, arc.y_center, arc.radius, arc.start_angle, arc.sweep]
+            .iter()
+            .all(|value| value.is_finite())
+    };
+
+    path.commands().iter().all(|command| match *command {
+        Command::NewFigure { x, y } | Command::LineTo { x, y } => x.is_finite() && y.is_finite(),
+        Command::NewFigureWithArc(arc) | Command::ArcTo(arc) => is_finite_arc(&arc),
+        Command::BezierTo {
+            c1x,
+            c1y,
+            c2x,
+            c2y,
+            x,
+            y,
+        } => [c1x, c1y, c2x, c2y, x, y].iter().all(|value| value.is_finite()),
+        Command::CloseFigure => true,
+        Command::AddRectangle {
+            x,
+            y,
+            width,
+            height,
+        } => [x, y, width, height].iter().all(|value| value.is_finite()),
+    })
+}
+
+fn is_finite_brush(brush: &Brush) -> bool {
+    let numbers = match brush {
+        Brush::Solid(_) => return true,
+        Brush::LinearGradient {
+            start: (x1, y1),
+            end: (x2, y2),
+            stops,
+        } => [*x1, *y1, *x2, *y2]
+            .into_iter()
+            .chain(stops.iter().map(|stop| stop.pos))
+            .collect::<Vec<_>>(),
+        Brush::RadialGradient {
+            start: (fx, fy),
+            outer_center: (cx, cy),
+            outer_radius,
+            stops,
+        } => [*fx, *fy, *cx, *cy, *outer_radius]
+            .into_iter()
+            .chain(stops.iter().map(|stop| stop.pos))
+            .collect(),
+    };
+
+    numbers.iter().all(|value| value.is_finite())
+}
+
+/// Escapes text for use in element content and attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn fill_rule(fill_mode: FillMode) -> &'static str {
+    match fill_mode {
+        FillMode::Winding => "nonzero",
+        FillMode::Alternate => "evenodd",
+    }
+}
+
+/// A `transform` attribute, preceded by a space, or nothing for the identity transformation.
+fn transform_attr(matrix: &Matrix) -> String {
+    if *matrix == Matrix::IDENTITY {
+        return String::new();
+    }
+
+    format!(
+        r#" transform="matrix({} {} {} {} {} {})""#,
+        matrix.m11, matrix.m12, matrix.m21, matrix.m22, matrix.m31, matrix.m32,
+    )
+}
+
+fn stroke_attrs(params: &StrokeParams) -> String {
+    let cap = match params.cap {
+        LineCap::Flat => "butt",
+        LineCap::Round => "round",
+        LineCap::Square => "square",
+    };
+    let join = match params.join {
+        LineJoin::Miter => "miter",
+        LineJoin::Round => "round",
+        LineJoin::Bevel => "bevel",
+    };
+    let mut attrs = format!(
+        r#" stroke-width="{}" stroke-linecap="{}" stroke-linejoin="{}" stroke-miterlimit="{}""#,
+        params.thickness, cap, join, params.miter_limit,
+    );
+    if !params.dashes.is_empty() {
+        let dashes: Vec<String> = params.dashes.iter().map(f64::to_string).collect();
+        let _ = write!(
+            attrs,
+            r#" stroke-dasharray="{}" stroke-dashoffset="{}""#,
+            dashes.join(" "),
+            params.dash_phase,
+        );
+    }
+
+    attrs
+}
+
+/// The value of the `d` attribute of a `<path>` equivalent to the given path.
+fn path_data(path: &Path) -> String {
+    let mut data = String::new();
+    for command in path.commands() {
+        // Writing to a `String` never fails.
+        let _ = match *command {
+            Command::NewFigure { x, y } => write!(data, "M{} {} ", x, y),
+            Command::NewFigureWithArc(arc) => {
+                let (x, y) = arc.point_at(arc.start_angle);
+                write!(data, "M{} {} ", x, y).and_then(|_| write_arc(&mut data, &arc))
+            }
+            Command::LineTo { x, y } => write!(data, "L{} {} ", x, y),
+            Command::ArcTo(arc) => {
+                let (x, y) = arc.point_at(arc.start_angle);
+                write!(data, "L{} {} ", x, y).and_then(|_| write_arc(&mut data, &arc))
+            }
+            Command::BezierTo {
+                c1x,
+                c1y,
+                c2x,
+                c2y,
+                x,
+                y,
+            } => write!(data, "C{} {} {} {} {} {} ", c1x, c1y, c2x, c2y, x, y),
+            Command::CloseFigure => write!(data, "Z "),
+            Command::AddRectangle {
+                x,
+                y,
+                width,
+                height,
+            } => write!(data, "M{} {} h{} v{} h{} Z ", x, y, width, height, -width),
+        };
+    }
+    data.truncate(data.trim_end().len());
+
+    data
+}
+
+/// Writes an arc as SVG elliptical arc commands.
+///
+/// SVG arcs can't describe a full circle, so arcs are split into pieces of at most half a turn.
+fn write_arc(data: &mut String, arc: &Arc) -> fmt::Result {
+    let sweep = arc.end_angle() - arc.start_angle;
+    let count = (sweep.abs() / PI).ceil().max(1.0) as usize;
+    // Positive angles are clockwise, as is SVG's positive sweep direction.
+    let sweep_flag = if arc.is_negative { 0 } else { 1 };
+
+    for i in 1..=count {
+        let (x, y) = arc.point_at(arc.start_angle + sweep * (i as f64) / (count as f64));
+        write!(data, "A{0} {0} 0 0 {1} {2} {3} ", arc.radius, sweep_flag, x, y)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! [`Matrix`].
+
+/// An affine transformation.
+///
+/// A point `(x, y)` is transformed to `(x * m11 + y * m21 + m31, x * m12 + y * m22 + m32)`.
+///
+/// Each of the methods that modify a matrix, like [`Matrix::translate`], apply their
+/// transformation *after* the existing one.
+///
+/// # Examples
+///
+/// ```no_run
+/// use boing::draw::Matrix;
+///
+/// let mut matrix = Matrix::IDENTITY;
+/// matrix.scale(0.0, 0.0, 2.0, 2.0);
+/// matrix.translate(10.0, 0.0);
+/// assert_eq!((12.0, 2.0), matrix.transform_point(1.0, 1.0));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Matrix {
+    pub m11: f64,
+    pub m12: f64,
+    pub m21: f64,
+    pub m22: f64,
+    pub m31: f64,
+    pub m32: f64,
+}
+
+impl Default for Matrix {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Matrix {
+    /// The transformation that leaves all points in place.
+    pub const IDENTITY: Self = Self {
+        m11: 1.0,
+        m12: 0.0,
+        m21: 0.0,
+        m22: 1.0,
+        m31: 0.0,
+        m32: 0.0,
+    };
+
+    /// Moves points by the given offset.
+    pub fn translate(&mut self, x: f64, y: f64) {
+        self.multiply(&Self {
+            m31: x,
+            m32: y,
+            ..Self::IDENTITY
+        });
+    }
+
+    /// Scales points by the given factors about the point `(x_center, y_center)`.
+    pub fn scale(&mut self, x_center: f64, y_center: f64, x: f64, y: f64) {
+        self.multiply(&Self {
+            m11: x,
+            m22: y,
+            m31: x_center - x * x_center,
+            m32: y_center - y * y_center,
+            ..Self::IDENTITY
+        });
+    }
+
+    /// Rotates points clockwise by the given angle, in radians, about the point `(x, y)`.
+    pub fn rotate(&mut self, x: f64, y: f64, amount: f64) {
+        let (sin, cos) = amount.sin_cos();
+        self.translate(-x, -y);
+        self.multiply(&Self {
+            m11: cos,
+            m12: sin,
+            m21: -sin,
+            m22: cos,
+            ..Self::IDENTITY
+        });
+        self.translate(x, y);
+    }
+
+    /// Skews points by the given angles, in radians, about the point `(x, y)`.
+    pub fn skew(&mut self, x: f64, y: f64, x_amount: f64, y_amount: f64) {
+        self.translate(-x, -y);
+        self.multiply(&Self {
+            m12: y_amount.tan(),
+            m21: x_amount.tan(),
+            ..Self::IDENTITY
+        });
+        self.translate(x, y);
+    }
+
+    /// Applies another transformation after this one.
+    pub fn multiply(&mut self, other: &Self) {
+        *self = Self {
+            m11: self.m11 * other.m11 + self.m12 * other.m21,
+            m12: self.m11 * other.m12 + self.m12 * other.m22,
+            m21: self.m21 * other.m11 + self.m22 * other.m21,
+            m22: self.m21 * other.m12 + self.m22 * other.m22,
+            m31: self.m31 * other.m11 + self.m32 * other.m21 + other.m31,
+            m32: self.m31 * other.m12 + self.m32 * other.m22 + other.m32,
+        };
+    }
+
+    /// The determinant of the linear part of this transformation.
+    fn determinant(&self) -> f64 {
+        self.m11 * self.m22 - self.m12 * self.m21
+    }
+
+    /// Determines if this transformation can be undone.
+    pub fn is_invertible(&self) -> bool {
+        let determinant = self.determinant();
+
+        determinant != 0.0 && determinant.is_finite()
+    }
+
+    /// The transformation that undoes this one, or `None` if this transformation isn't
+    /// invertible.
+    pub fn inverse(&self) -> Option<Self> {
+        if !self.is_invertible() {
+            return None;
+        }
+
+        let determinant = self.determinant();
+        let m11 = self.m22 / determinant;
+        let m12 = -self.m12 / determinant;
+        let m21 = -self.m21 / determinant;
+        let m22 = self.m11 / determinant;
+
+        Some(Self {
+            m11,
+            m12,
+            m21,
+            m22,
+            m31: -(self.m31 * m11 + self.m32 * m21),
+            m32: -(self.m31 * m12 + self.m32 * m22),
+        })
+    }
+
+    /// Transforms a point.
+    pub fn transform_point(&self, x: f64, y: f64) -> (f64, f64) {
+        let (x_size, y_size) = self.transform_size(x, y);
+
+        (x_size + self.m31, y_size + self.m32)
+    }
+
+    /// Transforms a distance, ignoring translation.
+    pub fn transform_size(&self, x: f64, y: f64) -> (f64, f64) {
+        (x * self.m11 + y * self.m21, x * self.m12 + y * self.m22)
+    }
+
+    /// The largest factor by which this transformation stretches distances.
+    pub(crate) fn max_scale(&self) -> f64 {
+        // This is the largest singular value of the linear part.
+        let a = self.m11 * self.m11 + self.m12 * self.m12;
+        let b = self.m11 * self.m21 + self.m12 * self.m22;
+        let c = self.m21 * self.m21 + self.m22 * self.m22;
+        let mean = (a + c) / 2.0;
+        let spread = (((a - c) / 2.0).powi(2) + b * b).sqrt();
+
+        (mean + spread).sqrt()
+    }
+}
@@ -0,0 +1,341 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The [`DrawContext`] that *libui-ng* passes to area handlers.
+
+use std::{ffi::CString, ptr};
+
+use super::{
+    Brush,
+    Command,
+    DrawContext,
+    FillMode,
+    GradientStop,
+    LineCap,
+    LineJoin,
+    Matrix,
+    Path,
+    StrokeParams,
+    TextAlign,
+    TextLayout,
+};
+use crate::{prelude::*, Color};
+
+/// A [`DrawContext`] that forwards to a `uiDrawContext`.
+pub(crate) struct NativeContext {
+    ptr: *mut uiDrawContext,
+}
+
+impl NativeContext {
+    /// Wraps a draw context.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be the context of an ongoing draw event, and the returned value must not outlive
+    /// the event.
+    pub(crate) unsafe fn new(ptr: *mut uiDrawContext) -> Self {
+        Self { ptr }
+    }
+}
+
+impl DrawContext for NativeContext {
+    fn fill(&mut self, path: &Path, brush: &Brush) {
+        let path = NativePath::new(path);
+        let mut brush = NativeBrush::new(brush);
+        unsafe { uiDrawFill(self.ptr, path.ptr, &mut brush.raw) };
+    }
+
+    fn stroke(&mut self, path: &Path, brush: &Brush, params: &StrokeParams) {
+        let path = NativePath::new(path);
+        let mut brush = NativeBrush::new(brush);
+        let mut dashes = params.dashes.clone();
+        let mut raw_params = uiDrawStrokeParams {
+            Cap: match params.cap {
+                LineCap::Flat => uiDrawLineCapFlat,
+                LineCap::Round => uiDrawLineCapRound,
+                LineCap::Square => uiDrawLineCapSquare,
+            } as _,
+            Join: match params.join {
+                LineJoin::Miter => uiDrawLineJoinMiter,
+                LineJoin::Round => uiDrawLineJoinRound,
+                LineJoin::Bevel => uiDrawLineJoinBevel,
+            } as _,
+            Thickness: params.thickness,
+            MiterLimit: params.miter_limit,
+            Dashes: if dashes.is_empty() { ptr::null_mut() } else { dashes.as_mut_ptr() },
+            NumDashes: dashes.len() as _,
+            DashPhase: params.dash_phase,
+        };
+        unsafe { uiDrawStroke(self.ptr, path.ptr, &mut brush.raw, &mut raw_params) };
+    }
+
+    fn transform(&mut self, matrix: &Matrix) {
+        let mut raw = uiDrawMatrix {
+            M11: matrix.m11,
+            M12: matrix.m12,
+            M21: matrix.m21,
+            M22: matrix.m22,
+            M31: matrix.m31,
+            M32: matrix.m32,
+        };
+        unsafe { uiDrawTransform(self.ptr, &mut raw) };
+    }
+
+    fn clip(&mut self, path: &Path) {
+        let path = NativePath::new(path);
+        unsafe { uiDrawClip(self.ptr, path.ptr) };
+    }
+
+    fn save(&mut self) {
+        unsafe { uiDrawSave(self.ptr) };
+    }
+
+    fn restore(&mut self) {
+        unsafe { uiDrawRestore(self.ptr) };
+    }
+
+    fn draw_text(&mut self, layout: &TextLayout, x: f64, y: f64) {
+        let ctx = self.ptr;
+        with_text_layout(layout, |tl| unsafe { uiDrawText(ctx, tl, x, y) });
+    }
+
+    fn measure_text(&mut self, layout: &TextLayout) -> (f64, f64) {
+        with_text_layout(layout, |tl| {
+            let (mut width, mut height) = (0.0, 0.0);
+            unsafe { uiDrawTextLayoutExtents(tl, &mut width, &mut height) };
+
+            (width, height)
+        })
+        .unwrap_or((0.0, 0.0))
+    }
+}
+
+/// A `uiDrawPath` built from a [`Path`], which is freed when dropped.
+struct NativePath {
+    ptr: *mut uiDrawPath,
+}
+
+impl NativePath {
+    fn new(path: &Path) -> Self {
+        let fill_mode = match path.fill_mode() {
+            FillMode::Winding => uiDrawFillModeWinding,
+            FillMode::Alternate => uiDrawFillModeAlternate,
+        };
+        let ptr = unsafe { uiDrawNewPath(fill_mode as _) };
+
+        // *libui-ng* aborts when lines are drawn outside of a figure, whereas [`Path`] starts a
+        // figure implicitly, so figures are tracked here to do the same.
+        let mut is_in_figure = false;
+        for command in path.commands() {
+            unsafe {
+                match *command {
+                    Command::NewFigure { x, y } => {
+                        uiDrawPathNewFigure(ptr, x, y);
+                        is_in_figure = true;
+                    }
+                    Command::NewFigureWithArc(arc) => {
+                        uiDrawPathNewFigureWithArc(
+                            ptr,
+                            arc.x_center,
+                            arc.y_center,
+                            arc.radius,
+                            arc.start_angle,
+                            arc.sweep,
+                            arc.is_negative.into(),
+                        );
+                        is_in_figure = true;
+                    }
+                    Command::LineTo { x, y } => {
+                        if is_in_figure {
+                            uiDrawPathLineTo(ptr, x, y);
+                        } else {
+                            uiDrawPathNewFigure(ptr, x, y);
+                            is_in_figure = true;
+                        }
+                    }
+                    Command::ArcTo(arc) => {
+                        let arc_fn = if is_in_figure {
+                            uiDrawPathArcTo
+                        } else {
+                            uiDrawPathNewFigureWithArc
+                        };
+                        arc_fn(
+                            ptr,
+                            arc.x_center,
+                            arc.y_center,
+                            arc.radius,
+                            arc.start_angle,
+                            arc.sweep,
+                            arc.is_negative.into(),
+                        );
+                        is_in_figure = true;
+                    }
+                    Command::BezierTo {
+                        c1x,
+                        c1y,
+                        c2x,
+                        c2y,
+                        x,
+                        y,
+                    } => {
+                        if !is_in_figure {
+                            uiDrawPathNewFigure(ptr, c1x, c1y);
+                            is_in_figure = true;
+                        }
+                        uiDrawPathBezierTo(ptr, c1x, c1y, c2x, c2y, x, y);
+                    }
+                    Command::CloseFigure => {
+                        if is_in_figure {
+                            uiDrawPathCloseFigure(ptr);
+                            is_in_figure = false;
+                        }
+                    }
+                    Command::AddRectangle {
+                        x,
+                        y,
+                        width,
+                        height,
+                    } => {
+                        uiDrawPathAddRectangle(ptr, x, y, width, height);
+                        is_in_figure = false;
+                    }
+                }
+            }
+        }
+        unsafe { uiDrawPathEnd(ptr) };
+
+        Self { ptr }
+    }
+}
+
+impl Drop for NativePath {
+    fn drop(&mut self) {
+        unsafe { uiDrawFreePath(self.ptr) };
+    }
+}
+
+/// A `uiDrawBrush` built from a [`Brush`], along with the gradient stops it points to.
+struct NativeBrush {
+    raw: uiDrawBrush,
+    _stops: Vec<uiDrawBrushGradientStop>,
+}
+
+impl NativeBrush {
+    fn new(brush: &Brush) -> Self {
+        let mut raw = uiDrawBrush {
+            Type: uiDrawBrushTypeSolid as _,
+            R: 0.0,
+            G: 0.0,
+            B: 0.0,
+            A: 0.0,
+            X0: 0.0,
+            Y0: 0.0,
+            X1: 0.0,
+            Y1: 0.0,
+            OuterRadius: 0.0,
+            Stops: ptr::null_mut(),
+            NumStops: 0,
+        };
+        let mut stops = Vec::new();
+
+        match brush {
+            Brush::Solid(color) => {
+                raw.R = color.red;
+                raw.G = color.green;
+                raw.B = color.blue;
+                raw.A = color.alpha;
+            }
+            Brush::LinearGradient {
+                start,
+                end,
+                stops: brush_stops,
+            } => {
+                raw.Type = uiDrawBrushTypeLinearGradient as _;
+                (raw.X0, raw.Y0) = *start;
+                (raw.X1, raw.Y1) = *end;
+                stops = brush_stops.iter().map(raw_stop).collect();
+            }
+            Brush::RadialGradient {
+                start,
+                outer_center,
+                outer_radius,
+                stops: brush_stops,
+            } => {
+                raw.Type = uiDrawBrushTypeRadialGradient as _;
+                (raw.X0, raw.Y0) = *start;
+                (raw.X1, raw.Y1) = *outer_center;
+                raw.OuterRadius = *outer_radius;
+                stops = brush_stops.iter().map(raw_stop).collect();
+            }
+        }
+        // The stops live on the heap, so moving the vector doesn't invalidate this pointer.
+        if !stops.is_empty() {
+            raw.Stops = stops.as_mut_ptr();
+            raw.NumStops = stops.len() as _;
+        }
+
+        Self { raw, _stops: stops }
+    }
+}
+
+fn raw_stop(stop: &GradientStop) -> uiDrawBrushGradientStop {
+    let Color {
+        red,
+        green,
+        blue,
+        alpha,
+    } = stop.color;
+
+    uiDrawBrushGradientStop {
+        Pos: stop.pos,
+        R: red,
+        G: green,
+        B: blue,
+        A: alpha,
+    }
+}
+
+/// Calls the given function with a `uiDrawTextLayout` built from a [`TextLayout`].
+///
+/// Returns `None`, without calling the function, if the text is empty or either it or the font
+/// family contains a NUL byte.
+fn with_text_layout<R>(
+    layout: &TextLayout,
+    f: impl FnOnce(*mut uiDrawTextLayout) -> R,
+) -> Option<R> {
+    if layout.text.is_empty() {
+        return None;
+    }
+    let text = CString::new(layout.text.as_str()).ok()?;
+    let desc = layout.font.to_descriptor().ok()?;
+
+    unsafe {
+        let string = uiNewAttributedString(text.as_ptr());
+        let Color {
+            red,
+            green,
+            blue,
+            alpha,
+        } = layout.color;
+        // The string takes ownership of the attribute.
+        let color = uiNewColorAttribute(red, green, blue, alpha);
+        uiAttributedStringSetAttribute(string, color, 0, uiAttributedStringLen(string));
+
+        let mut params = uiDrawTextLayoutParams {
+            String: string,
+            // *libui-ng* never writes through this pointer.
+            DefaultFont: desc.as_ptr() as *mut _,
+            Width: layout.width.unwrap_or(-1.0),
+            Align: match layout.align {
+                TextAlign::Left => uiDrawTextAlignLeft,
+                TextAlign::Center => uiDrawTextAlignCenter,
+                TextAlign::Right => uiDrawTextAlignRight,
+            } as _,
+        };
+        let tl = uiDrawNewTextLayout(&mut params);
+        let result = f(tl);
+        uiDrawFreeTextLayout(tl);
+        uiFreeAttributedString(string);
+
+        Some(result)
+    }
+}
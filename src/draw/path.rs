@@ -0,0 +1,312 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! [`Path`].
+
+use std::f64::consts::TAU;
+
+/// A shape made up of figures of lines and curves.
+///
+/// A path is built by issuing [`Command`]s through its methods. Each figure begins with
+/// [`Path::new_figure`] or [`Path::new_figure_with_arc`] and may be closed with
+/// [`Path::close_figure`].
+///
+/// Angles are in radians, and as the y-axis points downwards, positive angles are clockwise.
+///
+/// # Examples
+///
+/// ```no_run
+/// use boing::draw::{Arc, FillMode, Path};
+///
+/// // A pie slice.
+/// let mut path = Path::new(FillMode::Winding);
+/// path.new_figure(50.0, 50.0)
+///     .arc_to(Arc {
+///         x_center: 50.0,
+///         y_center: 50.0,
+///         radius: 40.0,
+///         start_angle: 0.0,
+///         sweep: std::f64::consts::FRAC_PI_2,
+///         is_negative: false,
+///     })
+///     .close_figure();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path {
+    fill_mode: FillMode,
+    commands: Vec<Command>,
+}
+
+/// The rule that determines which points are inside a [`Path`] with overlapping figures.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FillMode {
+    /// Points are inside if the figures wind around them a nonzero number of times.
+    Winding,
+    /// Points are inside if the figures wind around them an odd number of times.
+    Alternate,
+}
+
+/// A single step in building a [`Path`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command {
+    NewFigure { x: f64, y: f64 },
+    NewFigureWithArc(Arc),
+    LineTo { x: f64, y: f64 },
+    /// Draws a line to the start of the arc, followed by the arc.
+    ArcTo(Arc),
+    BezierTo {
+        c1x: f64,
+        c1y: f64,
+        c2x: f64,
+        c2y: f64,
+        x: f64,
+        y: f64,
+    },
+    CloseFigure,
+    AddRectangle {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
+}
+
+/// A circular arc.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Arc {
+    pub x_center: f64,
+    pub y_center: f64,
+    pub radius: f64,
+    /// The angle at which the arc starts.
+    pub start_angle: f64,
+    /// The angle spanned by the arc, which is at most a full turn.
+    pub sweep: f64,
+    /// Whether or not the arc runs counterclockwise from `start_angle`.
+    pub is_negative: bool,
+}
+
+impl Arc {
+    /// The point on this arc at the given angle.
+    pub fn point_at(&self, angle: f64) -> (f64, f64) {
+        (
+            self.x_center + self.radius * angle.cos(),
+            self.y_center + self.radius * angle.sin(),
+        )
+    }
+
+    /// The angle at which this arc ends.
+    pub fn end_angle(&self) -> f64 {
+        let sweep = self.sweep.clamp(0.0, TAU);
+        if self.is_negative {
+            self.start_angle - sweep
+        } else {
+            self.start_angle + sweep
+        }
+    }
+}
+
+impl Path {
+    /// Creates a new, empty path.
+    pub fn new(fill_mode: FillMode) -> Self {
+        Self {
+            fill_mode,
+            commands: Vec::new(),
+        }
+    }
+
+    /// The fill mode of this path.
+    pub fn fill_mode(&self) -> FillMode {
+        self.fill_mode
+    }
+
+    /// The commands that built this path, in order.
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    /// Begins a new figure at the given point.
+    pub fn new_figure(&mut self, x: f64, y: f64) -> &mut Self {
+        self.push(Command::NewFigure { x, y })
+    }
+
+    /// Begins a new figure with an arc.
+    pub fn new_figure_with_arc(&mut self, arc: Arc) -> &mut Self {
+        self.push(Command::NewFigureWithArc(arc))
+    }
+
+    /// Draws a line from the current point to the given point.
+    pub fn line_to(&mut self, x: f64, y: f64) -> &mut Self {
+        self.push(Command::LineTo { x, y })
+    }
+
+    /// Draws a line from the current point to the start of an arc, followed by the arc.
+    pub fn arc_to(&mut self, arc: Arc) -> &mut Self {
+        self.push(Command::ArcTo(arc))
+    }
+
+    /// Draws a cubic Bézier curve from the current point to `(x, y)` with the control points
+    /// `(c1x, c1y)` and `(c2x, c2y)`.
+    pub fn bezier_to(&mut self, c1x: f64, c1y: f64, c2x: f64, c2y: f64, x: f64, y: f64) -> &mut Self {
+        self.push(Command::BezierTo {
+            c1x,
+            c1y,
+            c2x,
+            c2y,
+            x,
+            y,
+        })
+    }
+
+    /// Closes the current figure with a line back to its start.
+    pub fn close_figure(&mut self) -> &mut Self {
+        self.push(Command::CloseFigure)
+    }
+
+    /// Adds a rectangle as a closed figure of its own.
+    pub fn add_rectangle(&mut self, x: f64, y: f64, width: f64, height: f64) -> &mut Self {
+        self.push(Command::AddRectangle {
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    fn push(&mut self, command: Command) -> &mut Self {
+        self.commands.push(command);
+
+        self
+    }
+
+    /// Approximates the figures of this path with polylines.
+    ///
+    /// No point of a curve is farther than `tolerance` from its approximation.
+    pub(crate) fn flatten(&self, tolerance: f64) -> Vec<Polyline> {
+        let mut polylines = Vec::new();
+        let mut current: Option<Polyline> = None;
+
+        for command in &self.commands {
+            match *command {
+                Command::NewFigure { x, y } => {
+                    polylines.extend(current.replace(Polyline::new((x, y))));
+                }
+                Command::NewFigureWithArc(arc) => {
+                    let mut figure = Polyline::new(arc.point_at(arc.start_angle));
+                    figure.push_arc(&arc, tolerance);
+                    polylines.extend(current.replace(figure));
+                }
+                Command::LineTo { x, y } => {
+                    current.get_or_insert_with(|| Polyline::new((x, y))).points.push((x, y));
+                }
+                Command::ArcTo(arc) => {
+                    let start = arc.point_at(arc.start_angle);
+                    let figure = current.get_or_insert_with(|| Polyline::new(start));
+                    figure.points.push(start);
+                    figure.push_arc(&arc, tolerance);
+                }
+                Command::BezierTo {
+                    c1x,
+                    c1y,
+                    c2x,
+                    c2y,
+                    x,
+                    y,
+                } => {
+                    let figure = current.get_or_insert_with(|| Polyline::new((c1x, c1y)));
+                    figure.push_bezier((c1x, c1y), (c2x, c2y), (x, y), tolerance);
+                }
+                Command::CloseFigure => {
+                    if let Some(mut figure) = current.take() {
+                        figure.is_closed = true;
+                        polylines.push(figure);
+                    }
+                }
+                Command::AddRectangle {
+                    x,
+                    y,
+                    width,
+                    height,
+                } => {
+                    polylines.extend(current.take());
+                    polylines.push(Polyline {
+                        points: vec![(x, y), (x + width, y), (x + width, y + height), (x, y + height)],
+                        is_closed: true,
+                    });
+                }
+            }
+        }
+        polylines.extend(current);
+
+        for polyline in &mut polylines {
+            polyline.points.dedup();
+        }
+
+        polylines
+    }
+}
+
+/// A figure of a [`Path`], approximated by straight lines.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Polyline {
+    pub points: Vec<(f64, f64)>,
+    pub is_closed: bool,
+}
+
+impl Polyline {
+    fn new(start: (f64, f64)) -> Self {
+        Self {
+            points: vec![start],
+            is_closed: false,
+        }
+    }
+
+    fn last(&self) -> (f64, f64) {
+        // Polylines are never created empty.
+        self.points[self.points.len() - 1]
+    }
+
+    fn push_arc(&mut self, arc: &Arc, tolerance: f64) {
+        let end_angle = arc.end_angle();
+        let sweep = end_angle - arc.start_angle;
+        let count = segment_count(arc.radius, sweep, tolerance);
+        self.points.extend((1..=count).map(|i| {
+            arc.point_at(arc.start_angle + sweep * (i as f64) / (count as f64))
+        }));
+    }
+
+    fn push_bezier(&mut self, c1: (f64, f64), c2: (f64, f64), end: (f64, f64), tolerance: f64) {
+        let start = self.last();
+
+        // The distance between a cubic Bézier curve and its approximation by `n` lines is at most
+        // `3 / 4 * d / n^2`, where `d` is the largest second difference of its control points.
+        let second_diff = |a: (f64, f64), b: (f64, f64), c: (f64, f64)| {
+            (a.0 - 2.0 * b.0 + c.0).hypot(a.1 - 2.0 * b.1 + c.1)
+        };
+        let d = second_diff(start, c1, c2).max(second_diff(c1, c2, end));
+        let count = (0.75 * d / tolerance).sqrt().ceil().clamp(1.0, 1024.0) as usize;
+
+        self.points.extend((1..=count).map(|i| {
+            let t = (i as f64) / (count as f64);
+            let u = 1.0 - t;
+            let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+
+            (
+                a * start.0 + b * c1.0 + c * c2.0 + d * end.0,
+                a * start.1 + b * c1.1 + c * c2.1 + d * end.1,
+            )
+        }));
+    }
+}
+
+/// The number of lines needed to approximate an arc of the given radius and sweep to within
+/// `tolerance`.
+pub(crate) fn segment_count(radius: f64, sweep: f64, tolerance: f64) -> usize {
+    let radius = radius.abs();
+    if radius <= tolerance {
+        return 1;
+    }
+
+    // Each line deviates from the arc by `radius * (1 - cos(step / 2))`.
+    let step = 2.0 * (1.0 - tolerance / radius).acos();
+
+    (sweep.abs() / step).ceil().clamp(1.0, 4096.0) as usize
+}
@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! [`TextLayout`].
+
+use crate::{Color, Font};
+
+/// A block of text in a single font and color, ready to be drawn with
+/// [`DrawContext::draw_text`](super::DrawContext::draw_text).
+///
+/// # Examples
+///
+/// ```no_run
+/// use boing::{
+///     draw::{TextAlign, TextLayout},
+///     font::{Font, StretchKind},
+///     Color,
+/// };
+///
+/// let layout = TextLayout {
+///     text: "Hello, world!".into(),
+///     font: Font {
+///         family: "sans-serif".into(),
+///         size: 12.0,
+///         weight: 400,
+///         italic_kind: None,
+///         stretch: StretchKind::Normal,
+///     },
+///     color: Color::BLACK,
+///     width: None,
+///     align: TextAlign::Left,
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextLayout {
+    pub text: String,
+    pub font: Font,
+    pub color: Color,
+    /// The width at which lines are wrapped, or `None` if lines are only broken at newlines.
+    pub width: Option<f64>,
+    /// The alignment of lines within [`TextLayout::width`].
+    pub align: TextAlign,
+}
+
+/// The horizontal alignment of the lines of a [`TextLayout`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TextAlign {
+    Left,
+    Center,
+    Right,
+}
+
+impl TextLayout {
+    /// The ratio of the height of a line to the size of its font.
+    pub(crate) const LINE_HEIGHT: f64 = 1.2;
+
+    /// The ratio of the average advance of a character to the size of its font.
+    const CHAR_WIDTH: f64 = 0.55;
+
+    /// A rough estimate of the width and height of this text, without consulting the font.
+    ///
+    /// Each character is assumed to be about half as wide as the font is tall. This is how
+    /// contexts that can't shape text implement [`DrawContext::measure_text`].
+    ///
+    /// [`DrawContext::measure_text`]: super::DrawContext::measure_text
+    pub fn estimated_size(&self) -> (f64, f64) {
+        let size = self.font.size;
+        let widest = self
+            .text
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let width = (widest as f64) * Self::CHAR_WIDTH * size;
+        let line_count = self.text.lines().count().max(1);
+
+        match self.width {
+            Some(max_width) if max_width > 0.0 && width > max_width => {
+                // Assume that lines wrap evenly.
+                let wrapped = (width / max_width).ceil() as usize + line_count - 1;
+                (max_width, (wrapped as f64) * Self::LINE_HEIGHT * size)
+            }
+            _ => (width, (line_count as f64) * Self::LINE_HEIGHT * size),
+        }
+    }
+}
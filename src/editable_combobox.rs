@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! [`EditableCombobox`].
+
+use crate::prelude::*;
+
+impl Ui {
+    /// Creates a new [`EditableCombobox`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), boing::Error> {
+    /// use boing::Ui;
+    ///
+    /// let ui: Ui;
+    /// # ui = Ui::new()?;
+    ///
+    /// let host = ui.create_editable_combobox()?;
+    /// host.push_new_item("localhost")?;
+    /// host.push_new_item("example.com")?;
+    /// host.on_changed(|host| {
+    ///     println!("Connecting to {}", host.text());
+    /// });
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_editable_combobox<'ui>(
+        &'ui self,
+    ) -> Result<&'ui EditableCombobox, crate::Error> {
+        call_libui_new_fn!(
+            ui: self,
+            fn: uiNewEditableCombobox() -> EditableCombobox,
+        )
+    }
+}
+
+def_subcontrol!(
+    docs: "
+        A box in which a single line of text is entered, with a drop-down menu of suggested values.
+
+        Unlike [`Combobox`](crate::Combobox), the text of an editable combobox is not limited to
+        its items.
+
+        # Examples
+
+        ```no_run
+        // TODO
+        ```
+    ",
+    ty: EditableCombobox,
+    kind: EditableCombobox,
+    handle: uiEditableCombobox,
+    cb_fns: [ on_changed() ],
+);
+
+impl<'ui> EditableCombobox<'ui> {
+    bind_set_text_fn!(
+        docs: "
+            Appends a new suggested item with the given text.
+
+            *libui-ng* provides no means of removing items once appended.
+
+            # Examples
+
+            ```no_run
+            // TODO
+            ```
+        ",
+        self: { fn: push_new_item(text) -> () },
+        libui: { fn: uiEditableComboboxAppend() },
+    );
+
+    bind_text_fn!(
+        docs: "
+            The text displayed in this combobox.
+
+            # Examples
+
+            ```no_run
+            // TODO
+            ```
+        ",
+        self: {
+            fn: text(),
+            raw_fn: raw_text(),
+            as_ptr_fn: text_ptr(),
+        },
+        libui: { fn: uiEditableComboboxText() },
+    );
+
+    bind_set_text_fn!(
+        docs: "
+            Sets the text displayed in this combobox.
+
+            # Examples
+
+            ```no_run
+            // TODO
+            ```
+        ",
+        self: { fn: set_text(text) -> () },
+        libui: { fn: uiEditableComboboxSetText() },
+    );
+
+    bind_callback_fn!(
+        docs: "
+            Sets a callback for when the text within this combobox changes, either because the user
+            typed into it or selected an item.
+
+            This callback is unset by default.
+
+            # Examples
+
+            ```no_run
+            // TODO
+            ```
+        ",
+        self: {
+            ty: EditableCombobox<'ui>,
+            handle: uiEditableCombobox,
+            fn: on_changed(),
+            emit: emit_changed(),
+            cb: { sig: f -> () },
+        },
+        libui: {
+            fn: uiEditableComboboxOnChanged(),
+            cb: { sig: () -> () },
+        },
+    );
+
+    /// Simulates typing, replacing the text of this combobox and then calling the callback set by
+    /// [`EditableCombobox::on_changed`] as though the user had typed `text`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn simulate_typing(&self, text: impl AsRef<str>) -> Result<(), crate::Error> {
+        self.set_text(text)?;
+        self.emit_changed();
+
+        Ok(())
+    }
+}
@@ -1,8 +1,12 @@
 // SPDX-License-Identifier: MPL-2.0
 
-//!
+//! [`Picker`] and the [`Font`] it selects.
 
-use std::mem::MaybeUninit;
+use std::{
+    ffi::{CStr, CString},
+    mem,
+    ptr,
+};
 
 use crate::prelude::*;
 
@@ -14,7 +18,7 @@ impl Ui {
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn create_font_picker<'ui>(&'ui self) -> Result<&'ui mut Picker, crate::Error> {
+    pub fn create_font_picker<'ui>(&'ui self) -> Result<&'ui Picker, crate::Error> {
         call_libui_new_fn!(
             ui: self,
             fn: uiNewFontButton() -> Picker,
@@ -33,35 +37,123 @@ def_subcontrol!(
         ```
     ",
     ty: Picker,
+    kind: FontPicker,
     handle: uiFontButton,
     cb_fns: [ on_selected() ],
 );
 
-/// A font returned by [`Picker::selected_font`].
+/// A font description, as returned by [`Picker::selected_font`].
+///
+/// A [`Font`] may be converted to a [`Descriptor`] with [`Font::to_descriptor`] to be handed back
+/// to *libui-ng*.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Font {
     pub family: String,
     pub size: f64,
+    /// The weight, where 400 is normal and 700 is bold.
     pub weight: u32,
     pub italic_kind: Option<ItalicKind>,
     pub stretch: StretchKind,
 }
 
+impl Font {
+    /// Copies a font descriptor from *libui-ng*.
+    ///
+    /// # Safety
+    ///
+    /// `desc.Family` must be null or point to a valid C string.
+    pub(crate) unsafe fn from_desc(desc: &uiFontDescriptor) -> Result<Self, crate::Error> {
+        let family = if desc.Family.is_null() {
+            String::new()
+        } else {
+            CStr::from_ptr(desc.Family).to_string_lossy().into()
+        };
+
+        Ok(Self {
+            family,
+            size: desc.Size,
+            weight: desc.Weight,
+            italic_kind: ItalicKind::try_from_desc(desc.Italic)?,
+            stretch: StretchKind::try_from_desc(desc.Stretch)?,
+        })
+    }
+
+    /// Converts this font to a descriptor that may be passed to *libui-ng*.
+    ///
+    /// This fails if [`Font::family`] contains a NUL byte.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn to_descriptor(&self) -> Result<Descriptor, crate::Error> {
+        let family = make_cstring!(self.family.as_str());
+        let raw = uiFontDescriptor {
+            // *libui-ng* never writes through this pointer.
+            Family: family.as_ptr() as *mut _,
+            Size: self.size,
+            Weight: self.weight,
+            Italic: ItalicKind::into_desc(self.italic_kind),
+            Stretch: self.stretch.into_desc(),
+        };
+
+        Ok(Descriptor { raw, _family: family })
+    }
+}
+
+/// A `uiFontDescriptor` created by [`Font::to_descriptor`].
+///
+/// The descriptor owns its family name, which is freed when the descriptor is dropped.
+pub struct Descriptor {
+    raw: uiFontDescriptor,
+    // `raw.Family` points into this string. Moving a `CString` doesn't move its contents, so the
+    // pointer remains valid for as long as the descriptor lives.
+    _family: CString,
+}
+
+impl Descriptor {
+    /// A pointer to the underlying *libui-ng* descriptor.
+    ///
+    /// The pointer is valid for as long as this descriptor is.
+    pub fn as_ptr(&self) -> *const uiFontDescriptor {
+        &self.raw
+    }
+}
+
+/// The slant of an italic [`Font`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum ItalicKind {
     Oblique,
     True,
 }
 
 impl ItalicKind {
-    fn try_from_desc(value: u32) -> Result<Option<Self>, ()> {
+    pub(crate) fn try_from_desc(value: uiTextItalic) -> Result<Option<Self>, crate::Error> {
         match value {
-            0 => Ok(None),
-            1 => Ok(Some(Self::Oblique)),
-            2 => Ok(Some(Self::True)),
-            _ => Err(()),
+            uiTextItalicNormal => Ok(None),
+            uiTextItalicOblique => Ok(Some(Self::Oblique)),
+            uiTextItalicItalic => Ok(Some(Self::True)),
+            _ => Err(unknown_desc_value("italic", value)),
+        }
+    }
+
+    pub(crate) fn into_desc(kind: Option<Self>) -> uiTextItalic {
+        match kind {
+            None => uiTextItalicNormal,
+            Some(Self::Oblique) => uiTextItalicOblique,
+            Some(Self::True) => uiTextItalicItalic,
         }
     }
 }
 
+/// The width of a [`Font`].
+///
+/// The variants are listed in the same order as *libui-ng*'s `uiTextStretch`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum StretchKind {
     UltraCondensed,
     ExtraCondensed,
@@ -75,60 +167,62 @@ pub enum StretchKind {
 }
 
 impl StretchKind {
-    fn try_from_desc(value: u32) -> Result<Self, ()> {
-        match value {
-            0 => Ok(Self::UltraCondensed),
-            1 => Ok(Self::ExtraCondensed),
-            2 => Ok(Self::Condensed),
-            3 => Ok(Self::SemiCondensed),
-            4 => Ok(Self::Normal),
-            5 => Ok(Self::SemiExpanded),
-            6 => Ok(Self::ExtraExpanded),
-            7 => Ok(Self::UltraExpanded),
-            _ => Err(()),
-        }
+    const ALL: [Self; 9] = [
+        Self::UltraCondensed,
+        Self::ExtraCondensed,
+        Self::Condensed,
+        Self::SemiCondensed,
+        Self::Normal,
+        Self::SemiExpanded,
+        Self::Expanded,
+        Self::ExtraExpanded,
+        Self::UltraExpanded,
+    ];
+
+    pub(crate) fn try_from_desc(value: uiTextStretch) -> Result<Self, crate::Error> {
+        usize::try_from(value)
+            .ok()
+            .and_then(|i| Self::ALL.get(i).copied())
+            .ok_or_else(|| unknown_desc_value("stretch", value))
+    }
+
+    pub(crate) fn into_desc(self) -> uiTextStretch {
+        self as uiTextStretch
+    }
+}
+
+fn unknown_desc_value(field: &str, value: u32) -> crate::Error {
+    crate::Error::LibuiFn {
+        name: "uiFontButtonFont",
+        cause: Some(format!("unknown {} value {}", field, value)),
     }
 }
 
 impl<'ui> Picker<'ui> {
     /// The font currently selected by this picker.
     ///
+    /// This fails if *libui-ng* reports an italic or stretch value unknown to *boing*.
+    ///
     /// # Examples
     ///
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn selected_font(&self) -> Font {
-        let mut desc = MaybeUninit::<uiFontDescriptor>::uninit();
-
-        // TODO: This is UB if, for example, *libui-ng* doesn't initialize `desc` when a font hasn't
-        // yet been selected. It would be safer to use default values instead of `MaybeUnunit`, but
-        // that would probably necessitate a heap allocation.
-        unsafe { uiFontButtonFont(self.as_ptr(), desc.as_mut_ptr()) };
-
-        // SAFETY: *libui-ng* should've initialized it...
-        let mut desc = unsafe { desc.assume_init() };
+    pub fn selected_font(&self) -> Result<Font, crate::Error> {
+        // SAFETY: `uiFontDescriptor` is plain old data, and a null family is handled by
+        // `Font::from_desc`. Zeroing it first means that nothing is left uninitialized should
+        // *libui-ng* not fill it out.
+        let mut desc: uiFontDescriptor = unsafe { mem::zeroed() };
+        unsafe { uiFontButtonFont(self.as_ptr(), &mut desc) };
 
-        // SAFETY: `desc.Family` should've been set by *libui-ng*...
-        let family = unsafe { std::ffi::CStr::from_ptr(desc.Family) }.to_string_lossy().into();
+        // SAFETY: `desc.Family` is either null or was set by *libui-ng*.
+        let font = unsafe { Font::from_desc(&desc) };
 
-        let size = desc.Size;
-        let weight = desc.Weight;
-        let italic_kind = ItalicKind::try_from_desc(desc.Italic).unwrap();
-        let stretch = StretchKind::try_from_desc(desc.Stretch).unwrap();
+        // Now that we've copied all the data from `desc`, we can safely free it---even if the
+        // conversion failed.
+        unsafe { uiFreeFontButtonFont(ptr::addr_of_mut!(desc)) };
 
-        // Now that we've copied all the data from `desc`, we can safely free it.
-        unsafe { uiFreeFontButtonFont(std::ptr::addr_of_mut!(desc)) };
-        // To be sure, we now can no longer use this!
-        drop(desc);
-
-        Font {
-            family,
-            size,
-            weight,
-            italic_kind,
-            stretch,
-        }
+        font
     }
 
     bind_callback_fn!(
@@ -147,6 +241,7 @@ impl<'ui> Picker<'ui> {
             ty: Picker<'ui>,
             handle: uiFontButton,
             fn: on_selected(),
+            emit: emit_selected(),
             cb: { sig: f -> () },
         },
         libui: {
@@ -154,4 +249,18 @@ impl<'ui> Picker<'ui> {
             cb: { sig: () -> () },
         },
     );
+
+    /// Simulates a selection, calling the callback set by [`Picker::on_selected`] as though the
+    /// user had confirmed the font dialog.
+    ///
+    /// *libui-ng* offers no way to change the selected font, so the callback sees the current font.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn simulate_selection(&self) {
+        self.emit_selected();
+    }
 }
@@ -4,8 +4,26 @@
 
 use crate::prelude::*;
 
+impl Ui {
+    /// Creates a new [`Form`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_form<'ui>(&'ui self) -> Result<&'ui Form, crate::Error> {
+        call_libui_new_fn!(
+            ui: self,
+            fn: uiNewForm() -> Form,
+        )
+    }
+}
+
 def_subcontrol!(
     docs: "
+        A column of labeled controls.
+
         # Examples
 
         ```no_run
@@ -13,7 +31,120 @@ def_subcontrol!(
         ```
     ",
     ty: Form,
+    kind: Form,
     handle: uiForm,
 );
 
-impl Form<'_> {}
+impl Form<'_> {
+    bind_bool_fn!(
+        docs: "
+            Determines if this form is padded.
+
+            Forms are unpadded by default.
+
+            # Examples
+
+            ```no_run
+            // TODO
+            ```
+        ",
+        self: { fn: is_padded() -> bool },
+        libui: { fn: uiFormPadded() },
+    );
+
+    bind_fn!(
+        docs: "
+            Sets whether or not this form is padded.
+
+            # Examples
+
+            ```no_run
+            // TODO
+            ```
+        ",
+        self: { fn: set_padded(value: bool) },
+        libui: { fn: uiFormSetPadded() },
+    );
+
+    bind_fn!(
+        docs: "
+            The number of child controls this form contains.
+
+            # Examples
+
+            ```no_run
+            // TODO
+            ```
+        ",
+        self: {
+            fn: child_count() -> u16,
+            map_out: |_, count| to_u16!(count),
+        },
+        libui: { fn: uiFormNumChildren() },
+    );
+
+    bind_fn!(
+        docs: "
+            Removes the child control at the given index.
+
+            This action may invalidate the previous indices of other items.
+
+            # Examples
+
+            ```no_run
+            // TODO
+            ```
+        ",
+        self: { fn: remove_child(index: u16) },
+        libui: { fn: uiFormDelete() },
+    );
+
+    /// Appends a new child control with the given label, returning its index.
+    ///
+    /// Note that the returned index may be invalidated after deletion operations. *boing* provides
+    /// no mechanism to update the index in these cases, so it is the programmer's responsibility to
+    /// keep track of each child's current index.
+    ///
+    /// # Arguments
+    ///
+    /// When `can_stretch` is `true`, the child control will fill the remaining height of the form.
+    /// Otherwise, controls retain their original size.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn push_new_child<'c>(
+        &self,
+        label: impl AsRef<str>,
+        child: &impl Deref<Target = Control<'c>>,
+        can_stretch: bool,
+    ) -> Result<u16, crate::Error> {
+        self.append(label.as_ref(), child, can_stretch)
+    }
+
+    /// Like [`Form::push_new_child`], but for a child that may still be referred to elsewhere.
+    pub(crate) fn append(
+        &self,
+        label: &str,
+        child: &Control,
+        can_stretch: bool,
+    ) -> Result<u16, crate::Error> {
+        let index = self.child_count();
+
+        // The label is converted first so that the child isn't marked as such if this fails.
+        let label = make_cstring!(label);
+        child.make_child();
+        unsafe {
+            uiFormAppend(
+                self.as_ptr(),
+                label.as_ptr(),
+                child.as_ptr(),
+                can_stretch.into(),
+            )
+        };
+
+        Ok(index)
+    }
+}
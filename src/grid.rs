@@ -12,7 +12,7 @@ impl Ui {
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn create_grid<'ui>(&'ui self) -> Result<&'ui mut Grid, crate::Error> {
+    pub fn create_grid<'ui>(&'ui self) -> Result<&'ui Grid, crate::Error> {
         call_libui_new_fn!(
             ui: self,
             fn: uiNewGrid() -> Grid,
@@ -29,9 +29,98 @@ def_subcontrol!(
         ```
     ",
     ty: Grid,
+    kind: Grid,
     handle: uiGrid,
 );
 
+/// The alignment of a control within its cell in a [`Grid`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Alignment {
+    /// The control fills its cell.
+    #[default]
+    Fill,
+    /// The control is aligned to the leading edge of its cell.
+    Start,
+    /// The control is centered within its cell.
+    Center,
+    /// The control is aligned to the trailing edge of its cell.
+    End,
+}
+
+impl Alignment {
+    fn into_param(self) -> u32 {
+        match self {
+            Self::Fill => 0,
+            Self::Start => 1,
+            Self::Center => 2,
+            Self::End => 3,
+        }
+    }
+}
+
+/// The position and size of a control within a [`Grid`].
+///
+/// A placement spans one cell and fills it without expanding by default.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Placement {
+    /// The column of the leftmost cell.
+    pub left: u16,
+    /// The row of the topmost cell.
+    pub top: u16,
+    /// The number of columns spanned.
+    pub x_span: u16,
+    /// The number of rows spanned.
+    pub y_span: u16,
+    /// Whether or not the control takes up excess horizontal space.
+    pub can_expand_x: bool,
+    /// The horizontal alignment of the control within its cells.
+    pub x_align: Alignment,
+    /// Whether or not the control takes up excess vertical space.
+    pub can_expand_y: bool,
+    /// The vertical alignment of the control within its cells.
+    pub y_align: Alignment,
+}
+
+impl Placement {
+    /// Creates a new [`Placement`] at the given cell.
+    pub fn at(left: u16, top: u16) -> Self {
+        Self {
+            left,
+            top,
+            x_span: 1,
+            y_span: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Spans the given number of columns and rows.
+    pub fn span(self, x_span: u16, y_span: u16) -> Self {
+        Self {
+            x_span,
+            y_span,
+            ..self
+        }
+    }
+
+    /// Takes up excess horizontal and/or vertical space.
+    pub fn expand(self, can_expand_x: bool, can_expand_y: bool) -> Self {
+        Self {
+            can_expand_x,
+            can_expand_y,
+            ..self
+        }
+    }
+
+    /// Aligns the control horizontally and vertically within its cells.
+    pub fn align(self, x_align: Alignment, y_align: Alignment) -> Self {
+        Self {
+            x_align,
+            y_align,
+            ..self
+        }
+    }
+}
+
 impl Grid<'_> {
     bind_bool_fn!(
         docs: "
@@ -62,4 +151,33 @@ impl Grid<'_> {
         self: { fn: set_padded(value: bool) },
         libui: { fn: uiGridSetPadded() },
     );
+
+    /// Appends a new child control at the given placement.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn push_new_child<'c>(
+        &self,
+        child: &impl Deref<Target = Control<'c>>,
+        placement: Placement,
+    ) {
+        child.make_child();
+        unsafe {
+            uiGridAppend(
+                self.as_ptr(),
+                child.as_ptr(),
+                placement.left.into(),
+                placement.top.into(),
+                placement.x_span.into(),
+                placement.y_span.into(),
+                placement.can_expand_x.into(),
+                placement.x_align.into_param(),
+                placement.can_expand_y.into(),
+                placement.y_align.into_param(),
+            )
+        };
+    }
 }
@@ -15,7 +15,7 @@ impl Ui {
     pub fn create_group<'ui>(
         &'ui self,
         title: impl AsRef<str>,
-    ) -> Result<&'ui mut Group, crate::Error> {
+    ) -> Result<&'ui Group, crate::Error> {
         // SAFETY: `uiNewGroup` `strdup`s `title`, so it's OK to drop at the end of scope.
         let title = make_cstring!(title.as_ref());
 
@@ -37,6 +37,7 @@ def_subcontrol!(
         ```
     ",
     ty: Group,
+    kind: Group,
     handle: uiGroup,
 );
 
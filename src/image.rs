@@ -1,64 +1,473 @@
 // SPDX-License-Identifier: MPL-2.0
 
-//! [`Image`].
+//! [`Image`] and the decoding of image files.
+
+use std::{fmt, marker::PhantomData};
 
 use crate::prelude::*;
 
 impl Ui {
-    /// Creates a new [`Image`].
+    /// Creates a new, empty [`Image`] of the given size in points.
+    ///
+    /// Bitmaps must be pushed with [`Image::push_rgba`] before the image may be displayed.
     ///
     /// # Examples
     ///
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn create_image(&self, width: f64, height: f64) -> Result<&mut Image, crate::Error> {
-        call_libui_new_fn!(
-            ui: self,
-            fn: uiNewImage(width, height) -> Image,
-        )
-    }
-}
+    pub fn create_image(&self, width: f64, height: f64) -> Result<Image<'_>, crate::Error> {
+        let ptr = call_fallible_libui_fn!(uiNewImage(width, height))?;
 
-impl Image {
-    pub(crate) fn new(_: &Ui, ptr: *mut uiImage) -> Self {
-        Self { ptr }
+        Ok(Image {
+            ptr,
+            _ui: PhantomData,
+        })
     }
 }
 
-/// An RGBA bitmap.
+/// An image, consisting of one or more RGBA bitmaps of different scales.
+///
+/// An image is scaled to its size in points for display. When multiple bitmaps are pushed, the
+/// operating system picks the bitmap best suited to the scale factor of the display, so a 2x
+/// bitmap should be pushed for high-DPI displays.
+///
+/// Unlike controls, images are owned by the caller, and are freed when dropped.
 ///
 /// # Examples
 ///
 /// ```no_run
 /// // TODO
 /// ```
-pub struct Image {
+pub struct Image<'ui> {
     ptr: *mut uiImage,
+    _ui: PhantomData<&'ui Ui>,
 }
 
-impl Image {
+impl Drop for Image<'_> {
+    fn drop(&mut self) {
+        unsafe { uiFreeImage(self.ptr) };
+    }
+}
+
+impl Image<'_> {
     pub(crate) fn as_ptr(&self) -> *mut uiImage {
         self.ptr
     }
 
+    /// Pushes a bitmap of 8-bit, non-premultiplied RGBA pixels.
+    ///
+    /// `byte_stride` is the distance in bytes between the starts of successive rows, which must be
+    /// at least `4 * width`. `pixels` must hold `height` rows, though the last row needn't be padded
+    /// to the full stride.
+    ///
     /// # Examples
     ///
     /// ```no_run
-    /// // TODO
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use boing::Ui;
+    ///
+    /// let ui: Ui;
+    /// # ui = Ui::new()?;
+    ///
+    /// // A 2x2 checkerboard.
+    /// let black = [0x00, 0x00, 0x00, 0xff];
+    /// let white = [0xff, 0xff, 0xff, 0xff];
+    /// let pixels = [black, white, white, black].concat();
+    ///
+    /// let image = ui.create_image(2.0, 2.0)?;
+    /// image.push_rgba(&pixels, 2, 2, 8)?;
+    /// #
+    /// # Ok(())
+    /// # }
     /// ```
-    pub fn push(&self, pixels: &mut [u8], width: u16, height: u16, byte_stride: u16) {
-        // SAFETY: `pixels` is dropped at the end of scope, but that's OK as *libui-ng* copies it.
-        let pixels = pixels.as_mut_ptr().cast();
+    pub fn push_rgba(
+        &self,
+        pixels: &[u8],
+        width: u16,
+        height: u16,
+        byte_stride: u16,
+    ) -> Result<(), Error> {
+        let row_len = 4 * usize::from(width);
+        if usize::from(byte_stride) < row_len {
+            return Err(Error::StrideTooSmall {
+                byte_stride,
+                min: row_len,
+            });
+        }
+
+        let expected_len = match usize::from(height) {
+            0 => 0,
+            height => usize::from(byte_stride) * (height - 1) + row_len,
+        };
+        if pixels.len() < expected_len {
+            return Err(Error::BufferTooSmall {
+                len: pixels.len(),
+                min: expected_len,
+            });
+        }
 
+        // SAFETY: *libui-ng* only reads from `pixels`, and copies it, so it needn't outlive this
+        // call. We've checked above that every byte it reads is in bounds.
         unsafe {
             uiImageAppend(
                 self.as_ptr(),
-                pixels,
+                pixels.as_ptr() as *mut _,
                 width.into(),
                 height.into(),
                 byte_stride.into(),
             );
         }
+
+        Ok(())
+    }
+
+    /// Pushes a decoded bitmap.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn push_bitmap(&self, bitmap: &Bitmap) -> Result<(), Error> {
+        let (width, height) = bitmap.checked_size()?;
+
+        self.push_rgba(&bitmap.pixels, width, height, 4 * width)
+    }
+}
+
+/// An error that occurred while pushing or decoding a bitmap.
+#[derive(Debug)]
+pub enum Error {
+    /// The byte stride is smaller than a row of pixels.
+    StrideTooSmall {
+        /// The byte stride that was given.
+        byte_stride: u16,
+        /// The length in bytes of a row of pixels.
+        min: usize,
+    },
+    /// The pixel buffer is too small for the given dimensions.
+    BufferTooSmall {
+        /// The length of the buffer that was given.
+        len: usize,
+        /// The minimum length for the given dimensions.
+        min: usize,
+    },
+    /// A bitmap is too large to be pushed to an image.
+    TooLarge {
+        width: u32,
+        height: u32,
+    },
+    /// An image file failed to be decoded.
+    Decode(String),
+    /// An image file failed to be read.
+    Io(std::io::Error),
+    /// A *libui-ng* function failed.
+    Libui(crate::Error),
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StrideTooSmall { byte_stride, min } => {
+                write!(f, "byte stride of {} is less than the row length of {}", byte_stride, min)
+            }
+            Self::BufferTooSmall { len, min } => {
+                write!(f, "pixel buffer of {} bytes is smaller than the required {}", len, min)
+            }
+            Self::TooLarge { width, height } => {
+                write!(f, "bitmap of {}x{} pixels is too large", width, height)
+            }
+            Self::Decode(e) => write!(f, "failed to decode image: {}", e),
+            Self::Io(e) => write!(f, "failed to read image: {}", e),
+            Self::Libui(e) => e.fmt(f),
+        }
+    }
+}
+
+impl From<crate::Error> for Error {
+    fn from(e: crate::Error) -> Self {
+        Self::Libui(e)
+    }
+}
+
+/// A decoded bitmap of 8-bit, non-premultiplied RGBA pixels without row padding.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bitmap {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Bitmap {
+    /// The dimensions of this bitmap as accepted by [`Image::push_rgba`].
+    fn checked_size(&self) -> Result<(u16, u16), Error> {
+        let too_large = || Error::TooLarge {
+            width: self.width,
+            height: self.height,
+        };
+
+        // The stride of `4 * width` must fit in a `u16` too.
+        let width = u16::try_from(self.width)
+            .ok()
+            .filter(|width| width.checked_mul(4).is_some())
+            .ok_or_else(too_large)?;
+        let height = u16::try_from(self.height).map_err(|_| too_large())?;
+
+        Ok((width, height))
+    }
+}
+
+#[cfg(feature = "image-decoders")]
+pub use decoders::{decode_png, decode_ppm, Format};
+
+#[cfg(feature = "image-decoders")]
+mod decoders {
+    use std::path::Path;
+
+    use super::{Bitmap, Error, Image};
+    use crate::Ui;
+
+    /// A file format that *boing* can decode.
+    #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+    pub enum Format {
+        /// Portable Network Graphics.
+        Png,
+        /// Portable Pixmap, in either the binary (`P6`) or plain (`P3`) flavor.
+        Ppm,
+    }
+
+    impl Format {
+        /// Guesses the format of a file from its extension.
+        pub fn from_path(path: &Path) -> Option<Self> {
+            let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+            match extension.as_str() {
+                "png" => Some(Self::Png),
+                "ppm" => Some(Self::Ppm),
+                _ => None,
+            }
+        }
+
+        /// Decodes the contents of a file of this format.
+        pub fn decode(self, data: &[u8]) -> Result<Bitmap, Error> {
+            match self {
+                Self::Png => decode_png(data),
+                Self::Ppm => decode_ppm(data),
+            }
+        }
+    }
+
+    impl Ui {
+        /// Creates a new [`Image`] from an encoded 1x bitmap and an optional 2x bitmap.
+        ///
+        /// The size of the image in points is that of the 1x bitmap in pixels.
+        ///
+        /// # Examples
+        ///
+        /// ```no_run
+        /// // TODO
+        /// ```
+        pub fn create_image_from_encoded(
+            &self,
+            format: Format,
+            one_x: &[u8],
+            two_x: Option<&[u8]>,
+        ) -> Result<Image<'_>, Error> {
+            let one_x = format.decode(one_x)?;
+            let two_x = two_x.map(|data| format.decode(data)).transpose()?;
+
+            self.create_image_from_bitmaps(&one_x, two_x.as_ref())
+        }
+
+        /// Loads a new [`Image`] from a PNG or PPM file.
+        ///
+        /// If a file of the same name suffixed with `@2x` exists alongside it, e.g., `icon@2x.png`
+        /// for `icon.png`, it is loaded as the 2x bitmap.
+        ///
+        /// # Examples
+        ///
+        /// ```no_run
+        /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+        /// use boing::Ui;
+        ///
+        /// let ui: Ui;
+        /// # ui = Ui::new()?;
+        ///
+        /// // Loads both `boing.png` and `boing@2x.png`.
+        /// let logo = ui.load_image("boing.png")?;
+        /// #
+        /// # Ok(())
+        /// # }
+        /// ```
+        pub fn load_image(&self, path: impl AsRef<Path>) -> Result<Image<'_>, Error> {
+            let path = path.as_ref();
+            let format = Format::from_path(path).ok_or_else(|| {
+                Error::Decode(format!("unknown image format of {}", path.display()))
+            })?;
+
+            let one_x = std::fs::read(path).map_err(Error::Io)?;
+            let two_x = two_x_path(path).and_then(|path| std::fs::read(path).ok());
+
+            self.create_image_from_encoded(format, &one_x, two_x.as_deref())
+        }
+
+        fn create_image_from_bitmaps(
+            &self,
+            one_x: &Bitmap,
+            two_x: Option<&Bitmap>,
+        ) -> Result<Image<'_>, Error> {
+            let image = self.create_image(one_x.width.into(), one_x.height.into())?;
+            image.push_bitmap(one_x)?;
+            if let Some(two_x) = two_x {
+                image.push_bitmap(two_x)?;
+            }
+
+            Ok(image)
+        }
+    }
+
+    /// The path of the 2x counterpart to the file at `path`, e.g., `icon@2x.png` for `icon.png`.
+    fn two_x_path(path: &Path) -> Option<std::path::PathBuf> {
+        let stem = path.file_stem()?.to_str()?;
+        let extension = path.extension()?.to_str()?;
+
+        Some(path.with_file_name(format!("{}@2x.{}", stem, extension)))
+    }
+
+    /// Decodes a PNG file.
+    ///
+    /// All color types and bit depths are converted to 8-bit RGBA.
+    pub fn decode_png(data: &[u8]) -> Result<Bitmap, Error> {
+        let decode_err = |e: png::DecodingError| Error::Decode(e.to_string());
+
+        let mut decoder = png::Decoder::new(data);
+        // Expand palettes and low bit depths, and strip 16-bit channels down to 8 bits.
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().map_err(decode_err)?;
+
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).map_err(decode_err)?;
+        buf.truncate(info.buffer_size());
+
+        let pixels = match info.color_type {
+            png::ColorType::Rgba => buf,
+            png::ColorType::Rgb => buf
+                .chunks_exact(3)
+                .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 0xff])
+                .collect(),
+            png::ColorType::GrayscaleAlpha => buf
+                .chunks_exact(2)
+                .flat_map(|ga| [ga[0], ga[0], ga[0], ga[1]])
+                .collect(),
+            png::ColorType::Grayscale => buf.iter().flat_map(|&g| [g, g, g, 0xff]).collect(),
+            png::ColorType::Indexed => {
+                // `normalize_to_color8` expands palettes, so this is unreachable in practice.
+                return Err(Error::Decode("unexpected indexed color".into()));
+            }
+        };
+
+        Ok(Bitmap {
+            width: info.width,
+            height: info.height,
+            pixels,
+        })
+    }
+
+    /// Decodes a PPM file.
+    ///
+    /// Both binary (`P6`) and plain (`P3`) files are supported, with any maximum value up to
+    /// 65535. Samples are scaled to 8 bits, and all pixels are opaque.
+    pub fn decode_ppm(data: &[u8]) -> Result<Bitmap, Error> {
+        let decode_err = |message: &str| Error::Decode(format!("invalid PPM: {}", message));
+
+        let mut header = Header { data, pos: 0 };
+        let magic = header.token().ok_or_else(|| decode_err("missing magic number"))?;
+        let is_binary = match magic {
+            b"P6" => true,
+            b"P3" => false,
+            _ => return Err(decode_err("unsupported magic number")),
+        };
+        let width = header.number().ok_or_else(|| decode_err("missing width"))?;
+        let height = header.number().ok_or_else(|| decode_err("missing height"))?;
+        let max = header.number().ok_or_else(|| decode_err("missing maximum value"))?;
+        if max == 0 || max > 0xffff {
+            return Err(decode_err("maximum value out of range"));
+        }
+
+        let sample_count = u64::from(width)
+            .checked_mul(u64::from(height))
+            .and_then(|pixels| pixels.checked_mul(3))
+            .and_then(|samples| usize::try_from(samples).ok())
+            .ok_or_else(|| decode_err("dimensions too large"))?;
+        let scale = |sample: u32| ((sample.min(max) * 255 + max / 2) / max) as u8;
+
+        let samples: Vec<u8> = if is_binary {
+            // Exactly one whitespace byte separates the header from the raster.
+            let raster = data.get((header.pos + 1)..).unwrap_or_default();
+            if max < 256 {
+                raster
+                    .get(..sample_count)
+                    .ok_or_else(|| decode_err("truncated raster"))?
+                    .iter()
+                    .map(|&sample| scale(sample.into()))
+                    .collect()
+            } else {
+                raster
+                    .get(..sample_count.saturating_mul(2))
+                    .ok_or_else(|| decode_err("truncated raster"))?
+                    .chunks_exact(2)
+                    .map(|be| scale(u16::from_be_bytes([be[0], be[1]]).into()))
+                    .collect()
+            }
+        } else {
+            (0..sample_count)
+                .map(|_| header.number().map(scale))
+                .collect::<Option<_>>()
+                .ok_or_else(|| decode_err("truncated raster"))?
+        };
+
+        Ok(Bitmap {
+            width,
+            height,
+            pixels: samples
+                .chunks_exact(3)
+                .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 0xff])
+                .collect(),
+        })
+    }
+
+    /// A reader of whitespace-separated tokens, with `#` comments, from a PPM header.
+    struct Header<'a> {
+        data: &'a [u8],
+        pos: usize,
+    }
+
+    impl<'a> Header<'a> {
+        fn token(&mut self) -> Option<&'a [u8]> {
+            loop {
+                match self.data.get(self.pos)? {
+                    b'#' => {
+                        while !matches!(self.data.get(self.pos), None | Some(b'\n' | b'\r')) {
+                            self.pos += 1;
+                        }
+                    }
+                    byte if byte.is_ascii_whitespace() => self.pos += 1,
+                    _ => break,
+                }
+            }
+
+            let start = self.pos;
+            while matches!(self.data.get(self.pos), Some(byte) if !byte.is_ascii_whitespace()) {
+                self.pos += 1;
+            }
+
+            Some(&self.data[start..self.pos])
+        }
+
+        fn number(&mut self) -> Option<u32> {
+            std::str::from_utf8(self.token()?).ok()?.parse().ok()
+        }
     }
 }
@@ -12,7 +12,7 @@ impl Ui {
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn create_label<'ui>(&'ui self, text: impl AsRef<str>) -> Result<&'ui mut Label, crate::Error> {
+    pub fn create_label<'ui>(&'ui self, text: impl AsRef<str>) -> Result<&'ui Label, crate::Error> {
         let text = make_cstring!(text.as_ref());
 
         call_libui_new_fn!(
@@ -41,6 +41,7 @@ def_subcontrol!(
         ## Linux
     ",
     ty: Label,
+    kind: Label,
     handle: uiLabel,
 );
 
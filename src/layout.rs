@@ -0,0 +1,375 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Declarative construction of control trees.
+//!
+//! See [`layout!`](crate::layout!) for more information.
+
+use crate::{grid::Placement, prelude::*, Axis, Form, Grid, Group, Tab, Window};
+
+/// A control that contains child controls.
+///
+/// This trait unifies the various `push_new_child`, `push_new_page`, and `set_child` methods of
+/// containers so that [`layout!`](crate::layout!) may treat them uniformly.
+pub trait Container {
+    /// Describes where and how a child is placed within this container.
+    type Slot;
+
+    /// Appends a new child control at the given slot.
+    fn push_slotted<'c>(
+        &self,
+        slot: Self::Slot,
+        child: &impl Deref<Target = Control<'c>>,
+    ) -> Result<(), crate::Error>;
+}
+
+/// The slot of a child within an [`Axis`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AxisSlot {
+    can_stretch: bool,
+}
+
+/// The slot of a page within a [`Tab`].
+#[derive(Clone, Debug)]
+pub struct PageSlot {
+    name: String,
+    is_margined: bool,
+}
+
+impl PageSlot {
+    /// Gives the page margins.
+    pub fn margined(self) -> Self {
+        Self {
+            is_margined: true,
+            ..self
+        }
+    }
+}
+
+/// The slot of a labeled child within a [`Form`].
+#[derive(Clone, Debug)]
+pub struct FieldSlot {
+    label: String,
+    can_stretch: bool,
+}
+
+impl FieldSlot {
+    /// Lets the child fill the remaining height of the form.
+    pub fn stretchy(self) -> Self {
+        Self {
+            can_stretch: true,
+            ..self
+        }
+    }
+}
+
+/// Lets a child fill its [`Axis`].
+pub fn stretchy() -> AxisSlot {
+    AxisSlot { can_stretch: true }
+}
+
+/// Places a child in a new [`Tab`] page with the given name.
+pub fn page(name: impl Into<String>) -> PageSlot {
+    PageSlot {
+        name: name.into(),
+        is_margined: false,
+    }
+}
+
+/// Places a child in a [`Form`] with the given label.
+pub fn field(label: impl Into<String>) -> FieldSlot {
+    FieldSlot {
+        label: label.into(),
+        can_stretch: false,
+    }
+}
+
+/// Places a child in a [`Grid`] at the given cell.
+///
+/// See [`Placement`] for further configuration.
+pub fn cell(left: u16, top: u16) -> Placement {
+    Placement::at(left, top)
+}
+
+impl Container for Axis<'_> {
+    type Slot = AxisSlot;
+
+    fn push_slotted<'c>(
+        &self,
+        slot: Self::Slot,
+        child: &impl Deref<Target = Control<'c>>,
+    ) -> Result<(), crate::Error> {
+        self.push_new_child(child, slot.can_stretch);
+
+        Ok(())
+    }
+}
+
+impl Container for Form<'_> {
+    type Slot = FieldSlot;
+
+    fn push_slotted<'c>(
+        &self,
+        slot: Self::Slot,
+        child: &impl Deref<Target = Control<'c>>,
+    ) -> Result<(), crate::Error> {
+        self.push_new_child(slot.label, child, slot.can_stretch)
+            .map(drop)
+    }
+}
+
+impl Container for Grid<'_> {
+    type Slot = Placement;
+
+    fn push_slotted<'c>(
+        &self,
+        slot: Self::Slot,
+        child: &impl Deref<Target = Control<'c>>,
+    ) -> Result<(), crate::Error> {
+        self.push_new_child(child, slot);
+
+        Ok(())
+    }
+}
+
+impl Container for Group<'_> {
+    type Slot = ();
+
+    fn push_slotted<'c>(
+        &self,
+        _: Self::Slot,
+        child: &impl Deref<Target = Control<'c>>,
+    ) -> Result<(), crate::Error> {
+        self.set_child(child);
+
+        Ok(())
+    }
+}
+
+impl Container for Tab<'_> {
+    type Slot = PageSlot;
+
+    fn push_slotted<'c>(
+        &self,
+        slot: Self::Slot,
+        child: &impl Deref<Target = Control<'c>>,
+    ) -> Result<(), crate::Error> {
+        let index = self.push_new_page(slot.name, child)?;
+        self.set_page_margined(index, slot.is_margined);
+
+        Ok(())
+    }
+}
+
+impl Container for Window<'_> {
+    type Slot = ();
+
+    fn push_slotted<'c>(
+        &self,
+        _: Self::Slot,
+        child: &impl Deref<Target = Control<'c>>,
+    ) -> Result<(), crate::Error> {
+        self.set_child(child);
+
+        Ok(())
+    }
+}
+
+/// The return value of a method called from within [`layout!`](crate::layout!).
+///
+/// This allows fallible and infallible methods alike to be called inline, with any error
+/// propagated out of the whole layout.
+#[doc(hidden)]
+pub trait IntoResult {
+    type Output;
+
+    fn into_result(self) -> Result<Self::Output, crate::Error>;
+}
+
+impl IntoResult for () {
+    type Output = ();
+
+    fn into_result(self) -> Result<Self::Output, crate::Error> {
+        Ok(())
+    }
+}
+
+impl IntoResult for u16 {
+    type Output = u16;
+
+    fn into_result(self) -> Result<Self::Output, crate::Error> {
+        Ok(self)
+    }
+}
+
+impl<T> IntoResult for Result<T, crate::Error> {
+    type Output = T;
+
+    fn into_result(self) -> Result<Self::Output, crate::Error> {
+        self
+    }
+}
+
+/// Declares a tree of controls.
+///
+/// The first argument is a [`Ui`](crate::Ui). It is followed by `=>` and a parenthesized list of
+/// the names to return, and then by the root control. This macro evaluates to a
+/// `Result<_, boing::Error>` of the named controls; should any control fail to be created, the
+/// first error is returned and the rest of the tree is not built.
+///
+/// # Syntax
+///
+/// Each control is written as a call to one of the `Ui::create_*` methods without the receiver,
+/// optionally followed by a `{ ... }` block of methods to call on it and then a `[ ... ]` list of
+/// children. A control may be named by prefixing it with `name =`, after which it is available by
+/// that name to later method arguments and to the returned list.
+///
+/// Children of containers may be prefixed with a slot and `=>` describing where they are placed:
+///
+/// | Container  | Slot                                      | Default          |
+/// |------------|-------------------------------------------|------------------|
+/// | [`Axis`]   | `stretchy()`                              | Not stretchy.    |
+/// | [`Form`]   | `field("Label")`, `.stretchy()`           | None; required.  |
+/// | [`Grid`]   | `cell(left, top)`, `.span(..)`, ...       | None; required.  |
+/// | [`Group`]  | None.                                     |                  |
+/// | [`Tab`]    | `page("Name")`, `.margined()`             | None; required.  |
+/// | [`Window`] | None.                                     |                  |
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), boing::Error> {
+/// use boing::{Progress, Ui};
+///
+/// let ui: Ui;
+/// # ui = Ui::new()?;
+///
+/// let (window, slider, progress_bar) = boing::layout!(&ui => (window, slider, progress_bar)
+///     window = create_window("Numbers", 240, 240, false, true) { set_margined(true) } [
+///         create_group("Numbers") { set_margined(true) } [
+///             create_vertical_axis() { set_padded(true) } [
+///                 create_spinbox(0, 100),
+///                 slider = create_slider(0, 100),
+///                 progress_bar = create_progress_bar(),
+///                 stretchy() => create_label("Drag the slider!"),
+///             ],
+///         ],
+///     ]
+/// )?;
+///
+/// slider.on_changed(move |slider| {
+///     let percent = slider.value().clamp(0, 100) as u8;
+///     progress_bar.set_value(Progress::Determinate(percent));
+/// });
+/// window.show();
+/// ui.run();
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! layout {
+    ($ui:expr => ( $($output:ident),* $(,)? ) $($root:tt)+) => {{
+        let __ui: &$crate::Ui = $ui;
+
+        '__layout: {
+            $crate::__layout!(@root '__layout, __ui; $($root)+);
+
+            ::core::result::Result::Ok::<_, $crate::Error>(( $($output),* ))
+        }
+    }};
+}
+
+/// The implementation of [`layout!`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __layout {
+    // Evaluates a method call, breaking out of the layout with the error if it fails.
+    (@try $label:lifetime; $call:expr) => {
+        match $crate::layout::IntoResult::into_result($call) {
+            ::core::result::Result::Ok(value) => value,
+            ::core::result::Result::Err(e) => break $label ::core::result::Result::Err(e),
+        }
+    };
+
+    // The root control.
+    (@root $label:lifetime, $ui:ident; $name:ident = $($node:tt)+) => {
+        $crate::__layout!(@node $label, $ui; $name = $($node)+);
+    };
+    (@root $label:lifetime, $ui:ident; $($node:tt)+) => {
+        $crate::__layout!(@node $label, $ui; _root = $($node)+);
+    };
+
+    // A single control and its children.
+    (
+        @node $label:lifetime, $ui:ident;
+        $name:ident = $ctor:ident ( $($arg:expr),* $(,)? )
+        $({ $( $method:ident ( $($method_arg:expr),* $(,)? ) ),* $(,)? })?
+        $([ $($children:tt)* ])?
+    ) => {
+        let $name = $crate::__layout!(@try $label; $ui.$ctor($($arg),*));
+        $($(
+            $crate::__layout!(@try $label; $name.$method($($method_arg),*));
+        )*)?
+        $(
+            $crate::__layout!(@children $label, $ui, $name; $($children)*);
+        )?
+    };
+
+    // A list of children, separated by commas.
+    (@children $label:lifetime, $ui:ident, $parent:ident; ) => {};
+    (
+        @children $label:lifetime, $ui:ident, $parent:ident;
+        $slot:ident $(( $($slot_arg:expr),* $(,)? ))?
+        $(. $slot_method:ident ( $($slot_method_arg:expr),* $(,)? ))*
+        => $($rest:tt)+
+    ) => {
+        $crate::__layout!(
+            @entry $label, $ui, $parent,
+            (
+                $crate::layout::$slot($($($slot_arg),*)?)
+                $(.$slot_method($($slot_method_arg),*))*
+            );
+            [] $($rest)+
+        );
+    };
+    (@children $label:lifetime, $ui:ident, $parent:ident; $($rest:tt)+) => {
+        $crate::__layout!(
+            @entry $label, $ui, $parent, (::core::default::Default::default());
+            [] $($rest)+
+        );
+    };
+
+    // Accumulates the tokens of a child up to the next comma.
+    (
+        @entry $label:lifetime, $ui:ident, $parent:ident, $slot:tt;
+        [ $($child:tt)+ ] , $($rest:tt)*
+    ) => {
+        $crate::__layout!(@child $label, $ui, $parent, $slot; $($child)+);
+        $crate::__layout!(@children $label, $ui, $parent; $($rest)*);
+    };
+    (
+        @entry $label:lifetime, $ui:ident, $parent:ident, $slot:tt;
+        [ $($child:tt)* ] $next:tt $($rest:tt)*
+    ) => {
+        $crate::__layout!(@entry $label, $ui, $parent, $slot; [ $($child)* $next ] $($rest)*);
+    };
+    (@entry $label:lifetime, $ui:ident, $parent:ident, $slot:tt; [ $($child:tt)+ ]) => {
+        $crate::__layout!(@child $label, $ui, $parent, $slot; $($child)+);
+    };
+
+    // A child, which is created and then pushed into its parent.
+    (@child $label:lifetime, $ui:ident, $parent:ident, $slot:tt; $name:ident = $($node:tt)+) => {
+        $crate::__layout!(@node $label, $ui; $name = $($node)+);
+        $crate::__layout!(
+            @try $label;
+            $crate::layout::Container::push_slotted(&*$parent, $slot, &*$name)
+        );
+    };
+    (@child $label:lifetime, $ui:ident, $parent:ident, $slot:tt; $($node:tt)+) => {
+        $crate::__layout!(@node $label, $ui; child = $($node)+);
+        $crate::__layout!(
+            @try $label;
+            $crate::layout::Container::push_slotted(&*$parent, $slot, &*child)
+        );
+    };
+}
@@ -13,7 +13,7 @@
 //!
 //! In the context that *boing* uses them, the terms "widget" and "control" are *not*
 //! interchangeable. A **widget** is an interactive visual element, while **controls** are a
-//! specific subset of widgets that implement `DerefMut<Target = boing::Control>`. In particular,
+//! specific subset of widgets that implement `Deref<Target = boing::Control>`. In particular,
 //! all widgets are controls except for [`Image`], [`Menu`], and [`MenuItem`].
 //!
 //! ## Usage
@@ -106,45 +106,70 @@
 // All *libui-ng-sys* exports violate Rust's naming convention.
 #![allow(non_upper_case_globals)]
 
+#[cfg(not(any(feature = "libui", feature = "mock")))]
+compile_error!("*boing* requires a backend; please enable either feature `libui` or `mock`");
+
 #[macro_use]
 mod macros;
 
 pub mod area;
+mod automation;
 mod axis;
+mod callback;
+pub mod chart;
 mod checkbox;
 pub mod color;
 mod combobox;
 mod control;
+mod custom;
+pub mod draw;
+pub mod date_time;
+pub mod dialog;
+mod editable_combobox;
 pub mod font;
 pub mod form;
-mod grid;
+pub mod grid;
 mod group;
-mod image;
+pub mod image;
 mod label;
+pub mod layout;
+pub mod locale;
 pub mod menu;
+#[cfg(feature = "mock")]
+pub mod mock;
 mod multiline_text_entry;
+pub mod parsed_entry;
 mod path;
 mod prelude;
 mod progress_bar;
 mod pushbutton;
 mod radio_buttons;
+mod registry;
 mod separator;
+pub mod session;
+pub mod settings;
 mod slider;
 mod spinbox;
+pub mod state;
 mod tab;
-mod table;
+pub mod table;
 mod text_entry;
 mod ui;
+pub mod undo;
 mod window;
 
 use std::fmt;
 
-pub use area::Area;
+pub use area::{Area, Handler as AreaHandler};
 pub use axis::Axis;
+pub use chart::Chart;
 pub use checkbox::Checkbox;
 pub use color::Color;
-pub use combobox::Combobox;
-pub use control::Control;
+pub use combobox::{Combobox, TypedCombobox};
+pub use control::{Control, Kind as ControlKind, Subcontrol};
+pub use custom::{Custom, CustomControl};
+pub use date_time::{DateTime, Picker as DateTimePicker};
+pub use editable_combobox::EditableCombobox;
 pub use font::{Font, Picker as FontPicker};
 pub use form::Form;
 pub use grid::Grid;
@@ -153,13 +178,18 @@ pub use image::Image;
 pub use label::Label;
 pub use menu::{Item as MenuItem, Menu};
 pub use multiline_text_entry::MultilineTextEntry;
+pub use parsed_entry::ParsedEntry;
 pub use path::Path;
-pub use progress_bar::ProgressBar;
+pub use progress_bar::{Progress, ProgressBar};
 pub use pushbutton::Pushbutton;
-pub use radio_buttons::RadioButtons;
+pub use radio_buttons::{RadioButtons, TypedRadioButtons};
 pub use separator::Separator;
-pub use slider::Slider;
+pub use settings::Settings;
+#[cfg(feature = "derive")]
+pub use boing_derive::Settings;
+pub use slider::{Scale, ScaledSlider, Slider};
 pub use spinbox::Spinbox;
+pub use state::State;
 pub use tab::Tab;
 pub use table::Table;
 pub use text_entry::TextEntry;
@@ -178,6 +208,10 @@ pub enum Error {
     ConvertCString(std::str::Utf8Error),
     /// A Rust string failed to be converted to a C string.
     ConvertRustString(std::ffi::NulError),
+    /// A control wasn't created by the [`Ui`] it was given to, or has been destroyed.
+    ///
+    /// Only controls returned by the `Ui::create_*` methods may be given IDs with [`Ui::set_id`].
+    ForeignControl,
     /// A *libui-ng* function failed.
     LibuiFn {
         /// The name of the function that failed.
@@ -201,6 +235,9 @@ impl fmt::Display for Error {
             Self::ConvertRustString(e) => {
                 write!(f, "failed to convert Rust string to C string: {}", e)
             }
+            Self::ForeignControl => {
+                write!(f, "control wasn't created by this `Ui` or has been destroyed")
+            }
             Self::LibuiFn { name, cause } => {
                 write!(f, "*libui-ng* function `{}` failed", name)?;
                 if let Some(cause) = cause {
@@ -0,0 +1,743 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Translating the text of widgets, and switching languages while the UI is running.
+//!
+//! Translations are kept in [`Catalog`]s, one per locale, which map message keys to text. Catalogs
+//! may be written in a subset of the [Fluent] syntax or as gettext `.po` files. Widgets created
+//! with the `Ui::create_localized_*` methods, or registered later with [`Ui::localize`], remember
+//! the [`Message`] they display, and [`Ui::set_locale`] translates them all again in place.
+//!
+//! A message is looked up in the catalog of the current locale, then in those of its parent
+//! locales (e.g., `fr` for `fr-CA`), and then in the catalog of the fallback locale. A message that
+//! is found nowhere is displayed as its key.
+//!
+//! # Limitations
+//!
+//! The text of menus and menu items cannot be changed after they are created, so they are
+//! translated only once, into the locale that is current when they are created. Menus must be
+//! created before any window, so the locale should be chosen before then.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use boing::{
+//!     locale::{Catalog, Message},
+//!     Ui,
+//! };
+//!
+//! let ui = Ui::new()?;
+//! ui.add_catalog("en", "greeting = Hello, { $name }!\nswitch = Auf Deutsch".parse::<Catalog>()?);
+//! ui.add_catalog("de", "greeting = Hallo, { $name }!\nswitch = In English".parse::<Catalog>()?);
+//! ui.set_fallback_locale("en");
+//! ui.set_locale("en");
+//!
+//! let window = ui.create_window("", 320, 240, false, true)?;
+//! let label = ui.create_localized_label(Message::new("greeting").arg("name", "Ferris"))?;
+//! let button = ui.create_localized_pushbutton("switch")?;
+//! button.on_clicked(|_| {
+//!     let locale = if ui.locale().as_deref() == Some("de") { "en" } else { "de" };
+//!     ui.set_locale(locale);
+//! });
+//!
+//! let axis = ui.create_vertical_axis()?;
+//! axis.push_new_child(label, false);
+//! axis.push_new_child(button, false);
+//! window.set_child(axis);
+//! window.show();
+//! ui.run();
+//! #
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Fluent]: https://projectfluent.org/fluent/guide/
+
+use std::{
+    collections::HashMap,
+    fmt,
+    fs,
+    io,
+    path::Path,
+    str::FromStr,
+};
+
+use crate::{
+    prelude::*,
+    Checkbox,
+    Group,
+    Label,
+    Menu,
+    Pushbutton,
+    Subcontrol,
+    Window,
+};
+
+impl Ui {
+    /// Adds the translations of a locale, merging them into those already added for it.
+    ///
+    /// Localized widgets are translated again, as the new translations may apply to them.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn add_catalog(&self, locale: impl Into<String>, catalog: Catalog) {
+        self.localizer
+            .borrow_mut()
+            .catalogs
+            .entry(locale.into())
+            .or_default()
+            .messages
+            .extend(catalog.messages);
+        self.relocalize();
+    }
+
+    /// Loads a catalog from a file with [`Catalog::load`] and adds it with [`Ui::add_catalog`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn load_catalog(
+        &self,
+        locale: impl Into<String>,
+        path: impl AsRef<Path>,
+    ) -> Result<(), LoadCatalogError> {
+        self.add_catalog(locale, Catalog::load(path)?);
+
+        Ok(())
+    }
+
+    /// The current locale, or `None` if none has been set.
+    pub fn locale(&self) -> Option<String> {
+        self.localizer.borrow().locale.clone()
+    }
+
+    /// Switches to the given locale, translating all localized widgets into it.
+    ///
+    /// The locale need not have a catalog; messages are looked up as described in the
+    /// [module documentation](crate::locale).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn set_locale(&self, locale: impl Into<String>) {
+        self.localizer.borrow_mut().locale = Some(locale.into());
+        self.relocalize();
+    }
+
+    /// Sets the locale whose catalog is consulted for messages missing from the current locale.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn set_fallback_locale(&self, locale: impl Into<String>) {
+        self.localizer.borrow_mut().fallback = Some(locale.into());
+        self.relocalize();
+    }
+
+    /// Translates a message into the current locale.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use boing::{locale::Message, Ui};
+    ///
+    /// let ui = Ui::new()?;
+    /// ui.add_catalog("en", "unread = You have { $count } unread messages.".parse()?);
+    /// ui.set_locale("en");
+    ///
+    /// let text = ui.tr(Message::new("unread").arg("count", 3));
+    /// assert_eq!(text, "You have 3 unread messages.");
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn tr(&self, message: impl Into<Message>) -> String {
+        self.localizer.borrow().translate(&message.into())
+    }
+
+    /// Displays a message in a widget, translating it again whenever the locale changes.
+    ///
+    /// This replaces any message previously displayed through this method, and is overridden in
+    /// turn by the text given to the widget directly, e.g., with [`Label::set_text`], until the
+    /// locale next changes. Use [`Ui::unlocalize`] to stop translating the widget.
+    ///
+    /// Any handle to the widget may be given, and the widget stops being translated once it is
+    /// destroyed.
+    ///
+    /// # Errors
+    ///
+    /// As with [`Ui::set_id`], only widgets created by the `Ui::create_*` methods of this [`Ui`]
+    /// may be localized. Other widgets cause [`crate::Error::ForeignControl`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn localize<'ui, T: Localizable<'ui>>(
+        &'ui self,
+        control: &T,
+        message: impl Into<Message>,
+    ) -> Result<(), crate::Error> {
+        let message = message.into();
+        let text = self.tr(message.clone());
+        self.registry
+            .with_entry(control.as_ptr(), |entry| entry.message = Some(message))
+            .ok_or(crate::Error::ForeignControl)?;
+
+        control.set_translation(&text)
+    }
+
+    /// Stops translating a widget registered with [`Ui::localize`], returning whether or not it
+    /// was registered.
+    ///
+    /// The widget keeps the text it currently displays.
+    pub fn unlocalize(&self, control: &Control) -> bool {
+        self.registry
+            .with_entry(control.as_ptr(), |entry| entry.message.take().is_some())
+            .unwrap_or(false)
+    }
+
+    /// Creates a new [`Label`] that displays the given message.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_localized_label<'ui>(
+        &'ui self,
+        message: impl Into<Message>,
+    ) -> Result<&'ui Label<'ui>, crate::Error> {
+        let label = self.create_label("")?;
+        self.localize(label, message)?;
+
+        Ok(label)
+    }
+
+    /// Creates a new [`Pushbutton`] that displays the given message.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_localized_pushbutton<'ui>(
+        &'ui self,
+        message: impl Into<Message>,
+    ) -> Result<&'ui Pushbutton<'ui>, crate::Error> {
+        let button = self.create_pushbutton("")?;
+        self.localize(button, message)?;
+
+        Ok(button)
+    }
+
+    /// Creates a new [`Checkbox`] that displays the given message.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_localized_checkbox<'ui>(
+        &'ui self,
+        message: impl Into<Message>,
+    ) -> Result<&'ui Checkbox<'ui>, crate::Error> {
+        let checkbox = self.create_checkbox("")?;
+        self.localize(checkbox, message)?;
+
+        Ok(checkbox)
+    }
+
+    /// Creates a new [`Group`] titled with the given message.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_localized_group<'ui>(
+        &'ui self,
+        message: impl Into<Message>,
+    ) -> Result<&'ui Group<'ui>, crate::Error> {
+        let group = self.create_group("")?;
+        self.localize(group, message)?;
+
+        Ok(group)
+    }
+
+    /// Creates a new [`Window`] titled with the given message.
+    ///
+    /// The other arguments are as for [`Ui::create_window`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_localized_window<'ui>(
+        &'ui self,
+        message: impl Into<Message>,
+        width: u16,
+        height: u16,
+        has_menubar: bool,
+        should_quit_on_close: bool,
+    ) -> Result<&'ui Window<'ui>, crate::Error> {
+        let message = message.into();
+        let window = self.create_window(
+            self.tr(message.clone()),
+            width,
+            height,
+            has_menubar,
+            should_quit_on_close,
+        )?;
+        self.localize(window, message)?;
+
+        Ok(window)
+    }
+
+    /// Creates a new [`Menu`] named with the given message, translated into the current locale.
+    ///
+    /// Unlike other localized widgets, the menu is not translated again when the locale changes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_localized_menu<'ui>(
+        &'ui self,
+        message: impl Into<Message>,
+    ) -> Result<&'ui Menu<'ui>, crate::Error> {
+        self.create_menu(self.tr(message))
+    }
+
+    /// Translates all localized widgets into the current locale.
+    fn relocalize(&self) {
+        // The texts are gathered first so that neither the localizer nor the registry is borrowed
+        // while widgets are modified.
+        let updates: Vec<_> = {
+            let localizer = self.localizer.borrow();
+            self.registry
+                .messages()
+                .into_iter()
+                .map(|(control, message)| (control, localizer.translate(&message)))
+                .collect()
+        };
+
+        // A control may be destroyed while others are translated, so each is looked up again.
+        for (control, text) in updates {
+            let Some(control) = self.handle_of(control) else {
+                continue;
+            };
+            if let Err(e) = apply(control, &text) {
+                tracing::warn!("Failed to translate control @ {:#?}: {}", control.as_ptr(), e);
+            }
+        }
+    }
+}
+
+/// A control whose text may be translated with [`Ui::localize`].
+///
+/// This trait is implemented by all controls with a single text or title, and there should be no
+/// need to implement it yourself.
+pub trait Localizable<'ui>: Subcontrol<'ui> {
+    /// Displays translated text.
+    #[doc(hidden)]
+    fn set_translation(&self, text: &str) -> Result<(), crate::Error>;
+}
+
+macro_rules! impl_localizable {
+    ($($ty:ident => $fn:ident),* $(,)?) => {
+        $(
+            impl<'ui> Localizable<'ui> for $ty<'ui> {
+                fn set_translation(&self, text: &str) -> Result<(), crate::Error> {
+                    self.$fn(text)
+                }
+            }
+        )*
+
+        /// Displays translated text in a control of any [`Localizable`] type.
+        fn apply(control: &Control, text: &str) -> Result<(), crate::Error> {
+            $(
+                if let Some(control) = control.downcast_ref::<$ty>() {
+                    return control.set_translation(text);
+                }
+            )*
+
+            unreachable!("localized control changed type")
+        }
+    };
+}
+
+impl_localizable!(
+    Checkbox => set_text,
+    Group => set_title,
+    Label => set_text,
+    Pushbutton => set_text,
+    Window => set_title,
+);
+
+/// The translations of every locale.
+///
+/// The message displayed by each localized widget is kept in the entry of the widget in the
+/// registry of the [`Ui`], so that it is forgotten along with the widget.
+#[derive(Default)]
+pub(crate) struct Localizer {
+    catalogs: HashMap<String, Catalog>,
+    locale: Option<String>,
+    fallback: Option<String>,
+}
+
+impl Localizer {
+    fn translate(&self, message: &Message) -> String {
+        let pattern = self
+            .locale
+            .iter()
+            .flat_map(|locale| parent_locales(locale))
+            .chain(self.fallback.as_deref())
+            .filter_map(|locale| self.catalogs.get(locale)?.get(&message.key))
+            .next()
+            .unwrap_or(&message.key);
+
+        format_pattern(pattern, &message.args)
+    }
+}
+
+/// The given locale followed by its parents, from most to least specific, e.g., `zh-Hant-TW`,
+/// `zh-Hant`, and `zh`.
+fn parent_locales(locale: &str) -> impl Iterator<Item = &str> {
+    let mut next = Some(locale);
+    std::iter::from_fn(move || {
+        let locale = next?;
+        next = locale.rfind(['-', '_']).map(|i| &locale[..i]);
+
+        Some(locale)
+    })
+}
+
+/// Fills in the placeables of a pattern.
+///
+/// `{ $name }` is replaced by the argument named `name` and `{ "text" }` by `text`. Other
+/// placeables, and those naming missing arguments, are left as they are.
+fn format_pattern(pattern: &str, args: &[(String, String)]) -> String {
+    let mut text = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+
+        let placeable = &rest[..=end];
+        let expression = placeable[1..end].trim();
+        let value = if let Some(name) = expression.strip_prefix('$') {
+            args.iter().find(|(arg, _)| arg == name).map(|(_, value)| value.as_str())
+        } else {
+            expression
+                .strip_prefix('"')
+                .and_then(|literal| literal.strip_suffix('"'))
+        };
+        text.push_str(value.unwrap_or(placeable));
+        rest = &rest[end + 1..];
+    }
+    text.push_str(rest);
+
+    text
+}
+
+/// A message key, along with the values of the arguments it refers to.
+///
+/// Strings convert into messages without arguments.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Message {
+    pub key: String,
+    /// The names and values of arguments, which replace placeables like `{ $name }`.
+    pub args: Vec<(String, String)>,
+}
+
+impl Message {
+    /// Creates a new message without arguments.
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Adds an argument, replacing any argument of the same name.
+    pub fn arg(mut self, name: impl Into<String>, value: impl fmt::Display) -> Self {
+        let name = name.into();
+        self.args.retain(|(arg, _)| *arg != name);
+        self.args.push((name, value.to_string()));
+
+        self
+    }
+}
+
+impl From<&str> for Message {
+    fn from(key: &str) -> Self {
+        Self::new(key)
+    }
+}
+
+impl From<String> for Message {
+    fn from(key: String) -> Self {
+        Self::new(key)
+    }
+}
+
+/// The translations of messages into one locale.
+///
+/// A catalog is parsed with [`Catalog::from_str`] from a subset of the [Fluent] syntax:
+///
+/// ```text
+/// # Comments begin with a number sign.
+/// greeting = Hello, { $name }!
+/// farewell =
+///     Goodbye,
+///     and thanks for all the fish.
+/// login = Log In
+///     .tooltip = Enter your user name and password.
+/// ```
+///
+/// Indented lines continue the message before them. Attributes, like `.tooltip` above, become
+/// messages of their own with keys like `login.tooltip`. Selectors and functions are not
+/// supported.
+///
+/// [Fluent]: https://projectfluent.org/fluent/guide/
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Catalog {
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// Creates a new, empty catalog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a catalog from a file.
+    ///
+    /// Files with the extension `po` are parsed with [`Catalog::from_po`]; all others, e.g., `ftl`
+    /// files, are parsed with [`Catalog::from_str`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LoadCatalogError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(LoadCatalogError::Io)?;
+
+        if path.extension().is_some_and(|extension| extension == "po") {
+            Self::from_po(&text)
+        } else {
+            text.parse()
+        }
+        .map_err(LoadCatalogError::Parse)
+    }
+
+    /// Parses a gettext `.po` file, keyed by `msgid`.
+    ///
+    /// Only the first form of plural messages is used, and untranslated messages, i.e., those with
+    /// an empty `msgstr`, are skipped. Placeables are filled in as in other catalogs.
+    pub fn from_po(s: &str) -> Result<Self, ParseCatalogError> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Field {
+            Id,
+            Str,
+            Other,
+        }
+
+        let mut catalog = Self::new();
+        let mut id = String::new();
+        let mut translation = String::new();
+        let mut field = Field::Other;
+        let mut insert = |id: &mut String, translation: &mut String| {
+            if !id.is_empty() && !translation.is_empty() {
+                catalog.insert(std::mem::take(id), std::mem::take(translation));
+            }
+            id.clear();
+        };
+
+        for (number, line) in s.lines().enumerate() {
+            let error = ParseCatalogError { line: number + 1 };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (keyword, rest) = match line.split_once(char::is_whitespace) {
+                Some((keyword, rest)) if !keyword.starts_with('"') => (Some(keyword), rest),
+                _ => (None, line),
+            };
+            let string = parse_po_string(rest.trim()).ok_or(error)?;
+            match keyword {
+                Some("msgid") => {
+                    insert(&mut id, &mut translation);
+                    id = string;
+                    field = Field::Id;
+                }
+                Some("msgstr" | "msgstr[0]") => {
+                    translation = string;
+                    field = Field::Str;
+                }
+                Some("msgctxt" | "msgid_plural") => field = Field::Other,
+                Some(keyword) if keyword.starts_with("msgstr[") => field = Field::Other,
+                Some(_) => return Err(error),
+                None => match field {
+                    Field::Id => id.push_str(&string),
+                    Field::Str => translation.push_str(&string),
+                    Field::Other => {}
+                },
+            }
+        }
+        insert(&mut id, &mut translation);
+
+        Ok(catalog)
+    }
+
+    /// Adds a message, replacing any message with the same key.
+    pub fn insert(&mut self, key: impl Into<String>, pattern: impl Into<String>) {
+        self.messages.insert(key.into(), pattern.into());
+    }
+
+    /// The untranslated pattern of a message, with its placeables, if this catalog has it.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.messages.get(key).map(String::as_str)
+    }
+
+    /// The number of messages in this catalog.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Determines if this catalog has no messages.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+impl FromStr for Catalog {
+    type Err = ParseCatalogError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut catalog = Self::new();
+        // The message being parsed, and the key of the message being continued, which differs for
+        // attributes.
+        let mut message: Option<String> = None;
+        let mut current: Option<String> = None;
+
+        for (number, line) in s.lines().enumerate() {
+            let error = ParseCatalogError { line: number + 1 };
+            if line.trim().is_empty() {
+                continue;
+            }
+            if line.starts_with('#') {
+                message = None;
+                current = None;
+                continue;
+            }
+
+            if !line.starts_with(char::is_whitespace) {
+                let (key, value) = parse_entry(line).ok_or(error)?;
+                catalog.insert(key, value);
+                message = Some(key.into());
+                current = Some(key.into());
+                continue;
+            }
+
+            let line = line.trim();
+            if let Some(attribute) = line.strip_prefix('.') {
+                let message = message.as_ref().ok_or(error)?;
+                let (name, value) = parse_entry(attribute).ok_or(error)?;
+                let key = format!("{}.{}", message, name);
+                catalog.insert(key.clone(), value);
+                current = Some(key);
+            } else {
+                let pattern = current
+                    .as_ref()
+                    .and_then(|key| catalog.messages.get_mut(key))
+                    .ok_or(error)?;
+                if !pattern.is_empty() {
+                    pattern.push('\n');
+                }
+                pattern.push_str(line);
+            }
+        }
+
+        Ok(catalog)
+    }
+}
+
+/// Parses a line of the form `key = value`.
+fn parse_entry(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once('=')?;
+    let key = key.trim();
+    let is_valid = key.starts_with(|c: char| c.is_ascii_alphabetic())
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    is_valid.then(|| (key, value.trim()))
+}
+
+/// Parses a quoted string in a `.po` file, processing its escape sequences.
+fn parse_po_string(s: &str) -> Option<String> {
+    let s = s.strip_prefix('"')?.strip_suffix('"')?;
+    let mut string = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            string.push(c);
+            continue;
+        }
+        string.push(match chars.next()? {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            c @ ('"' | '\\') => c,
+            _ => return None,
+        });
+    }
+
+    Some(string)
+}
+
+/// The error returned by [`Catalog::from_str`] and [`Catalog::from_po`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ParseCatalogError {
+    /// The one-based number of the line that failed to parse.
+    pub line: usize,
+}
+
+impl fmt::Display for ParseCatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid catalog at line {}", self.line)
+    }
+}
+
+impl std::error::Error for ParseCatalogError {}
+
+/// The error returned by [`Catalog::load`].
+#[derive(Debug)]
+pub enum LoadCatalogError {
+    Io(io::Error),
+    Parse(ParseCatalogError),
+}
+
+impl fmt::Display for LoadCatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "failed to read catalog: {}", e),
+            Self::Parse(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for LoadCatalogError {}
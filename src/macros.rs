@@ -40,10 +40,11 @@ macro_rules! def_subcontrol {
     (
         docs: $docs:literal,
         ty: $ty:ident,
+        kind: $kind:ident,
         handle: $ptr_ty:ident
         $(
             , cb_fns: [
-                $($cb:ident() $(-> $out:ty)?),* $(,)?
+                $($cb:ident( $($cb_arg:ty),* $(,)? ) $(-> $out:ty)?),* $(,)?
             ]
         )?
         $(
@@ -59,15 +60,18 @@ macro_rules! def_subcontrol {
                 // Here, we cast `ptr` to `*mut uiControl`. This is safe because the memory layout
                 // of all subcontrols begins with a `uiControl` struct, so they are effectively
                 // subclasses of `uiControl`.
-                Self::from_control(ui, unsafe { Control::new(ptr.cast()) })
+                let control =
+                    unsafe { Control::new(ui, ptr.cast(), $crate::ControlKind::$kind) };
+
+                Self::from_control(ui, control)
             }
 
-            pub(crate) fn from_control(ui: &'ui Ui, control: Control) -> Self {
+            pub(crate) fn from_control(ui: &'ui Ui, control: Control<'ui>) -> Self {
                 Self {
                     ui,
                     inner: control
                     $(
-                        , $($cb: None),*
+                        , $($cb: Default::default()),*
                     )?
                     $(
                         , $($field_name: $field_default),*
@@ -79,9 +83,14 @@ macro_rules! def_subcontrol {
         #[doc = indoc::indoc!($docs)]
         pub struct $ty<'ui> {
             ui: &'ui Ui,
-            inner: $crate::Control
+            inner: $crate::Control<'ui>
             $(
-                , $($cb: Option<&'ui mut (dyn 'ui + FnMut(&mut Self) $(-> $out)?)>),*
+                , $(
+                    $cb: $crate::callback::Callback<
+                        'ui,
+                        dyn 'ui + FnMut(&mut Self $(, $cb_arg)*) $(-> $out)?,
+                    >
+                ),*
             )?
             $(
                 , $($field_name: $field_ty),*
@@ -102,19 +111,20 @@ macro_rules! def_subcontrol {
             }
         }
 
-        impl std::ops::Deref for $ty<'_> {
-            type Target = Control;
+        impl $crate::control::sealed::Sealed for $ty<'_> {}
+
+        impl<'ui> $crate::Subcontrol<'ui> for $ty<'ui> {
+            const KIND: $crate::ControlKind = $crate::ControlKind::$kind;
+        }
+
+        impl<'ui> std::ops::Deref for $ty<'ui> {
+            type Target = Control<'ui>;
 
             fn deref(&self) -> &Self::Target {
                 &self.inner
             }
         }
 
-        impl std::ops::DerefMut for $ty<'_> {
-            fn deref_mut(&mut self) -> &mut Self::Target {
-                &mut self.inner
-            }
-        }
     };
 }
 
@@ -132,19 +142,33 @@ macro_rules! call_libui_new_fn {
         fn: $fn:ident( $($arg:expr),* $(,)? ) -> $out_ty:ident,
     ) => {
         call_fallible_libui_fn!( $fn($($arg),*) )
-            .map(|ptr| $ui.alloc_object($out_ty::new($ui, ptr)))
+            .map(|ptr| $ui.alloc_control($out_ty::new($ui, ptr)))
     };
 }
 
 macro_rules! bind_callback_fn {
+    // Calls the user-set callback, computing its argument, if any, from the handle beforehand.
+    (@call $user_cb:ident, $handle:ident) => {
+        ($user_cb)(&mut $handle)
+    };
+    (@call $user_cb:ident, $handle:ident, |$arg_handle:ident| $arg:expr) => {{
+        let arg = {
+            let $arg_handle: &_ = &$handle;
+            $arg
+        };
+
+        ($user_cb)(&mut $handle, arg)
+    }};
     (
         docs: $docs:literal,
         self: {
             ty: $self_ty:tt<$cb_lt:lifetime>,
             handle: $self_handle_ty:ident,
             fn: $fn:ident(),
+            emit: $emit_fn:ident(),
             cb: {
-                sig: $user_cb:ident -> $user_cb_out:ty
+                sig: $user_cb:ident $(( $user_cb_arg_ty:ty ))? -> $user_cb_out:ty
+                $(, arg: |$arg_handle:ident| $arg:expr )?
                 $(, map: $map_user_cb:expr )? $(,)?
             } $(,)?
         },
@@ -158,13 +182,13 @@ macro_rules! bind_callback_fn {
         // Wow, callbacks are complicated!
 
         #[doc = indoc::indoc!($docs)]
-        pub fn $fn<F>(&mut self, $user_cb: F)
+        pub fn $fn<F>(&self, $user_cb: F)
         where
-            F: $cb_lt + FnMut(&mut Self) -> $user_cb_out,
+            F: $cb_lt + FnMut(&mut Self $(, $user_cb_arg_ty)?) -> $user_cb_out,
         {
             /// A trampoline function to the user-set callback.
             unsafe extern "C" fn trampoline<$cb_lt>(
-                handle: *mut libui_ng_sys::$self_handle_ty,
+                handle: *mut $self_handle_ty,
                 $(_: $cb_arg,)*
                 this: *mut std::os::raw::c_void,
             ) -> $libui_cb_out {
@@ -172,24 +196,28 @@ macro_rules! bind_callback_fn {
                 debug_assert!(!handle.is_null());
                 debug_assert!(!this.is_null());
 
-                let this: &mut $self_ty<$cb_lt> = &mut *this.cast();
-                debug_assert!(this.$fn.is_some());
-
-                let mut handle = std::mem::ManuallyDrop::new($self_ty::new(this.ui, this.as_ptr()));
-
-                // SAFETY: `this.$fn` should definitely be `Some`, so it's OK to unwrap without
-                // checks.
-                let user_cb: &mut &mut (dyn FnMut(&mut $self_ty<$cb_lt>) -> $user_cb_out) = this.$fn.as_mut().unwrap_unchecked();
-
-                let result = (user_cb)(&mut handle);
-                $(
-                    let result = $map_user_cb(result);
-                )?
+                let this: &$self_ty<$cb_lt> = &*this.cast();
+                // While a dialog is open, only the controls within it respond to the user. See
+                // [`crate::dialog`].
+                if this.ui.is_blocked_by_dialog(this) {
+                    return <$libui_cb_out>::default();
+                }
 
-                result
+                match this.$emit_fn() {
+                    Some(result) => {
+                        $(
+                            let result = $map_user_cb(result);
+                        )?
+
+                        result
+                    }
+                    // The callback is already running, and this event was caused by it, e.g., by
+                    // changing the value of the control.
+                    None => <$libui_cb_out>::default(),
+                }
             }
 
-            self.$fn = Some(self.ui.alloc_object($user_cb));
+            self.$fn.set(self.ui.alloc_object($user_cb));
 
             unsafe {
                 $libui_fn(
@@ -198,10 +226,38 @@ macro_rules! bind_callback_fn {
                         $($libui_arg),*
                     )?
                     Some(trampoline),
-                    std::ptr::addr_of_mut!(*self).cast(),
+                    (self as *const Self).cast_mut().cast(),
                 );
             }
         }
+
+        /// Calls the user-set callback, if any, just as *libui-ng* would.
+        ///
+        /// This returns `None` if the callback is unset or already running.
+        // Not every event can be simulated, so some of these go unused.
+        #[allow(dead_code, unused_unsafe)]
+        pub(crate) fn $emit_fn(&self) -> Option<$user_cb_out> {
+            // SAFETY: `self.as_ptr()` is valid, and the handle, like the one created by the
+            // trampoline, never destroys it. Some widgets, like [`crate::MenuItem`], are
+            // constructed unsafely.
+            let handle = unsafe { $self_ty::new(self.ui, self.as_ptr()) };
+            let mut handle = std::mem::ManuallyDrop::new(handle);
+            let ui = self.ui;
+
+            self.$fn.call(|user_cb| {
+                // Events are recorded if a recording is in progress, whether they reach the
+                // trampoline or are simulated. This happens before the callback is called so that
+                // the event is recorded as the user made it.
+                let is_recorded =
+                    $crate::session::Source::record_event(&*handle, ui, stringify!($fn));
+                let result = bind_callback_fn!(@call user_cb, handle $(, |$arg_handle| $arg)?);
+                if is_recorded {
+                    $crate::session::record_checks(ui);
+                }
+
+                result
+            })
+        }
     };
 }
 
@@ -216,7 +272,10 @@ macro_rules! bind_add_child_fn {
         } $(,)?
     ) => {
         #[doc = indoc::indoc!($docs)]
-        pub fn $self_fn(&self, $self_child: &impl std::ops::DerefMut<Target = Control>) {
+        pub fn $self_fn<'c>(
+            &self,
+            $self_child: &impl std::ops::Deref<Target = Control<'c>>,
+        ) {
             // Inform the child control that it should not destroy itself as *libui-ng* will take
             // care of that for us.
             $self_child.make_child();
@@ -399,3 +458,117 @@ macro_rules! bind_fn {
         }
     };
 }
+
+/// Implements the methods shared by selection controls whose items are values of type `T`, e.g.,
+/// [`crate::TypedCombobox`].
+///
+/// The type must have the fields `control`, which holds the underlying control, and `items`, which
+/// holds an `Rc<RefCell<Vec<T>>>` of the values in display order.
+macro_rules! impl_typed_selection {
+    (
+        ty: $ty:ident,
+        control: $control_ty:ident { select: $select_fn:ident() } $(,)?
+    ) => {
+        impl<'ui, T: 'ui + std::fmt::Display> $ty<'ui, T> {
+            /// The items of this control, in display order.
+            pub fn items(&self) -> std::cell::Ref<'_, [T]> {
+                std::cell::Ref::map(self.items.borrow(), Vec::as_slice)
+            }
+
+            /// Appends a new item, returning its index.
+            pub fn push(&self, item: T) -> Result<u16, crate::Error> {
+                let index = self.control.push_new_item(item.to_string())?;
+                self.items.borrow_mut().push(item);
+
+                Ok(index)
+            }
+
+            /// Appends all items of the given iterator.
+            pub fn extend(&self, items: impl IntoIterator<Item = T>) -> Result<(), crate::Error> {
+                for item in items {
+                    self.push(item)?;
+                }
+
+                Ok(())
+            }
+
+            /// The index of the currently-selected item, or `None` if no item is selected.
+            pub fn selected_index(&self) -> Option<u16> {
+                self.control.selected_item()
+            }
+
+            /// Calls the given function with the currently-selected item, or `None` if no item is
+            /// selected.
+            pub fn with_selected<U>(&self, f: impl FnOnce(Option<&T>) -> U) -> U {
+                let items = self.items.borrow();
+                f(self
+                    .selected_index()
+                    .and_then(|index| items.get(usize::from(index))))
+            }
+
+            /// A copy of the currently-selected item, or `None` if no item is selected.
+            pub fn selected(&self) -> Option<T>
+            where
+                T: Clone,
+            {
+                self.with_selected(|item| item.cloned())
+            }
+
+            /// Selects the item with the given index, or clears the selection if `index` is
+            /// `None`.
+            pub fn select(&self, index: Option<u16>) {
+                self.control.$select_fn(index);
+            }
+
+            /// Selects the first item equal to `value`, returning whether or not one was found.
+            ///
+            /// The selection is left unchanged if no item was found.
+            pub fn select_value(&self, value: &T) -> bool
+            where
+                T: PartialEq,
+            {
+                let index = self
+                    .items
+                    .borrow()
+                    .iter()
+                    .position(|item| item == value)
+                    .and_then(|index| u16::try_from(index).ok());
+                if index.is_some() {
+                    self.select(index);
+                }
+
+                index.is_some()
+            }
+
+            /// Sets a callback for when an item is selected.
+            ///
+            /// The callback receives a copy of the newly-selected item, or `None` if the selection
+            /// was cleared. The items aren't borrowed while it runs, so it may modify them.
+            pub fn on_item_selected(&self, mut f: impl 'ui + FnMut(Option<&T>))
+            where
+                T: Clone,
+            {
+                let items = std::rc::Rc::clone(&self.items);
+                self.control.on_item_selected(move |_, index| {
+                    let item = index
+                        .and_then(|index| items.borrow().get(usize::from(index)).cloned());
+                    f(item.as_ref());
+                });
+            }
+
+            /// Simulates a selection as though the user had picked the item at the given index.
+            #[doc = concat!("\n\nSee [`", stringify!($control_ty), "::simulate_selection`].")]
+            pub fn simulate_selection(&self, index: Option<u16>) {
+                self.control.simulate_selection(index);
+            }
+        }
+
+        impl<'ui, T> std::ops::Deref for $ty<'ui, T> {
+            type Target = Control<'ui>;
+
+            fn deref(&self) -> &Self::Target {
+                self.control
+            }
+        }
+    };
+}
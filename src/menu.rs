@@ -6,7 +6,7 @@ mod item;
 
 pub use item::Item;
 
-use crate::prelude::*;
+use crate::{locale::Message, prelude::*};
 
 impl Ui {
     /// Creates a new [`Menu`].
@@ -16,9 +16,9 @@ impl Ui {
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn create_menu(&self, name: impl AsRef<str>) -> Result<&mut Menu, crate::Error> {
+    pub fn create_menu(&self, name: impl AsRef<str>) -> Result<&Menu, crate::Error> {
         let name = make_cstring!(name.as_ref());
-        call_fallible_libui_fn!(uiNewMenu(name.as_ptr())).map(|menu| self.alloc_object(Menu { ptr: menu, ui: self }))
+        call_fallible_libui_fn!(uiNewMenu(name.as_ptr())).map(|menu| &*self.alloc_object(Menu { ptr: menu, ui: self }))
     }
 }
 
@@ -70,3 +70,39 @@ impl Menu<'_> {
         self.ptr
     }
 }
+
+impl<'ui> Menu<'ui> {
+    /// Appends a new item with the given message, translated into the current locale, and returns
+    /// it.
+    ///
+    /// Unlike other localized widgets, the item is not translated again when the locale changes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn push_new_localized_item(
+        &self,
+        message: impl Into<Message>,
+    ) -> Result<&'ui Item<'ui>, crate::Error> {
+        self.push_new_item(self.ui.tr(message))
+    }
+
+    /// Appends a new check item with the given message, translated into the current locale, and
+    /// returns it.
+    ///
+    /// Unlike other localized widgets, the item is not translated again when the locale changes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn push_new_localized_check_item(
+        &self,
+        message: impl Into<Message>,
+    ) -> Result<&'ui Item<'ui>, crate::Error> {
+        self.push_new_check_item(self.ui.tr(message))
+    }
+}
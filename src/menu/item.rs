@@ -24,7 +24,7 @@ macro_rules! impl_push_item_fn_with_name {
             pub fn $boing_fn(
                 &self,
                 text: impl AsRef<str>,
-            ) -> Result<&'ui mut Item<'ui>, $crate::Error> {
+            ) -> Result<&'ui Item<'ui>, $crate::Error> {
                 // SAFETY:
                 //
                 // `text` is dropped at the end of scope, at which point the underling string buffer
@@ -40,7 +40,7 @@ macro_rules! impl_push_item_fn_with_name {
 
                         // SAFETY: Items own callbacks, so they must live for the duration of
                         // `self.ui`.
-                        self.ui.alloc_object(item)
+                        &*self.ui.alloc_object(item)
                     })
             }
         }
@@ -55,7 +55,7 @@ macro_rules! impl_push_item_fn {
     ) => {
         impl<'ui> Menu<'ui> {
             #[doc = indoc::indoc!($docs)]
-            pub fn $boing_fn(&self) -> Result<&'ui mut Item<'ui>, $crate::Error> {
+            pub fn $boing_fn(&self) -> Result<&'ui Item<'ui>, $crate::Error> {
                 call_fallible_libui_fn!($libui_fn(self.as_ptr()))
                     .map(|ptr| {
                         // SAFETY: `call_fallible_libui_fn` guarantees that the mapped pointer is
@@ -64,7 +64,7 @@ macro_rules! impl_push_item_fn {
 
                         // SAFETY: Items own callbacks, so they must live for the duration of
                         // `self.ui`.
-                        self.ui.alloc_object(item)
+                        &*self.ui.alloc_object(item)
                     })
             }
         }
@@ -167,7 +167,7 @@ impl<'ui> Item<'ui> {
         Self {
             ui,
             ptr,
-            on_clicked: None,
+            on_clicked: Default::default(),
         }
     }
 }
@@ -204,7 +204,7 @@ impl<'ui> Item<'ui> {
 pub struct Item<'ui> {
     ui: &'ui Ui,
     ptr: *mut uiMenuItem,
-    on_clicked: Option<&'ui mut (dyn 'ui + FnMut(&mut Self))>,
+    on_clicked: crate::callback::Callback<'ui, dyn 'ui + FnMut(&mut Self)>,
 }
 
 impl<'ui> Item<'ui> {
@@ -256,6 +256,7 @@ impl<'ui> Item<'ui> {
             ty: Item<'ui>,
             handle: uiMenuItem,
             fn: on_clicked(),
+            emit: emit_clicked(),
             cb: { sig: f -> () },
         },
         libui: {
@@ -264,6 +265,20 @@ impl<'ui> Item<'ui> {
         },
     );
 
+    /// Simulates a click, calling the callback set by [`Item::on_clicked`] as though the user had
+    /// clicked this item.
+    ///
+    /// Unlike a real click, this doesn't toggle check items.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn simulate_click(&self) {
+        self.emit_clicked();
+    }
+
     bind_bool_fn!(
         docs: "
             Determines if this item is checked.
@@ -0,0 +1,892 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! An in-memory backend for testing *boing* applications.
+//!
+//! When *boing* is compiled with feature `mock`, it calls into a pure-Rust implementation of
+//! *libui-ng* rather than *libui-ng* itself. No window is ever displayed, so tests may run on
+//! machines without a display server or even a native GUI toolkit. Mock widgets remember everything
+//! they are told, which makes their state observable through the usual *boing* API, and the
+//! functions in this module simulate what a user would do.
+//!
+//! Feature `mock` takes precedence over feature `libui`, so it needn't be enabled with default
+//! features disabled. A crate that runs its tests against the mock backend should enable it in its
+//! `dev-dependencies`, which Cargo only unifies with its regular dependencies when building tests,
+//! so that the application itself still uses *libui-ng*.
+//!
+//! As with *libui-ng*, setting the state of a widget programmatically does not fire its callbacks;
+//! only the functions in this module do. The one exception is [`Tab::select_page`], which, like
+//! *libui-ng*, reports the new page as selected.
+//!
+//! Events are ignored for widgets that are not [enabled to the
+//! user](Control::is_enabled_to_user), as a real user couldn't interact with them either.
+//!
+//! # Panics
+//!
+//! The functions in this module panic when given a control that cannot receive the event, e.g.,
+//! when [`click`]ing a [`Label`], or when given a [`Custom`] control.
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> Result<(), boing::Error> {
+//! use std::{cell::Cell, rc::Rc};
+//!
+//! use boing::{mock, Ui};
+//!
+//! let ui = Ui::new()?;
+//!
+//! let clicks = Rc::new(Cell::new(0));
+//! let button = ui.create_pushbutton("Press Me!")?;
+//! button.on_clicked({
+//!     let clicks = Rc::clone(&clicks);
+//!     move |_| clicks.set(clicks.get() + 1)
+//! });
+//!
+//! mock::click(button);
+//! mock::click(button);
+//! assert_eq!(2, clicks.get());
+//! #
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Tab::select_page`]: crate::Tab::select_page
+//! [`Label`]: crate::Label
+//! [`Custom`]: crate::Custom
+
+pub mod ffi;
+
+use std::{fmt, os::raw::c_int, ptr};
+
+use ffi::{MenuItemRole, TableColumn, Widget, WidgetKind};
+
+use crate::{
+    area::{Modifiers, MouseEvent},
+    draw::DisplayList,
+    prelude::*,
+    Area,
+    Color,
+    ControlKind,
+    DateTime,
+    Font,
+    FontPicker,
+    MenuItem,
+    Table,
+    Window,
+};
+
+/// The mock widget behind the given control.
+fn widget(control: &Control) -> *mut Widget {
+    let ptr = control.as_ptr();
+    // SAFETY: `Control` guarantees that its pointer is valid.
+    assert!(
+        unsafe { ffi::is_widget(ptr) },
+        "{:?} controls cannot be driven by the mock backend",
+        control.kind(),
+    );
+
+    ptr.cast()
+}
+
+/// The mock widget behind the given control, or `None` if the user couldn't interact with it.
+fn interactable_widget(control: &Control, kinds: &[WidgetKind]) -> Option<*mut Widget> {
+    let w = widget(control);
+    // SAFETY: `w` is a valid widget.
+    let kind = unsafe { ffi::with(w, |s| s.kind) };
+    assert!(
+        kinds.contains(&kind),
+        "{:?} controls cannot receive this event",
+        control.kind(),
+    );
+
+    control.is_enabled_to_user().then_some(w)
+}
+
+/// Clicks a pushbutton, or toggles a checkbox.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), boing::Error> {
+/// use boing::{mock, Ui};
+///
+/// let ui = Ui::new()?;
+/// let checkbox = ui.create_checkbox("Remember me")?;
+///
+/// mock::click(checkbox);
+/// assert!(checkbox.checked());
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn click(control: &Control) {
+    if let Some(w) = interactable_widget(control, &[WidgetKind::Button, WidgetKind::Checkbox]) {
+        unsafe {
+            ffi::with(w, |s| {
+                if s.kind == WidgetKind::Checkbox {
+                    s.is_checked = !s.is_checked;
+                }
+            });
+            ffi::fire_changed(w);
+        }
+    }
+}
+
+/// Replaces the text of a text entry or editable combobox, as if the user had typed it.
+///
+/// Read-only entries are left untouched.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), boing::Error> {
+/// use boing::{mock, Ui};
+///
+/// let ui = Ui::new()?;
+/// let entry = ui.create_text_entry()?;
+/// entry.on_changed(|entry| println!("Hello, {}!", entry.text()));
+///
+/// mock::type_text(entry, "world");
+/// assert_eq!("world", entry.text());
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn type_text(control: &Control, text: &str) {
+    let kinds = [
+        WidgetKind::Entry,
+        WidgetKind::MultilineEntry,
+        WidgetKind::EditableCombobox,
+    ];
+    if let Some(w) = interactable_widget(control, &kinds) {
+        unsafe {
+            if !ffi::with(w, |s| s.is_read_only) {
+                ffi::with(w, |s| s.text = text.to_string());
+                ffi::fire_changed(w);
+            }
+        }
+    }
+}
+
+/// Moves a slider or spinbox to the given value.
+///
+/// The value is clamped to the range of the control.
+pub fn set_value(control: &Control, value: i32) {
+    if let Some(w) = interactable_widget(control, &[WidgetKind::Slider, WidgetKind::Spinbox]) {
+        unsafe {
+            ffi::with(w, |s| s.value = (value as c_int).clamp(s.min, s.max));
+            ffi::fire_changed(w);
+        }
+    }
+}
+
+/// Releases a slider, as if the user let go of it after dragging.
+pub fn release(control: &Control) {
+    if let Some(w) = interactable_widget(control, &[WidgetKind::Slider]) {
+        unsafe { ffi::fire_released(w) };
+    }
+}
+
+/// Selects an item of a combobox or radio buttons, or a page of a tab.
+///
+/// `None` deselects all items, which is something only combobox and radio button users can do.
+/// Selecting an item of an editable combobox replaces its text with the item.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), boing::Error> {
+/// use boing::{mock, Ui};
+///
+/// let ui = Ui::new()?;
+/// let combobox = ui.create_combobox()?;
+/// combobox.push_new_item("Apple")?;
+/// combobox.push_new_item("Orange")?;
+///
+/// mock::select(combobox, Some(1));
+/// assert_eq!(Some(1), combobox.selected_item());
+/// #
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Selecting a page of a tab calls its callback, which sees the same page titles as any other
+/// handle to the tab:
+///
+/// ```
+/// # fn main() -> Result<(), boing::Error> {
+/// use std::{cell::RefCell, rc::Rc};
+///
+/// use boing::{mock, Ui};
+///
+/// let ui = Ui::new()?;
+/// let tab = ui.create_tab()?;
+/// tab.push_new_page("Summary", ui.create_label("")?)?;
+/// tab.push_new_page("Details", ui.create_label("")?)?;
+///
+/// let shown = Rc::new(RefCell::new(None));
+/// tab.on_page_selected({
+///     let shown = Rc::clone(&shown);
+///     move |tab, page| *shown.borrow_mut() = page.and_then(|page| tab.page_title(page))
+/// });
+///
+/// mock::select(tab, Some(1));
+/// assert_eq!(Some("Details"), shown.borrow().as_deref());
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn select(control: &Control, index: Option<u16>) {
+    let kinds = [
+        WidgetKind::Combobox,
+        WidgetKind::EditableCombobox,
+        WidgetKind::RadioButtons,
+        WidgetKind::Tab,
+    ];
+    let w = match interactable_widget(control, &kinds) {
+        Some(w) => w,
+        None => return,
+    };
+
+    unsafe {
+        let is_valid = ffi::with(w, |s| {
+            let count = match s.kind {
+                WidgetKind::Tab => s.children.len(),
+                _ => s.items.len(),
+            };
+
+            match index {
+                Some(index) => usize::from(index) < count,
+                None => s.kind != WidgetKind::Tab,
+            }
+        });
+        assert!(is_valid, "cannot select index {:?} of {:?}", index, control.kind());
+
+        ffi::with(w, |s| {
+            let index = index.map_or(-1, c_int::from);
+            if s.kind == WidgetKind::EditableCombobox {
+                if let Some(item) = s.items.get(index as usize) {
+                    s.text = item.clone();
+                }
+            } else {
+                s.selected = index;
+            }
+        });
+        ffi::fire_changed(w);
+    }
+}
+
+/// Picks a color with a color picker.
+pub fn pick_color(control: &Control, color: Color) {
+    if let Some(w) = interactable_widget(control, &[WidgetKind::ColorButton]) {
+        unsafe {
+            ffi::with(w, |s| s.color = [color.red, color.green, color.blue, color.alpha]);
+            ffi::fire_changed(w);
+        }
+    }
+}
+
+/// Picks a date and time with a date-time picker.
+pub fn pick_date_time(control: &Control, date_time: DateTime) {
+    if let Some(w) = interactable_widget(control, &[WidgetKind::DateTimePicker]) {
+        unsafe {
+            ffi::with(w, |s| s.time = date_time.to_tm());
+            ffi::fire_changed(w);
+        }
+    }
+}
+
+/// Picks a font with a font picker.
+///
+/// # Panics
+///
+/// This panics if the family name of `font` contains a NUL byte.
+pub fn pick_font(control: &Control, font: &Font) {
+    if let Some(w) = interactable_widget(control, &[WidgetKind::FontButton]) {
+        set_font(w, font);
+        unsafe { ffi::fire_changed(w) };
+    }
+}
+
+/// Selects a font with a font picker without telling its callback, which *libui-ng* itself can't
+/// do.
+pub(crate) fn select_font(picker: &FontPicker, font: &Font) {
+    set_font(widget(picker), font);
+}
+
+fn set_font(w: *mut Widget, font: &Font) {
+    let desc = font.to_descriptor().expect("font family contains a NUL byte");
+    unsafe {
+        let desc = &*desc.as_ptr();
+        ffi::with(w, |s| {
+            s.font = (
+                font.family.clone(),
+                desc.Size,
+                desc.Weight,
+                desc.Italic,
+                desc.Stretch,
+            );
+        });
+    }
+}
+
+/// Asks a window to close, as if the user had clicked its close button.
+///
+/// What happens next is up to the window: one created with `should_quit_on_close` quits the
+/// application (see [`has_quit`]), while others are merely hidden.
+pub fn close(window: &Window) {
+    let w = widget(window);
+    // *boing* never asks for its windows to be destroyed, as they are owned by their [`Window`]s.
+    let _ = unsafe { ffi::fire_closing(w) };
+}
+
+/// Resizes the content area of a window, as if the user had dragged its border.
+pub fn resize(window: &Window, width: u16, height: u16) {
+    let w = widget(window);
+    unsafe {
+        ffi::with(w, |s| s.size = (width.into(), height.into()));
+        ffi::fire_content_size_changed(w);
+    }
+}
+
+/// Draws an area at the given size, returning what its handler drew.
+///
+/// The area remembers its size, which is reported to its handler by [`move_mouse`].
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), boing::Error> {
+/// use boing::{
+///     area::DrawParams,
+///     draw::{Brush, DrawContext, FillMode, Op, Path},
+///     mock,
+///     AreaHandler,
+///     Color,
+///     Ui,
+/// };
+///
+/// struct Background;
+///
+/// impl AreaHandler for Background {
+///     fn draw(&mut self, ctx: &mut dyn DrawContext, params: &DrawParams) {
+///         let mut path = Path::new(FillMode::Winding);
+///         path.add_rectangle(0.0, 0.0, params.area_width, params.area_height);
+///         ctx.fill(&path, &Brush::Solid(Color::WHITE));
+///     }
+/// }
+///
+/// let ui = Ui::new()?;
+/// let area = ui.create_area(Background)?;
+///
+/// let list = mock::draw(area, 64.0, 48.0);
+/// assert!(matches!(list.ops[..], [Op::Fill { .. }]));
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn draw(area: &Area, width: f64, height: f64) -> DisplayList {
+    let w = widget(area);
+    let ah = unsafe {
+        ffi::with(w, |s| {
+            s.size = (width as c_int, height as c_int);
+            s.area_handler
+        })
+    };
+    let mut ctx = ffi::uiDrawContext {
+        list: DisplayList::new(width, height),
+    };
+    let mut params = ffi::uiAreaDrawParams {
+        Context: &mut ctx,
+        AreaWidth: width,
+        AreaHeight: height,
+        ClipX: 0.0,
+        ClipY: 0.0,
+        ClipWidth: width,
+        ClipHeight: height,
+    };
+    unsafe {
+        if let Some(f) = (*ah).Draw {
+            f(ah, w, &mut params);
+        }
+    }
+
+    ctx.list
+}
+
+/// Sends a mouse event to an area.
+///
+/// Unlike those of other controls, area events are not recorded by [`Ui::start_recording`].
+///
+/// [`Ui::start_recording`]: crate::Ui::start_recording
+pub fn mouse_event(area: &Area, event: &MouseEvent) {
+    let Some(w) = interactable_widget(area, &[WidgetKind::Area]) else {
+        return;
+    };
+    let modifier = |is_held: bool, modifier: ffi::uiModifiers| if is_held { modifier } else { 0 };
+    let Modifiers {
+        ctrl,
+        alt,
+        shift,
+        super_key,
+    } = event.modifiers;
+    let button = |number: Option<u32>| number.map_or(0, |number| number as c_int);
+    let mut raw = ffi::uiAreaMouseEvent {
+        X: event.x,
+        Y: event.y,
+        AreaWidth: event.area_width,
+        AreaHeight: event.area_height,
+        Down: button(event.down),
+        Up: button(event.up),
+        Count: event.count as c_int,
+        Modifiers: modifier(ctrl, ffi::uiModifierCtrl)
+            | modifier(alt, ffi::uiModifierAlt)
+            | modifier(shift, ffi::uiModifierShift)
+            | modifier(super_key, ffi::uiModifierSuper),
+        Held1To64: event.held,
+    };
+    unsafe {
+        let ah = ffi::with(w, |s| s.area_handler);
+        if let Some(f) = (*ah).MouseEvent {
+            f(ah, w, &mut raw);
+        }
+    }
+}
+
+/// Moves the mouse to the given point of an area, with no buttons held.
+///
+/// The area is reported to be the size it was last drawn at by [`draw`].
+pub fn move_mouse(area: &Area, x: f64, y: f64) {
+    let (width, height) = unsafe { ffi::with(widget(area), |s| s.size) };
+    mouse_event(
+        area,
+        &MouseEvent {
+            x,
+            y,
+            area_width: width.into(),
+            area_height: height.into(),
+            down: None,
+            up: None,
+            count: 0,
+            modifiers: Modifiers::default(),
+            held: 0,
+        },
+    );
+}
+
+/// Moves the mouse into an area, or out of it if `has_left` is `true`.
+pub fn cross_mouse(area: &Area, has_left: bool) {
+    if let Some(w) = interactable_widget(area, &[WidgetKind::Area]) {
+        unsafe {
+            let ah = ffi::with(w, |s| s.area_handler);
+            if let Some(f) = (*ah).MouseCrossed {
+                f(ah, w, has_left.into());
+            }
+        }
+    }
+}
+
+/// The number of times an area has been asked to redraw itself, e.g., by
+/// [`Area::queue_redraw_all`].
+pub fn redraw_count(area: &Area) -> usize {
+    unsafe { ffi::with(widget(area), |s| s.redraw_count) }
+}
+
+/// The contents of a cell of a [`Table`], as shown by one of its columns.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Cell {
+    Text(String),
+    /// An image of the given size in points.
+    Image { width: i32, height: i32 },
+}
+
+/// The cells shown by a table, by row, in the order of its columns.
+///
+/// # Panics
+///
+/// Panics if the model gives a cell of the wrong type for a column.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use boing::{
+///     mock::{self, Cell},
+///     table::{RowBackgroundColor, Source, Value, ValueType},
+///     Image,
+///     Ui,
+/// };
+///
+/// struct Contacts<'ui> {
+///     avatar: Image<'ui>,
+///     names: Vec<&'static str>,
+/// }
+///
+/// impl Source for Contacts<'_> {
+///     fn column_count(&self) -> u16 {
+///         2
+///     }
+///
+///     fn column_type(&self, column: u16) -> ValueType {
+///         [ValueType::String, ValueType::Image][usize::from(column)]
+///     }
+///
+///     fn row_count(&self) -> u16 {
+///         self.names.len() as u16
+///     }
+///
+///     fn cell(&self, row: u16, column: u16) -> Value<'_> {
+///         match column {
+///             0 => Value::String(self.names[usize::from(row)]),
+///             _ => Value::Image(&self.avatar),
+///         }
+///     }
+/// }
+///
+/// let ui = Ui::new()?;
+/// let avatar = ui.create_image(24.0, 24.0)?;
+/// avatar.push_rgba(&[0xff; 4 * 24 * 24], 24, 24, 4 * 24)?;
+///
+/// let model = ui.create_table_model(Contacts {
+///     avatar,
+///     names: vec!["Ada", "Grace"],
+/// })?;
+/// let table = ui.create_table(model, RowBackgroundColor::Default)?;
+/// table.push_new_image_column("", 1)?;
+/// table.push_new_text_column("Name", 0)?;
+///
+/// let avatar = Cell::Image { width: 24, height: 24 };
+/// assert_eq!(
+///     vec![
+///         vec![avatar.clone(), Cell::Text("Ada".into())],
+///         vec![avatar, Cell::Text("Grace".into())],
+///     ],
+///     mock::cells(table),
+/// );
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn cells(table: &Table) -> Vec<Vec<Cell>> {
+    let w = widget(table);
+    let (model, columns) = unsafe { ffi::with(w, |s| (s.model, s.columns.clone())) };
+    let mh = unsafe { ffi::with(model, |s| s.model_handler) };
+
+    let (num_rows, cell_value) = unsafe { ((*mh).NumRows.unwrap(), (*mh).CellValue.unwrap()) };
+    let row_count = unsafe { num_rows(mh, model) };
+    (0..row_count)
+        .map(|row| {
+            columns
+                .iter()
+                .map(|(_, column)| {
+                    let (column, is_image) = match *column {
+                        TableColumn::Text(column) => (column, false),
+                        TableColumn::Image(column) => (column, true),
+                    };
+                    // SAFETY: *libui-ng* takes ownership of the values returned by models.
+                    let value = unsafe { Box::from_raw(cell_value(mh, model, row, column)) };
+                    match (*value, is_image) {
+                        (ffi::uiTableValue::String(text), false) => Cell::Text(text),
+                        (ffi::uiTableValue::Image(image), true) => {
+                            let (width, height) = unsafe { ffi::with(image, |s| s.size) };
+                            Cell::Image { width, height }
+                        }
+                        (value, _) => panic!("table cell has the wrong type: {:?}", value),
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Clicks a menu item.
+///
+/// Check items are toggled before the callback is called, and "Quit" items ask the application to
+/// quit (see [`has_quit`]). Disabled items ignore the click.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), boing::Error> {
+/// use boing::{mock, Ui};
+///
+/// let ui = Ui::new()?;
+/// let menu = ui.create_menu("File")?;
+/// let quit = menu.push_new_quit_item()?;
+///
+/// let _window = ui.create_window("Editor", 640, 480, true, true)?;
+/// mock::click_menu_item(quit);
+/// assert!(mock::has_quit());
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn click_menu_item(item: &MenuItem) {
+    let w = item.as_ptr();
+    unsafe {
+        let (role, is_enabled) = ffi::with(w, |s| (s.kind, s.is_enabled));
+        if !is_enabled {
+            return;
+        }
+        match role {
+            WidgetKind::MenuItem(MenuItemRole::Quit) => ffi::request_quit(),
+            WidgetKind::MenuItem(MenuItemRole::Check) => {
+                ffi::with(w, |s| s.is_checked = !s.is_checked);
+                ffi::fire_clicked(w, ptr::null_mut());
+            }
+            _ => ffi::fire_clicked(w, ptr::null_mut()),
+        }
+    }
+}
+
+/// Determines if the application has quit, e.g., because [`Ui::quit`] was called.
+///
+/// This is reset when a new [`Ui`] is created.
+///
+/// [`Ui::quit`]: crate::Ui
+pub fn has_quit() -> bool {
+    ffi::RUNTIME.with(|rt| rt.borrow().has_quit)
+}
+
+/// A message box shown by the application.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Message {
+    pub title: String,
+    pub description: String,
+    /// Whether or not this is an error message.
+    pub is_error: bool,
+}
+
+/// Removes and returns all message boxes shown so far, oldest first.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), boing::Error> {
+/// use boing::{mock, Ui};
+///
+/// let ui = Ui::new()?;
+/// let window = ui.create_window("Editor", 640, 480, false, false)?;
+/// window.present_alert("Saved", "Your changes were saved.")?;
+///
+/// let messages = mock::take_messages();
+/// assert_eq!(1, messages.len());
+/// assert_eq!("Saved", messages[0].title);
+/// assert!(mock::take_messages().is_empty());
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn take_messages() -> Vec<Message> {
+    ffi::RUNTIME.with(|rt| {
+        std::mem::take(&mut rt.borrow_mut().messages)
+            .into_iter()
+            .map(|(title, description, is_error)| Message {
+                title,
+                description,
+                is_error,
+            })
+            .collect()
+    })
+}
+
+/// The state of a control and its descendants at the time [`snapshot`] was called.
+///
+/// Fields irrelevant to the kind of control keep their default values. The [`fmt::Display`]
+/// implementation prints an indented outline, which makes for readable assertions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node {
+    pub kind: ControlKind,
+    /// The form label or tab page name given to this control by its parent.
+    pub label: Option<String>,
+    /// Whether or not this control was added to its parent with `can_stretch` set.
+    pub can_stretch: bool,
+    /// The text, title, or name of this control.
+    pub text: String,
+    pub is_checked: bool,
+    pub value: i32,
+    pub items: Vec<String>,
+    pub selected: Option<u16>,
+    pub is_visible: bool,
+    pub is_enabled: bool,
+    pub children: Vec<Node>,
+}
+
+/// Captures the state of a control and its descendants.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), boing::Error> {
+/// use boing::{mock, Ui};
+///
+/// let ui = Ui::new()?;
+/// let window = ui.create_window("Settings", 320, 240, false, false)?;
+/// let form = ui.create_form()?;
+/// form.push_new_child("Name", ui.create_text_entry()?, false)?;
+/// form.push_new_child("Notify", ui.create_checkbox("Enabled")?, false)?;
+/// window.set_child(form);
+///
+/// assert_eq!(
+///     concat!(
+///         "Window \"Settings\" (hidden)\n",
+///         "  Form\n",
+///         "    Name: TextEntry \"\"\n",
+///         "    Notify: Checkbox \"Enabled\" [ ]\n",
+///     ),
+///     mock::snapshot(window).to_string(),
+/// );
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn snapshot(control: &Control) -> Node {
+    unsafe { snapshot_ptr(control.as_ptr(), None, false) }
+}
+
+unsafe fn snapshot_ptr(ptr: *mut uiControl, label: Option<String>, can_stretch: bool) -> Node {
+    let is_visible = uiControlVisible(ptr) != 0;
+    let is_enabled = uiControlEnabled(ptr) != 0;
+
+    if !ffi::is_widget(ptr) {
+        return Node {
+            kind: ControlKind::Custom,
+            label,
+            can_stretch,
+            text: String::new(),
+            is_checked: false,
+            value: 0,
+            items: Vec::new(),
+            selected: None,
+            is_visible,
+            is_enabled,
+            children: Vec::new(),
+        };
+    }
+
+    let w: *mut Widget = ptr.cast();
+    let (node, children) = ffi::with(w, |s| {
+        let node = Node {
+            kind: control_kind(s.kind),
+            label,
+            can_stretch,
+            text: s.text.clone(),
+            is_checked: s.is_checked,
+            value: s.value,
+            items: s.items.clone(),
+            selected: u16::try_from(s.selected).ok(),
+            is_visible,
+            is_enabled,
+            children: Vec::new(),
+        };
+        let children: Vec<_> = s
+            .children
+            .iter()
+            .map(|child| (child.control, child.label.clone(), child.is_stretchy))
+            .collect();
+
+        (node, children)
+    });
+
+    Node {
+        children: children
+            .into_iter()
+            .map(|(child, label, can_stretch)| snapshot_ptr(child, label, can_stretch))
+            .collect(),
+        ..node
+    }
+}
+
+fn control_kind(kind: WidgetKind) -> ControlKind {
+    match kind {
+        WidgetKind::Area => ControlKind::Area,
+        WidgetKind::Box => ControlKind::Axis,
+        WidgetKind::Button => ControlKind::Pushbutton,
+        WidgetKind::Checkbox => ControlKind::Checkbox,
+        WidgetKind::ColorButton => ControlKind::ColorPicker,
+        WidgetKind::Combobox => ControlKind::Combobox,
+        WidgetKind::DateTimePicker => ControlKind::DateTimePicker,
+        WidgetKind::EditableCombobox => ControlKind::EditableCombobox,
+        WidgetKind::Entry => ControlKind::TextEntry,
+        WidgetKind::FontButton => ControlKind::FontPicker,
+        WidgetKind::Form => ControlKind::Form,
+        WidgetKind::Grid => ControlKind::Grid,
+        WidgetKind::Group => ControlKind::Group,
+        WidgetKind::Label => ControlKind::Label,
+        WidgetKind::MultilineEntry => ControlKind::MultilineTextEntry,
+        WidgetKind::ProgressBar => ControlKind::ProgressBar,
+        WidgetKind::RadioButtons => ControlKind::RadioButtons,
+        WidgetKind::Separator => ControlKind::Separator,
+        WidgetKind::Slider => ControlKind::Slider,
+        WidgetKind::Spinbox => ControlKind::Spinbox,
+        WidgetKind::Tab => ControlKind::Tab,
+        WidgetKind::Table => ControlKind::Table,
+        WidgetKind::Window => ControlKind::Window,
+        // These are never children of controls.
+        WidgetKind::Image
+        | WidgetKind::Menu
+        | WidgetKind::MenuItem(_)
+        | WidgetKind::TableModel => ControlKind::Custom,
+    }
+}
+
+impl Node {
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{:width$}", "", width = 2 * depth)?;
+        if let Some(ref label) = self.label {
+            write!(f, "{}: ", label)?;
+        }
+        write!(f, "{:?}", self.kind)?;
+
+        match self.kind {
+            ControlKind::Checkbox => {
+                write!(f, " {:?} [{}]", self.text, if self.is_checked { 'x' } else { ' ' })?;
+            }
+            ControlKind::Pushbutton
+            | ControlKind::Group
+            | ControlKind::Label
+            | ControlKind::MultilineTextEntry
+            | ControlKind::TextEntry
+            | ControlKind::Window => write!(f, " {:?}", self.text)?,
+            ControlKind::EditableCombobox => write!(f, " {:?} {:?}", self.text, self.items)?,
+            ControlKind::ProgressBar | ControlKind::Slider | ControlKind::Spinbox => {
+                write!(f, " = {}", self.value)?;
+            }
+            ControlKind::Combobox | ControlKind::RadioButtons => {
+                write!(f, " {:?}", self.items)?;
+                if let Some(selected) = self.selected {
+                    write!(f, " selected {}", selected)?;
+                }
+            }
+            ControlKind::Tab => {
+                if let Some(selected) = self.selected {
+                    write!(f, " selected {}", selected)?;
+                }
+            }
+            _ => {}
+        }
+
+        if self.can_stretch {
+            write!(f, " (stretchy)")?;
+        }
+        if !self.is_visible {
+            write!(f, " (hidden)")?;
+        }
+        if !self.is_enabled {
+            write!(f, " (disabled)")?;
+        }
+        writeln!(f)?;
+
+        for child in &self.children {
+            child.fmt_indented(f, depth + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
@@ -0,0 +1,2082 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! An in-memory implementation of the subset of the *libui-ng* API that *boing* uses.
+//!
+//! Names and signatures mirror those of *libui-ng-sys* so that the rest of *boing* is oblivious to
+//! which backend it calls into. Every widget is a [`Widget`] whose state is kept in memory, and
+//! callbacks are only called when an event is fired from [`crate::mock`].
+
+#![allow(
+    clippy::missing_safety_doc,
+    non_camel_case_types,
+    non_snake_case,
+    non_upper_case_globals
+)]
+
+use std::{
+    alloc::{self, Layout},
+    cell::RefCell,
+    collections::HashMap,
+    ffi::{CStr, CString},
+    mem,
+    os::raw::{c_char, c_int, c_long, c_uint, c_void},
+    ptr,
+};
+
+use crate::{
+    draw::{
+        Arc,
+        Brush,
+        DisplayList,
+        DrawContext,
+        FillMode,
+        GradientStop,
+        LineCap,
+        LineJoin,
+        Matrix,
+        Path,
+        StrokeParams,
+        TextAlign,
+        TextLayout,
+    },
+    Color,
+    Font,
+};
+
+/// The OS signature given to all mock widgets.
+pub(super) const OS_SIGNATURE: u32 = u32::from_be_bytes(*b"Mock");
+
+/// The signature *libui-ng* gives to all controls.
+const SIGNATURE: u32 = u32::from_be_bytes(*b"uiCo");
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct uiInitOptions {
+    pub Size: usize,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct uiControl {
+    pub Signature: u32,
+    pub OSSignature: u32,
+    pub TypeSignature: u32,
+    pub Destroy: Option<unsafe extern "C" fn(*mut uiControl)>,
+    pub Handle: Option<unsafe extern "C" fn(*mut uiControl) -> usize>,
+    pub Parent: Option<unsafe extern "C" fn(*mut uiControl) -> *mut uiControl>,
+    pub SetParent: Option<unsafe extern "C" fn(*mut uiControl, *mut uiControl)>,
+    pub Toplevel: Option<unsafe extern "C" fn(*mut uiControl) -> c_int>,
+    pub Visible: Option<unsafe extern "C" fn(*mut uiControl) -> c_int>,
+    pub Show: Option<unsafe extern "C" fn(*mut uiControl)>,
+    pub Hide: Option<unsafe extern "C" fn(*mut uiControl)>,
+    pub Enabled: Option<unsafe extern "C" fn(*mut uiControl) -> c_int>,
+    pub Enable: Option<unsafe extern "C" fn(*mut uiControl)>,
+    pub Disable: Option<unsafe extern "C" fn(*mut uiControl)>,
+}
+
+pub type uiTextWeight = c_uint;
+pub type uiTextItalic = c_uint;
+pub type uiTextStretch = c_uint;
+
+pub const uiTextItalicNormal: uiTextItalic = 0;
+pub const uiTextItalicOblique: uiTextItalic = 1;
+pub const uiTextItalicItalic: uiTextItalic = 2;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct uiFontDescriptor {
+    pub Family: *mut c_char,
+    pub Size: f64,
+    pub Weight: uiTextWeight,
+    pub Italic: uiTextItalic,
+    pub Stretch: uiTextStretch,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct tm {
+    pub tm_sec: c_int,
+    pub tm_min: c_int,
+    pub tm_hour: c_int,
+    pub tm_mday: c_int,
+    pub tm_mon: c_int,
+    pub tm_year: c_int,
+    pub tm_wday: c_int,
+    pub tm_yday: c_int,
+    pub tm_isdst: c_int,
+    pub tm_gmtoff: c_long,
+    pub tm_zone: *const c_char,
+}
+
+pub type uiDrawBrushType = c_uint;
+pub type uiDrawLineCap = c_uint;
+pub type uiDrawLineJoin = c_uint;
+pub type uiDrawFillMode = c_uint;
+pub type uiDrawTextAlign = c_uint;
+pub type uiModifiers = c_uint;
+
+pub const uiDrawBrushTypeSolid: uiDrawBrushType = 0;
+pub const uiDrawBrushTypeLinearGradient: uiDrawBrushType = 1;
+pub const uiDrawBrushTypeRadialGradient: uiDrawBrushType = 2;
+pub const uiDrawBrushTypeImage: uiDrawBrushType = 3;
+
+pub const uiDrawLineCapFlat: uiDrawLineCap = 0;
+pub const uiDrawLineCapRound: uiDrawLineCap = 1;
+pub const uiDrawLineCapSquare: uiDrawLineCap = 2;
+
+pub const uiDrawLineJoinMiter: uiDrawLineJoin = 0;
+pub const uiDrawLineJoinRound: uiDrawLineJoin = 1;
+pub const uiDrawLineJoinBevel: uiDrawLineJoin = 2;
+
+pub const uiDrawFillModeWinding: uiDrawFillMode = 0;
+pub const uiDrawFillModeAlternate: uiDrawFillMode = 1;
+
+pub const uiDrawTextAlignLeft: uiDrawTextAlign = 0;
+pub const uiDrawTextAlignCenter: uiDrawTextAlign = 1;
+pub const uiDrawTextAlignRight: uiDrawTextAlign = 2;
+
+pub const uiModifierCtrl: uiModifiers = 1 << 0;
+pub const uiModifierAlt: uiModifiers = 1 << 1;
+pub const uiModifierShift: uiModifiers = 1 << 2;
+pub const uiModifierSuper: uiModifiers = 1 << 3;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct uiAreaHandler {
+    pub Draw: Option<unsafe extern "C" fn(*mut uiAreaHandler, *mut uiArea, *mut uiAreaDrawParams)>,
+    pub MouseEvent:
+        Option<unsafe extern "C" fn(*mut uiAreaHandler, *mut uiArea, *mut uiAreaMouseEvent)>,
+    pub MouseCrossed: Option<unsafe extern "C" fn(*mut uiAreaHandler, *mut uiArea, c_int)>,
+    pub DragBroken: Option<unsafe extern "C" fn(*mut uiAreaHandler, *mut uiArea)>,
+    pub KeyEvent:
+        Option<unsafe extern "C" fn(*mut uiAreaHandler, *mut uiArea, *mut uiAreaKeyEvent) -> c_int>,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct uiAreaDrawParams {
+    pub Context: *mut uiDrawContext,
+    pub AreaWidth: f64,
+    pub AreaHeight: f64,
+    pub ClipX: f64,
+    pub ClipY: f64,
+    pub ClipWidth: f64,
+    pub ClipHeight: f64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct uiAreaMouseEvent {
+    pub X: f64,
+    pub Y: f64,
+    pub AreaWidth: f64,
+    pub AreaHeight: f64,
+    pub Down: c_int,
+    pub Up: c_int,
+    pub Count: c_int,
+    pub Modifiers: uiModifiers,
+    pub Held1To64: u64,
+}
+
+/// Key events are never fired by the mock.
+pub enum uiAreaKeyEvent {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct uiDrawBrushGradientStop {
+    pub Pos: f64,
+    pub R: f64,
+    pub G: f64,
+    pub B: f64,
+    pub A: f64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct uiDrawBrush {
+    pub Type: uiDrawBrushType,
+    pub R: f64,
+    pub G: f64,
+    pub B: f64,
+    pub A: f64,
+    pub X0: f64,
+    pub Y0: f64,
+    pub X1: f64,
+    pub Y1: f64,
+    pub OuterRadius: f64,
+    pub Stops: *mut uiDrawBrushGradientStop,
+    pub NumStops: usize,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct uiDrawStrokeParams {
+    pub Cap: uiDrawLineCap,
+    pub Join: uiDrawLineJoin,
+    pub Thickness: f64,
+    pub MiterLimit: f64,
+    pub Dashes: *mut f64,
+    pub NumDashes: usize,
+    pub DashPhase: f64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct uiDrawMatrix {
+    pub M11: f64,
+    pub M12: f64,
+    pub M21: f64,
+    pub M22: f64,
+    pub M31: f64,
+    pub M32: f64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct uiDrawTextLayoutParams {
+    pub String: *mut uiAttributedString,
+    pub DefaultFont: *mut uiFontDescriptor,
+    pub Width: f64,
+    pub Align: uiDrawTextAlign,
+}
+
+/// A mock draw context, which records what is drawn.
+pub struct uiDrawContext {
+    pub(super) list: DisplayList,
+}
+
+/// A mock path, which is built up as a [`Path`].
+pub struct uiDrawPath {
+    path: Path,
+}
+
+pub struct uiAttributedString {
+    text: String,
+    color: Option<Color>,
+}
+
+/// A mock attribute. Only color attributes are supported.
+pub struct uiAttribute {
+    color: Color,
+}
+
+pub struct uiDrawTextLayout {
+    layout: TextLayout,
+}
+
+/// A mock widget.
+///
+/// Like *libui-ng* controls, widgets begin with a `uiControl`, so pointers to them may be cast to
+/// and from `*mut uiControl`. Menus, menu items, and images are widgets too, although they are not
+/// controls as far as *boing* is concerned.
+#[repr(C)]
+pub struct Widget {
+    control: uiControl,
+    pub(super) state: RefCell<State>,
+}
+
+pub type uiTableValueType = c_uint;
+
+pub const uiTableValueTypeString: uiTableValueType = 0;
+pub const uiTableValueTypeImage: uiTableValueType = 1;
+pub const uiTableValueTypeInt: uiTableValueType = 2;
+pub const uiTableValueTypeColor: uiTableValueType = 3;
+
+pub const uiTableModelColumnNeverEditable: c_int = -1;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct uiTableModelHandler {
+    pub NumColumns:
+        Option<unsafe extern "C" fn(*mut uiTableModelHandler, *mut uiTableModel) -> c_int>,
+    pub ColumnType: Option<
+        unsafe extern "C" fn(
+            *mut uiTableModelHandler,
+            *mut uiTableModel,
+            c_int,
+        ) -> uiTableValueType,
+    >,
+    pub NumRows:
+        Option<unsafe extern "C" fn(*mut uiTableModelHandler, *mut uiTableModel) -> c_int>,
+    pub CellValue: Option<
+        unsafe extern "C" fn(
+            *mut uiTableModelHandler,
+            *mut uiTableModel,
+            c_int,
+            c_int,
+        ) -> *mut uiTableValue,
+    >,
+    pub SetCellValue: Option<
+        unsafe extern "C" fn(
+            *mut uiTableModelHandler,
+            *mut uiTableModel,
+            c_int,
+            c_int,
+            *const uiTableValue,
+        ),
+    >,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct uiTableParams {
+    pub Model: *mut uiTableModel,
+    pub RowBackgroundColorModelColumn: c_int,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct uiTableTextColumnOptionalParams {
+    pub ColorModelColumn: c_int,
+}
+
+/// The value of a table cell.
+#[derive(Debug)]
+pub enum uiTableValue {
+    String(String),
+    Image(*mut uiImage),
+    Int(c_int),
+    Color([f64; 4]),
+}
+
+/// A column of a table, and the model column it shows.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum TableColumn {
+    Text(c_int),
+    Image(c_int),
+}
+
+pub type uiArea = Widget;
+pub type uiBox = Widget;
+pub type uiButton = Widget;
+pub type uiCheckbox = Widget;
+pub type uiColorButton = Widget;
+pub type uiCombobox = Widget;
+pub type uiDateTimePicker = Widget;
+pub type uiEditableCombobox = Widget;
+pub type uiEntry = Widget;
+pub type uiFontButton = Widget;
+pub type uiForm = Widget;
+pub type uiGrid = Widget;
+pub type uiGroup = Widget;
+pub type uiImage = Widget;
+pub type uiLabel = Widget;
+pub type uiMenu = Widget;
+pub type uiMenuItem = Widget;
+pub type uiMultilineEntry = Widget;
+pub type uiProgressBar = Widget;
+pub type uiRadioButtons = Widget;
+pub type uiSeparator = Widget;
+pub type uiSlider = Widget;
+pub type uiSpinbox = Widget;
+pub type uiTab = Widget;
+pub type uiTable = Widget;
+pub type uiTableModel = Widget;
+pub type uiWindow = Widget;
+
+/// The *libui-ng* type of a [`Widget`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum WidgetKind {
+    Area,
+    Box,
+    Button,
+    Checkbox,
+    ColorButton,
+    Combobox,
+    DateTimePicker,
+    EditableCombobox,
+    Entry,
+    FontButton,
+    Form,
+    Grid,
+    Group,
+    Image,
+    Label,
+    Menu,
+    MenuItem(MenuItemRole),
+    MultilineEntry,
+    ProgressBar,
+    RadioButtons,
+    Separator,
+    Slider,
+    Spinbox,
+    Tab,
+    Table,
+    TableModel,
+    Window,
+}
+
+/// The purpose of a menu item.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum MenuItemRole {
+    Normal,
+    Check,
+    Quit,
+    Preferences,
+    About,
+    Separator,
+}
+
+type Callback = unsafe extern "C" fn(*mut Widget, *mut c_void);
+type ClosingCallback = unsafe extern "C" fn(*mut Widget, *mut c_void) -> c_int;
+type MenuCallback = unsafe extern "C" fn(*mut Widget, *mut Widget, *mut c_void);
+type ShouldQuitCallback = unsafe extern "C" fn(*mut c_void) -> c_int;
+type TimerCallback = unsafe extern "C" fn(*mut c_void) -> c_int;
+
+/// A callback and the data it is called with.
+#[derive(Clone, Copy)]
+pub(super) struct Handler<F> {
+    pub f: F,
+    pub data: *mut c_void,
+}
+
+fn handler<F>(f: Option<F>, data: *mut c_void) -> Option<Handler<F>> {
+    f.map(|f| Handler { f, data })
+}
+
+/// A callback scheduled with `uiTimer`.
+struct Timer {
+    due: u64,
+    interval: u64,
+    handler: Handler<TimerCallback>,
+}
+
+/// A child of a container widget.
+pub(super) struct Child {
+    pub control: *mut uiControl,
+    /// The form label or tab page name of the child.
+    pub label: Option<String>,
+    pub is_stretchy: bool,
+    pub is_margined: bool,
+}
+
+/// Everything there is to know about a [`Widget`].
+///
+/// Fields irrelevant to the kind of widget keep their default values.
+pub(super) struct State {
+    pub kind: WidgetKind,
+    /// The text, title, or name of the widget.
+    pub text: String,
+    pub is_checked: bool,
+    pub value: c_int,
+    pub min: c_int,
+    pub max: c_int,
+    pub has_tool_tip: bool,
+    pub is_read_only: bool,
+    pub is_margined: bool,
+    pub is_padded: bool,
+    pub items: Vec<String>,
+    /// The index of the selected item or page, or -1.
+    pub selected: c_int,
+    pub children: Vec<Child>,
+    pub parent: *mut uiControl,
+    pub is_visible: bool,
+    pub is_enabled: bool,
+    pub time: tm,
+    pub color: [f64; 4],
+    pub font: (String, f64, uiTextWeight, uiTextItalic, uiTextStretch),
+    pub size: (c_int, c_int),
+    pub is_fullscreen: bool,
+    pub is_borderless: bool,
+    pub is_resizeable: bool,
+    pub is_header_visible: bool,
+    pub column_widths: HashMap<c_int, c_int>,
+    /// The columns of a table.
+    pub columns: Vec<(String, TableColumn)>,
+    /// The model of a table.
+    pub model: *mut uiTableModel,
+    /// The handler of a table model.
+    pub model_handler: *mut uiTableModelHandler,
+    /// The sizes of the bitmaps pushed to an image.
+    pub bitmaps: Vec<(c_int, c_int)>,
+    /// The handler of an area.
+    pub area_handler: *mut uiAreaHandler,
+    /// The number of times an area has been asked to redraw itself.
+    pub redraw_count: usize,
+    /// The callback for the primary event of the widget, e.g., a button click.
+    pub on_changed: Option<Handler<Callback>>,
+    pub on_released: Option<Handler<Callback>>,
+    pub on_content_size_changed: Option<Handler<Callback>>,
+    pub on_closing: Option<Handler<ClosingCallback>>,
+    pub on_clicked: Option<Handler<MenuCallback>>,
+}
+
+impl State {
+    fn new(kind: WidgetKind) -> Self {
+        Self {
+            kind,
+            text: String::new(),
+            is_checked: false,
+            value: 0,
+            min: 0,
+            max: 0,
+            has_tool_tip: true,
+            is_read_only: false,
+            is_margined: false,
+            is_padded: false,
+            items: Vec::new(),
+            selected: -1,
+            children: Vec::new(),
+            parent: ptr::null_mut(),
+            is_visible: kind != WidgetKind::Window,
+            is_enabled: true,
+            time: tm {
+                tm_sec: 0,
+                tm_min: 0,
+                tm_hour: 0,
+                tm_mday: 1,
+                tm_mon: 0,
+                tm_year: 70,
+                tm_wday: 4,
+                tm_yday: 0,
+                tm_isdst: -1,
+                tm_gmtoff: 0,
+                tm_zone: ptr::null(),
+            },
+            color: [0.0, 0.0, 0.0, 1.0],
+            font: ("Sans".into(), 12.0, 400, uiTextItalicNormal, 4),
+            size: (0, 0),
+            is_fullscreen: false,
+            is_borderless: false,
+            is_resizeable: true,
+            is_header_visible: true,
+            column_widths: HashMap::new(),
+            columns: Vec::new(),
+            model: ptr::null_mut(),
+            model_handler: ptr::null_mut(),
+            bitmaps: Vec::new(),
+            area_handler: ptr::null_mut(),
+            redraw_count: 0,
+            on_changed: None,
+            on_released: None,
+            on_content_size_changed: None,
+            on_closing: None,
+            on_clicked: None,
+        }
+    }
+}
+
+/// Process-wide state, which is kept per thread so that tests may run in parallel.
+#[derive(Default)]
+pub(super) struct Runtime {
+    pub has_quit: bool,
+    pub on_should_quit: Option<Handler<ShouldQuitCallback>>,
+    /// Message boxes as `(title, description, is_error)`.
+    pub messages: Vec<(String, String, bool)>,
+    /// The time, in milliseconds, that the mock has spent in its main loop.
+    ///
+    /// Time only passes when a timer is due, so timers fire instantly in real time.
+    pub clock: u64,
+    timers: Vec<Timer>,
+    allocations: HashMap<usize, Layout>,
+}
+
+thread_local! {
+    pub(super) static RUNTIME: RefCell<Runtime> = RefCell::default();
+}
+
+fn new_widget(kind: WidgetKind, init: impl FnOnce(&mut State)) -> *mut Widget {
+    let mut state = State::new(kind);
+    init(&mut state);
+
+    Box::into_raw(Box::new(Widget {
+        control: uiControl {
+            Signature: SIGNATURE,
+            OSSignature: OS_SIGNATURE,
+            TypeSignature: 0,
+            Destroy: Some(widget_destroy),
+            Handle: Some(widget_handle),
+            Parent: Some(widget_parent),
+            SetParent: Some(widget_set_parent),
+            Toplevel: Some(widget_toplevel),
+            Visible: Some(widget_visible),
+            Show: Some(widget_show),
+            Hide: Some(widget_hide),
+            Enabled: Some(widget_enabled),
+            Enable: Some(widget_enable),
+            Disable: Some(widget_disable),
+        },
+        state: RefCell::new(state),
+    }))
+}
+
+/// Determines if the given control is a [`Widget`] rather than, e.g., a custom control.
+pub(super) unsafe fn is_widget(c: *mut uiControl) -> bool {
+    (*c).OSSignature == OS_SIGNATURE
+}
+
+/// Calls the given function with the state of a widget.
+///
+/// The state must not be borrowed while callbacks are called, as they may call back into this
+/// module.
+pub(super) unsafe fn with<R>(w: *mut Widget, f: impl FnOnce(&mut State) -> R) -> R {
+    debug_assert!(!w.is_null());
+    f(&mut (*w).state.borrow_mut())
+}
+
+/// Fires the primary event of a widget.
+pub(super) unsafe fn fire_changed(w: *mut Widget) {
+    if let Some(h) = with(w, |s| s.on_changed) {
+        (h.f)(w, h.data);
+    }
+}
+
+/// Fires the release event of a slider.
+pub(super) unsafe fn fire_released(w: *mut Widget) {
+    if let Some(h) = with(w, |s| s.on_released) {
+        (h.f)(w, h.data);
+    }
+}
+
+/// Fires the content-size-changed event of a window.
+pub(super) unsafe fn fire_content_size_changed(w: *mut Widget) {
+    if let Some(h) = with(w, |s| s.on_content_size_changed) {
+        (h.f)(w, h.data);
+    }
+}
+
+/// Fires the closing event of a window, returning whether the handler asked for the window to be
+/// destroyed.
+pub(super) unsafe fn fire_closing(w: *mut Widget) -> bool {
+    match with(w, |s| s.on_closing) {
+        Some(h) => (h.f)(w, h.data) != 0,
+        None => false,
+    }
+}
+
+/// Fires the click event of a menu item.
+pub(super) unsafe fn fire_clicked(item: *mut Widget, window: *mut Widget) {
+    if let Some(h) = with(item, |s| s.on_clicked) {
+        (h.f)(item, window, h.data);
+    }
+}
+
+/// Asks the should-quit handler whether or not to quit, quitting if so.
+pub(super) unsafe fn request_quit() {
+    let handler = RUNTIME.with(|rt| rt.borrow().on_should_quit);
+    if handler.is_none_or(|h| (h.f)(h.data) != 0) {
+        uiQuit();
+    }
+}
+
+fn to_string(text: *const c_char) -> String {
+    if text.is_null() {
+        String::new()
+    } else {
+        unsafe { CStr::from_ptr(text) }.to_string_lossy().into_owned()
+    }
+}
+
+fn to_text(text: &str) -> *mut c_char {
+    CString::new(text).unwrap_or_default().into_raw()
+}
+
+fn to_bool(value: c_int) -> bool {
+    value != 0
+}
+
+unsafe fn adopt(parent: *mut Widget, child: *mut uiControl) {
+    if !child.is_null() {
+        uiControlSetParent(child, parent.cast());
+    }
+}
+
+unsafe fn orphan(child: *mut uiControl) {
+    if !child.is_null() {
+        uiControlSetParent(child, ptr::null_mut());
+    }
+}
+
+unsafe fn push_child(w: *mut Widget, child: Child) {
+    let control = child.control;
+    with(w, |s| s.children.push(child));
+    adopt(w, control);
+}
+
+unsafe fn remove_child(w: *mut Widget, index: c_int) {
+    let child = with(w, |s| {
+        usize::try_from(index)
+            .ok()
+            .filter(|&i| i < s.children.len())
+            .map(|i| s.children.remove(i))
+    });
+    if let Some(child) = child {
+        orphan(child.control);
+    }
+}
+
+unsafe fn set_only_child(w: *mut Widget, child: *mut uiControl) {
+    let old = with(w, |s| mem::take(&mut s.children));
+    for old in old {
+        orphan(old.control);
+    }
+    if !child.is_null() {
+        push_child(
+            w,
+            Child {
+                control: child,
+                label: None,
+                is_stretchy: false,
+                is_margined: false,
+            },
+        );
+    }
+}
+
+unsafe fn set_selected(w: *mut Widget, index: c_int) {
+    with(w, |s| {
+        let count = match s.kind {
+            WidgetKind::Tab => s.children.len(),
+            _ => s.items.len(),
+        };
+        let is_valid = usize::try_from(index).is_ok_and(|i| i < count);
+        s.selected = if is_valid { index } else { -1 };
+    });
+}
+
+// Control vtable.
+
+unsafe extern "C" fn widget_destroy(c: *mut uiControl) {
+    let widget = Box::from_raw(c.cast::<Widget>());
+    let children = mem::take(&mut widget.state.borrow_mut().children);
+    drop(widget);
+
+    // Like *libui-ng*, destroying a container destroys its children.
+    for child in children {
+        uiControlDestroy(child.control);
+    }
+}
+
+unsafe extern "C" fn widget_handle(c: *mut uiControl) -> usize {
+    c as usize
+}
+
+unsafe extern "C" fn widget_parent(c: *mut uiControl) -> *mut uiControl {
+    with(c.cast(), |s| s.parent)
+}
+
+unsafe extern "C" fn widget_set_parent(c: *mut uiControl, parent: *mut uiControl) {
+    with(c.cast(), |s| s.parent = parent);
+}
+
+unsafe extern "C" fn widget_toplevel(c: *mut uiControl) -> c_int {
+    with(c.cast(), |s| s.kind == WidgetKind::Window).into()
+}
+
+unsafe extern "C" fn widget_visible(c: *mut uiControl) -> c_int {
+    with(c.cast(), |s| s.is_visible).into()
+}
+
+unsafe extern "C" fn widget_show(c: *mut uiControl) {
+    with(c.cast(), |s| s.is_visible = true);
+}
+
+unsafe extern "C" fn widget_hide(c: *mut uiControl) {
+    with(c.cast(), |s| s.is_visible = false);
+}
+
+unsafe extern "C" fn widget_enabled(c: *mut uiControl) -> c_int {
+    with(c.cast(), |s| s.is_enabled).into()
+}
+
+unsafe extern "C" fn widget_enable(c: *mut uiControl) {
+    with(c.cast(), |s| s.is_enabled = true);
+}
+
+unsafe extern "C" fn widget_disable(c: *mut uiControl) {
+    with(c.cast(), |s| s.is_enabled = false);
+}
+
+// Initialization and the main loop.
+
+pub unsafe fn uiInit(_: *mut uiInitOptions) -> *const c_char {
+    RUNTIME.with(|rt| {
+        let mut rt = rt.borrow_mut();
+        rt.has_quit = false;
+        // Timers set by a previous `Ui` would call into freed memory.
+        rt.timers.clear();
+        rt.clock = 0;
+    });
+
+    ptr::null()
+}
+
+pub unsafe fn uiUninit() {}
+
+pub unsafe fn uiFreeInitError(_: *const c_char) {}
+
+/// Fires the timer that is due next, if any, advancing the clock to it.
+///
+/// Returns whether or not a timer was fired.
+unsafe fn fire_next_timer() -> bool {
+    let timer = RUNTIME.with(|rt| {
+        let mut rt = rt.borrow_mut();
+        // Timers due at the same time fire in the order they were scheduled.
+        let (index, _) = rt.timers.iter().enumerate().min_by_key(|(_, timer)| timer.due)?;
+        let timer = rt.timers.remove(index);
+        rt.clock = rt.clock.max(timer.due);
+
+        Some(timer)
+    });
+
+    match timer {
+        Some(mut timer) => {
+            if (timer.handler.f)(timer.handler.data) != 0 {
+                RUNTIME.with(|rt| {
+                    let mut rt = rt.borrow_mut();
+                    timer.due = rt.clock + timer.interval;
+                    rt.timers.push(timer);
+                });
+            }
+
+            true
+        }
+        None => false,
+    }
+}
+
+pub unsafe fn uiMain() {
+    // The only events the mock generates itself are timers, so the main loop runs until there are
+    // none left.
+    while RUNTIME.with(|rt| !rt.borrow().has_quit) && fire_next_timer() {}
+}
+
+pub unsafe fn uiMainStep(wait: c_int) -> c_int {
+    if RUNTIME.with(|rt| rt.borrow().has_quit) {
+        return 0;
+    }
+    // Timers are the only events there are, so without one, *libui-ng* would wait forever.
+    assert!(
+        fire_next_timer() || wait == 0,
+        "the main loop is waiting for an event, but no timer is pending",
+    );
+
+    RUNTIME.with(|rt| !rt.borrow().has_quit).into()
+}
+
+pub unsafe fn uiQuit() {
+    RUNTIME.with(|rt| rt.borrow_mut().has_quit = true);
+}
+
+pub unsafe fn uiTimer(milliseconds: c_int, f: Option<TimerCallback>, data: *mut c_void) {
+    if let Some(handler) = handler(f, data) {
+        RUNTIME.with(|rt| {
+            let mut rt = rt.borrow_mut();
+            let interval = u64::try_from(milliseconds).unwrap_or(0);
+            let due = rt.clock + interval;
+            rt.timers.push(Timer {
+                due,
+                interval,
+                handler,
+            });
+        });
+    }
+}
+
+pub unsafe fn uiOnShouldQuit(f: Option<ShouldQuitCallback>, data: *mut c_void) {
+    RUNTIME.with(|rt| rt.borrow_mut().on_should_quit = handler(f, data));
+}
+
+pub unsafe fn uiFreeText(text: *mut c_char) {
+    drop(CString::from_raw(text));
+}
+
+// Controls.
+
+pub unsafe fn uiControlDestroy(c: *mut uiControl) {
+    ((*c).Destroy.unwrap())(c);
+}
+
+pub unsafe fn uiControlHandle(c: *mut uiControl) -> usize {
+    ((*c).Handle.unwrap())(c)
+}
+
+pub unsafe fn uiControlParent(c: *mut uiControl) -> *mut uiControl {
+    ((*c).Parent.unwrap())(c)
+}
+
+pub unsafe fn uiControlSetParent(c: *mut uiControl, parent: *mut uiControl) {
+    ((*c).SetParent.unwrap())(c, parent);
+}
+
+pub unsafe fn uiControlToplevel(c: *mut uiControl) -> c_int {
+    ((*c).Toplevel.unwrap())(c)
+}
+
+pub unsafe fn uiControlVisible(c: *mut uiControl) -> c_int {
+    ((*c).Visible.unwrap())(c)
+}
+
+pub unsafe fn uiControlShow(c: *mut uiControl) {
+    ((*c).Show.unwrap())(c);
+}
+
+pub unsafe fn uiControlHide(c: *mut uiControl) {
+    ((*c).Hide.unwrap())(c);
+}
+
+pub unsafe fn uiControlEnabled(c: *mut uiControl) -> c_int {
+    ((*c).Enabled.unwrap())(c)
+}
+
+pub unsafe fn uiControlEnable(c: *mut uiControl) {
+    ((*c).Enable.unwrap())(c);
+}
+
+pub unsafe fn uiControlDisable(c: *mut uiControl) {
+    ((*c).Disable.unwrap())(c);
+}
+
+pub unsafe fn uiControlEnabledToUser(c: *mut uiControl) -> c_int {
+    let mut c = c;
+    while !c.is_null() {
+        if !to_bool(uiControlEnabled(c)) {
+            return 0;
+        }
+        c = uiControlParent(c);
+    }
+
+    1
+}
+
+pub unsafe fn uiAllocControl(
+    size: usize,
+    os_signature: u32,
+    type_signature: u32,
+    _type_name: *const c_char,
+) -> *mut uiControl {
+    let layout = Layout::from_size_align(size.max(mem::size_of::<uiControl>()), 16).unwrap();
+    let c: *mut uiControl = alloc::alloc_zeroed(layout).cast();
+    if c.is_null() {
+        alloc::handle_alloc_error(layout);
+    }
+    RUNTIME.with(|rt| rt.borrow_mut().allocations.insert(c as usize, layout));
+
+    (*c).Signature = SIGNATURE;
+    (*c).OSSignature = os_signature;
+    (*c).TypeSignature = type_signature;
+
+    c
+}
+
+pub unsafe fn uiFreeControl(c: *mut uiControl) {
+    let layout = RUNTIME.with(|rt| rt.borrow_mut().allocations.remove(&(c as usize)));
+    alloc::dealloc(c.cast(), layout.expect("control was not allocated by `uiAllocControl`"));
+}
+
+pub unsafe fn uiControlVerifySetParent(c: *mut uiControl, parent: *mut uiControl) {
+    if to_bool(uiControlToplevel(c)) {
+        panic!("cannot set the parent of a top-level control");
+    }
+    if !parent.is_null() && !uiControlParent(c).is_null() {
+        panic!("cannot set the parent of a control that already has one");
+    }
+}
+
+// Windows.
+
+pub unsafe fn uiNewWindow(
+    title: *const c_char,
+    width: c_int,
+    height: c_int,
+    _has_menubar: c_int,
+) -> *mut uiWindow {
+    new_widget(WidgetKind::Window, |s| {
+        s.text = to_string(title);
+        s.size = (width, height);
+    })
+}
+
+pub unsafe fn uiWindowTitle(w: *mut uiWindow) -> *mut c_char {
+    with(w, |s| to_text(&s.text))
+}
+
+pub unsafe fn uiWindowSetTitle(w: *mut uiWindow, title: *const c_char) {
+    with(w, |s| s.text = to_string(title));
+}
+
+pub unsafe fn uiWindowContentSize(w: *mut uiWindow, width: *mut c_int, height: *mut c_int) {
+    with(w, |s| (*width, *height) = s.size);
+}
+
+pub unsafe fn uiWindowSetContentSize(w: *mut uiWindow, width: c_int, height: c_int) {
+    with(w, |s| s.size = (width, height));
+}
+
+pub unsafe fn uiWindowFullscreen(w: *mut uiWindow) -> c_int {
+    with(w, |s| s.is_fullscreen).into()
+}
+
+pub unsafe fn uiWindowSetFullscreen(w: *mut uiWindow, value: c_int) {
+    with(w, |s| s.is_fullscreen = to_bool(value));
+}
+
+pub unsafe fn uiWindowOnContentSizeChanged(w: *mut uiWindow, f: Option<Callback>, data: *mut c_void) {
+    with(w, |s| s.on_content_size_changed = handler(f, data));
+}
+
+pub unsafe fn uiWindowOnClosing(w: *mut uiWindow, f: Option<ClosingCallback>, data: *mut c_void) {
+    with(w, |s| s.on_closing = handler(f, data));
+}
+
+pub unsafe fn uiWindowBorderless(w: *mut uiWindow) -> c_int {
+    with(w, |s| s.is_borderless).into()
+}
+
+pub unsafe fn uiWindowSetBorderless(w: *mut uiWindow, value: c_int) {
+    with(w, |s| s.is_borderless = to_bool(value));
+}
+
+pub unsafe fn uiWindowSetChild(w: *mut uiWindow, child: *mut uiControl) {
+    set_only_child(w, child);
+}
+
+pub unsafe fn uiWindowMargined(w: *mut uiWindow) -> c_int {
+    with(w, |s| s.is_margined).into()
+}
+
+pub unsafe fn uiWindowSetMargined(w: *mut uiWindow, value: c_int) {
+    with(w, |s| s.is_margined = to_bool(value));
+}
+
+pub unsafe fn uiWindowResizeable(w: *mut uiWindow) -> c_int {
+    with(w, |s| s.is_resizeable).into()
+}
+
+pub unsafe fn uiWindowSetResizeable(w: *mut uiWindow, value: c_int) {
+    with(w, |s| s.is_resizeable = to_bool(value));
+}
+
+pub unsafe fn uiMsgBox(_parent: *mut uiWindow, title: *const c_char, desc: *const c_char) {
+    RUNTIME.with(|rt| {
+        rt.borrow_mut()
+            .messages
+            .push((to_string(title), to_string(desc), false))
+    });
+}
+
+pub unsafe fn uiMsgBoxError(_parent: *mut uiWindow, title: *const c_char, desc: *const c_char) {
+    RUNTIME.with(|rt| {
+        rt.borrow_mut()
+            .messages
+            .push((to_string(title), to_string(desc), true))
+    });
+}
+
+// Containers.
+
+pub unsafe fn uiNewHorizontalBox() -> *mut uiBox {
+    new_widget(WidgetKind::Box, |_| {})
+}
+
+pub unsafe fn uiNewVerticalBox() -> *mut uiBox {
+    new_widget(WidgetKind::Box, |s| s.text = "vertical".into())
+}
+
+pub unsafe fn uiBoxAppend(b: *mut uiBox, child: *mut uiControl, stretchy: c_int) {
+    push_child(
+        b,
+        Child {
+            control: child,
+            label: None,
+            is_stretchy: to_bool(stretchy),
+            is_margined: false,
+        },
+    );
+}
+
+pub unsafe fn uiBoxNumChildren(b: *mut uiBox) -> c_int {
+    with(b, |s| s.children.len() as c_int)
+}
+
+pub unsafe fn uiBoxDelete(b: *mut uiBox, index: c_int) {
+    remove_child(b, index);
+}
+
+pub unsafe fn uiBoxPadded(b: *mut uiBox) -> c_int {
+    with(b, |s| s.is_padded).into()
+}
+
+pub unsafe fn uiBoxSetPadded(b: *mut uiBox, value: c_int) {
+    with(b, |s| s.is_padded = to_bool(value));
+}
+
+pub unsafe fn uiNewForm() -> *mut uiForm {
+    new_widget(WidgetKind::Form, |_| {})
+}
+
+pub unsafe fn uiFormAppend(
+    f: *mut uiForm,
+    label: *const c_char,
+    child: *mut uiControl,
+    stretchy: c_int,
+) {
+    push_child(
+        f,
+        Child {
+            control: child,
+            label: Some(to_string(label)),
+            is_stretchy: to_bool(stretchy),
+            is_margined: false,
+        },
+    );
+}
+
+pub unsafe fn uiFormNumChildren(f: *mut uiForm) -> c_int {
+    with(f, |s| s.children.len() as c_int)
+}
+
+pub unsafe fn uiFormDelete(f: *mut uiForm, index: c_int) {
+    remove_child(f, index);
+}
+
+pub unsafe fn uiFormPadded(f: *mut uiForm) -> c_int {
+    with(f, |s| s.is_padded).into()
+}
+
+pub unsafe fn uiFormSetPadded(f: *mut uiForm, value: c_int) {
+    with(f, |s| s.is_padded = to_bool(value));
+}
+
+pub unsafe fn uiNewGrid() -> *mut uiGrid {
+    new_widget(WidgetKind::Grid, |_| {})
+}
+
+#[allow(clippy::too_many_arguments)]
+pub unsafe fn uiGridAppend(
+    g: *mut uiGrid,
+    child: *mut uiControl,
+    left: c_int,
+    top: c_int,
+    _x_span: c_int,
+    _y_span: c_int,
+    can_expand_x: c_int,
+    _x_align: c_uint,
+    can_expand_y: c_int,
+    _y_align: c_uint,
+) {
+    push_child(
+        g,
+        Child {
+            control: child,
+            label: Some(format!("{}, {}", left, top)),
+            is_stretchy: to_bool(can_expand_x) || to_bool(can_expand_y),
+            is_margined: false,
+        },
+    );
+}
+
+pub unsafe fn uiGridPadded(g: *mut uiGrid) -> c_int {
+    with(g, |s| s.is_padded).into()
+}
+
+pub unsafe fn uiGridSetPadded(g: *mut uiGrid, value: c_int) {
+    with(g, |s| s.is_padded = to_bool(value));
+}
+
+pub unsafe fn uiNewGroup(title: *const c_char) -> *mut uiGroup {
+    new_widget(WidgetKind::Group, |s| s.text = to_string(title))
+}
+
+pub unsafe fn uiGroupTitle(g: *mut uiGroup) -> *mut c_char {
+    with(g, |s| to_text(&s.text))
+}
+
+pub unsafe fn uiGroupSetTitle(g: *mut uiGroup, title: *const c_char) {
+    with(g, |s| s.text = to_string(title));
+}
+
+pub unsafe fn uiGroupSetChild(g: *mut uiGroup, child: *mut uiControl) {
+    set_only_child(g, child);
+}
+
+pub unsafe fn uiGroupMargined(g: *mut uiGroup) -> c_int {
+    with(g, |s| s.is_margined).into()
+}
+
+pub unsafe fn uiGroupSetMargined(g: *mut uiGroup, value: c_int) {
+    with(g, |s| s.is_margined = to_bool(value));
+}
+
+pub unsafe fn uiNewTab() -> *mut uiTab {
+    new_widget(WidgetKind::Tab, |_| {})
+}
+
+pub unsafe fn uiTabAppend(t: *mut uiTab, name: *const c_char, child: *mut uiControl) {
+    let index = uiTabNumPages(t);
+    uiTabInsertAt(t, name, index, child);
+}
+
+pub unsafe fn uiTabInsertAt(t: *mut uiTab, name: *const c_char, before: c_int, child: *mut uiControl) {
+    with(t, |s| {
+        let index = usize::try_from(before).unwrap_or(0).min(s.children.len());
+        s.children.insert(
+            index,
+            Child {
+                control: child,
+                label: Some(to_string(name)),
+                is_stretchy: false,
+                is_margined: false,
+            },
+        );
+
+        // Like *libui-ng*, the first page is selected as soon as it is added, and the selected page
+        // stays selected as pages are inserted before it.
+        if s.selected < 0 {
+            s.selected = 0;
+        } else if s.selected >= index as c_int {
+            s.selected += 1;
+        }
+    });
+    adopt(t, child);
+}
+
+pub unsafe fn uiTabDelete(t: *mut uiTab, index: c_int) {
+    remove_child(t, index);
+    with(t, |s| {
+        let count = s.children.len() as c_int;
+        if s.selected > index || s.selected >= count {
+            s.selected -= 1;
+        }
+        if count == 0 {
+            s.selected = -1;
+        }
+    });
+}
+
+pub unsafe fn uiTabNumPages(t: *mut uiTab) -> c_int {
+    with(t, |s| s.children.len() as c_int)
+}
+
+pub unsafe fn uiTabMargined(t: *mut uiTab, page: c_int) -> c_int {
+    with(t, |s| s.children[page as usize].is_margined).into()
+}
+
+pub unsafe fn uiTabSetMargined(t: *mut uiTab, page: c_int, value: c_int) {
+    with(t, |s| s.children[page as usize].is_margined = to_bool(value));
+}
+
+pub unsafe fn uiTabSelected(t: *mut uiTab) -> c_int {
+    with(t, |s| s.selected)
+}
+
+pub unsafe fn uiTabSetSelected(t: *mut uiTab, page: c_int) {
+    set_selected(t, page);
+    // Unlike most setters, switching pages programmatically is reported as a selection.
+    fire_changed(t);
+}
+
+pub unsafe fn uiTabOnSelected(t: *mut uiTab, f: Option<Callback>, data: *mut c_void) {
+    with(t, |s| s.on_changed = handler(f, data));
+}
+
+// Buttons and text.
+
+pub unsafe fn uiNewButton(text: *const c_char) -> *mut uiButton {
+    new_widget(WidgetKind::Button, |s| s.text = to_string(text))
+}
+
+pub unsafe fn uiButtonText(b: *mut uiButton) -> *mut c_char {
+    with(b, |s| to_text(&s.text))
+}
+
+pub unsafe fn uiButtonSetText(b: *mut uiButton, text: *const c_char) {
+    with(b, |s| s.text = to_string(text));
+}
+
+pub unsafe fn uiButtonOnClicked(b: *mut uiButton, f: Option<Callback>, data: *mut c_void) {
+    with(b, |s| s.on_changed = handler(f, data));
+}
+
+pub unsafe fn uiNewCheckbox(text: *const c_char) -> *mut uiCheckbox {
+    new_widget(WidgetKind::Checkbox, |s| s.text = to_string(text))
+}
+
+pub unsafe fn uiCheckboxText(c: *mut uiCheckbox) -> *mut c_char {
+    with(c, |s| to_text(&s.text))
+}
+
+pub unsafe fn uiCheckboxSetText(c: *mut uiCheckbox, text: *const c_char) {
+    with(c, |s| s.text = to_string(text));
+}
+
+pub unsafe fn uiCheckboxOnToggled(c: *mut uiCheckbox, f: Option<Callback>, data: *mut c_void) {
+    with(c, |s| s.on_changed = handler(f, data));
+}
+
+pub unsafe fn uiCheckboxChecked(c: *mut uiCheckbox) -> c_int {
+    with(c, |s| s.is_checked).into()
+}
+
+pub unsafe fn uiCheckboxSetChecked(c: *mut uiCheckbox, value: c_int) {
+    with(c, |s| s.is_checked = to_bool(value));
+}
+
+pub unsafe fn uiNewLabel(text: *const c_char) -> *mut uiLabel {
+    new_widget(WidgetKind::Label, |s| s.text = to_string(text))
+}
+
+pub unsafe fn uiLabelText(l: *mut uiLabel) -> *mut c_char {
+    with(l, |s| to_text(&s.text))
+}
+
+pub unsafe fn uiLabelSetText(l: *mut uiLabel, text: *const c_char) {
+    with(l, |s| s.text = to_string(text));
+}
+
+pub unsafe fn uiNewEntry() -> *mut uiEntry {
+    new_widget(WidgetKind::Entry, |_| {})
+}
+
+pub unsafe fn uiNewPasswordEntry() -> *mut uiEntry {
+    new_widget(WidgetKind::Entry, |_| {})
+}
+
+pub unsafe fn uiNewSearchEntry() -> *mut uiEntry {
+    new_widget(WidgetKind::Entry, |_| {})
+}
+
+pub unsafe fn uiEntryText(e: *mut uiEntry) -> *mut c_char {
+    with(e, |s| to_text(&s.text))
+}
+
+pub unsafe fn uiEntrySetText(e: *mut uiEntry, text: *const c_char) {
+    with(e, |s| s.text = to_string(text));
+}
+
+pub unsafe fn uiEntryOnChanged(e: *mut uiEntry, f: Option<Callback>, data: *mut c_void) {
+    with(e, |s| s.on_changed = handler(f, data));
+}
+
+pub unsafe fn uiEntryReadOnly(e: *mut uiEntry) -> c_int {
+    with(e, |s| s.is_read_only).into()
+}
+
+pub unsafe fn uiEntrySetReadOnly(e: *mut uiEntry, value: c_int) {
+    with(e, |s| s.is_read_only = to_bool(value));
+}
+
+pub unsafe fn uiNewMultilineEntry() -> *mut uiMultilineEntry {
+    new_widget(WidgetKind::MultilineEntry, |_| {})
+}
+
+pub unsafe fn uiNewNonWrappingMultilineEntry() -> *mut uiMultilineEntry {
+    new_widget(WidgetKind::MultilineEntry, |_| {})
+}
+
+pub unsafe fn uiMultilineEntryText(e: *mut uiMultilineEntry) -> *mut c_char {
+    with(e, |s| to_text(&s.text))
+}
+
+pub unsafe fn uiMultilineEntrySetText(e: *mut uiMultilineEntry, text: *const c_char) {
+    with(e, |s| s.text = to_string(text));
+}
+
+pub unsafe fn uiMultilineEntryAppend(e: *mut uiMultilineEntry, text: *const c_char) {
+    with(e, |s| s.text.push_str(&to_string(text)));
+}
+
+pub unsafe fn uiMultilineEntryOnChanged(
+    e: *mut uiMultilineEntry,
+    f: Option<Callback>,
+    data: *mut c_void,
+) {
+    with(e, |s| s.on_changed = handler(f, data));
+}
+
+pub unsafe fn uiMultilineEntryReadOnly(e: *mut uiMultilineEntry) -> c_int {
+    with(e, |s| s.is_read_only).into()
+}
+
+pub unsafe fn uiMultilineEntrySetReadOnly(e: *mut uiMultilineEntry, value: c_int) {
+    with(e, |s| s.is_read_only = to_bool(value));
+}
+
+// Numbers.
+
+fn ordered(min: c_int, max: c_int) -> (c_int, c_int) {
+    // *libui-ng* swaps the bounds if they're given in the wrong order.
+    (min.min(max), min.max(max))
+}
+
+pub unsafe fn uiNewSpinbox(min: c_int, max: c_int) -> *mut uiSpinbox {
+    new_widget(WidgetKind::Spinbox, |s| {
+        (s.min, s.max) = ordered(min, max);
+        s.value = s.min;
+    })
+}
+
+pub unsafe fn uiSpinboxValue(b: *mut uiSpinbox) -> c_int {
+    with(b, |s| s.value)
+}
+
+pub unsafe fn uiSpinboxSetValue(b: *mut uiSpinbox, value: c_int) {
+    with(b, |s| s.value = value.clamp(s.min, s.max));
+}
+
+pub unsafe fn uiSpinboxOnChanged(b: *mut uiSpinbox, f: Option<Callback>, data: *mut c_void) {
+    with(b, |s| s.on_changed = handler(f, data));
+}
+
+pub unsafe fn uiNewSlider(min: c_int, max: c_int) -> *mut uiSlider {
+    new_widget(WidgetKind::Slider, |s| {
+        (s.min, s.max) = ordered(min, max);
+        s.value = s.min;
+    })
+}
+
+pub unsafe fn uiSliderValue(b: *mut uiSlider) -> c_int {
+    with(b, |s| s.value)
+}
+
+pub unsafe fn uiSliderSetValue(b: *mut uiSlider, value: c_int) {
+    with(b, |s| s.value = value.clamp(s.min, s.max));
+}
+
+pub unsafe fn uiSliderHasToolTip(b: *mut uiSlider) -> c_int {
+    with(b, |s| s.has_tool_tip).into()
+}
+
+pub unsafe fn uiSliderSetHasToolTip(b: *mut uiSlider, value: c_int) {
+    with(b, |s| s.has_tool_tip = to_bool(value));
+}
+
+pub unsafe fn uiSliderOnChanged(b: *mut uiSlider, f: Option<Callback>, data: *mut c_void) {
+    with(b, |s| s.on_changed = handler(f, data));
+}
+
+pub unsafe fn uiSliderOnReleased(b: *mut uiSlider, f: Option<Callback>, data: *mut c_void) {
+    with(b, |s| s.on_released = handler(f, data));
+}
+
+pub unsafe fn uiSliderSetRange(b: *mut uiSlider, min: c_int, max: c_int) {
+    with(b, |s| {
+        (s.min, s.max) = ordered(min, max);
+        s.value = s.value.clamp(s.min, s.max);
+    });
+}
+
+pub unsafe fn uiNewProgressBar() -> *mut uiProgressBar {
+    new_widget(WidgetKind::ProgressBar, |s| (s.min, s.max) = (-1, 100))
+}
+
+pub unsafe fn uiProgressBarValue(b: *mut uiProgressBar) -> c_int {
+    with(b, |s| s.value)
+}
+
+pub unsafe fn uiProgressBarSetValue(b: *mut uiProgressBar, value: c_int) {
+    with(b, |s| s.value = value.clamp(s.min, s.max));
+}
+
+pub unsafe fn uiNewHorizontalSeparator() -> *mut uiSeparator {
+    new_widget(WidgetKind::Separator, |_| {})
+}
+
+pub unsafe fn uiNewVerticalSeparator() -> *mut uiSeparator {
+    new_widget(WidgetKind::Separator, |s| s.text = "vertical".into())
+}
+
+// Choices.
+
+pub unsafe fn uiNewCombobox() -> *mut uiCombobox {
+    new_widget(WidgetKind::Combobox, |_| {})
+}
+
+pub unsafe fn uiComboboxAppend(c: *mut uiCombobox, text: *const c_char) {
+    with(c, |s| s.items.push(to_string(text)));
+}
+
+pub unsafe fn uiComboboxInsertAt(c: *mut uiCombobox, before: c_int, text: *const c_char) {
+    with(c, |s| {
+        let index = usize::try_from(before).unwrap_or(0).min(s.items.len());
+        s.items.insert(index, to_string(text));
+        if s.selected >= index as c_int {
+            s.selected += 1;
+        }
+    });
+}
+
+pub unsafe fn uiComboboxDelete(c: *mut uiCombobox, index: c_int) {
+    with(c, |s| {
+        if let Some(i) = usize::try_from(index).ok().filter(|&i| i < s.items.len()) {
+            s.items.remove(i);
+            if s.selected == index {
+                s.selected = -1;
+            } else if s.selected > index {
+                s.selected -= 1;
+            }
+        }
+    });
+}
+
+pub unsafe fn uiComboboxClear(c: *mut uiCombobox) {
+    with(c, |s| {
+        s.items.clear();
+        s.selected = -1;
+    });
+}
+
+pub unsafe fn uiComboboxNumItems(c: *mut uiCombobox) -> c_int {
+    with(c, |s| s.items.len() as c_int)
+}
+
+pub unsafe fn uiComboboxSelected(c: *mut uiCombobox) -> c_int {
+    with(c, |s| s.selected)
+}
+
+pub unsafe fn uiComboboxSetSelected(c: *mut uiCombobox, index: c_int) {
+    set_selected(c, index);
+}
+
+pub unsafe fn uiComboboxOnSelected(c: *mut uiCombobox, f: Option<Callback>, data: *mut c_void) {
+    with(c, |s| s.on_changed = handler(f, data));
+}
+
+pub unsafe fn uiNewEditableCombobox() -> *mut uiEditableCombobox {
+    new_widget(WidgetKind::EditableCombobox, |_| {})
+}
+
+pub unsafe fn uiEditableComboboxAppend(c: *mut uiEditableCombobox, text: *const c_char) {
+    with(c, |s| s.items.push(to_string(text)));
+}
+
+pub unsafe fn uiEditableComboboxText(c: *mut uiEditableCombobox) -> *mut c_char {
+    with(c, |s| to_text(&s.text))
+}
+
+pub unsafe fn uiEditableComboboxSetText(c: *mut uiEditableCombobox, text: *const c_char) {
+    with(c, |s| s.text = to_string(text));
+}
+
+pub unsafe fn uiEditableComboboxOnChanged(
+    c: *mut uiEditableCombobox,
+    f: Option<Callback>,
+    data: *mut c_void,
+) {
+    with(c, |s| s.on_changed = handler(f, data));
+}
+
+pub unsafe fn uiNewRadioButtons() -> *mut uiRadioButtons {
+    new_widget(WidgetKind::RadioButtons, |_| {})
+}
+
+pub unsafe fn uiRadioButtonsAppend(r: *mut uiRadioButtons, text: *const c_char) {
+    with(r, |s| s.items.push(to_string(text)));
+}
+
+pub unsafe fn uiRadioButtonsSelected(r: *mut uiRadioButtons) -> c_int {
+    with(r, |s| s.selected)
+}
+
+pub unsafe fn uiRadioButtonsSetSelected(r: *mut uiRadioButtons, index: c_int) {
+    set_selected(r, index);
+}
+
+pub unsafe fn uiRadioButtonsOnSelected(
+    r: *mut uiRadioButtons,
+    f: Option<Callback>,
+    data: *mut c_void,
+) {
+    with(r, |s| s.on_changed = handler(f, data));
+}
+
+// Pickers.
+
+pub unsafe fn uiNewDateTimePicker() -> *mut uiDateTimePicker {
+    new_widget(WidgetKind::DateTimePicker, |_| {})
+}
+
+pub unsafe fn uiNewDatePicker() -> *mut uiDateTimePicker {
+    new_widget(WidgetKind::DateTimePicker, |s| s.text = "date".into())
+}
+
+pub unsafe fn uiNewTimePicker() -> *mut uiDateTimePicker {
+    new_widget(WidgetKind::DateTimePicker, |s| s.text = "time".into())
+}
+
+pub unsafe fn uiDateTimePickerTime(d: *mut uiDateTimePicker, time: *mut tm) {
+    with(d, |s| *time = s.time);
+}
+
+pub unsafe fn uiDateTimePickerSetTime(d: *mut uiDateTimePicker, time: *const tm) {
+    with(d, |s| s.time = *time);
+}
+
+pub unsafe fn uiDateTimePickerOnChanged(
+    d: *mut uiDateTimePicker,
+    f: Option<Callback>,
+    data: *mut c_void,
+) {
+    with(d, |s| s.on_changed = handler(f, data));
+}
+
+pub unsafe fn uiNewColorButton() -> *mut uiColorButton {
+    new_widget(WidgetKind::ColorButton, |_| {})
+}
+
+pub unsafe fn uiColorButtonColor(
+    b: *mut uiColorButton,
+    red: *mut f64,
+    green: *mut f64,
+    blue: *mut f64,
+    alpha: *mut f64,
+) {
+    with(b, |s| [*red, *green, *blue, *alpha] = s.color);
+}
+
+pub unsafe fn uiColorButtonSetColor(
+    b: *mut uiColorButton,
+    red: f64,
+    green: f64,
+    blue: f64,
+    alpha: f64,
+) {
+    with(b, |s| s.color = [red, green, blue, alpha]);
+}
+
+pub unsafe fn uiColorButtonOnChanged(b: *mut uiColorButton, f: Option<Callback>, data: *mut c_void) {
+    with(b, |s| s.on_changed = handler(f, data));
+}
+
+pub unsafe fn uiNewFontButton() -> *mut uiFontButton {
+    new_widget(WidgetKind::FontButton, |_| {})
+}
+
+pub unsafe fn uiFontButtonFont(b: *mut uiFontButton, desc: *mut uiFontDescriptor) {
+    with(b, |s| {
+        let (family, size, weight, italic, stretch) = &s.font;
+        *desc = uiFontDescriptor {
+            Family: to_text(family),
+            Size: *size,
+            Weight: *weight,
+            Italic: *italic,
+            Stretch: *stretch,
+        };
+    });
+}
+
+pub unsafe fn uiFreeFontButtonFont(desc: *mut uiFontDescriptor) {
+    if !(*desc).Family.is_null() {
+        uiFreeText((*desc).Family);
+        (*desc).Family = ptr::null_mut();
+    }
+}
+
+pub unsafe fn uiFontButtonOnChanged(b: *mut uiFontButton, f: Option<Callback>, data: *mut c_void) {
+    with(b, |s| s.on_changed = handler(f, data));
+}
+
+// Menus.
+
+pub unsafe fn uiNewMenu(name: *const c_char) -> *mut uiMenu {
+    // Like *libui-ng* menus, mock menus live until the program exits.
+    new_widget(WidgetKind::Menu, |s| s.text = to_string(name))
+}
+
+unsafe fn push_menu_item(m: *mut uiMenu, role: MenuItemRole, name: String) -> *mut uiMenuItem {
+    let item = new_widget(WidgetKind::MenuItem(role), |s| s.text = name);
+    with(m, |s| {
+        s.children.push(Child {
+            control: item.cast(),
+            label: None,
+            is_stretchy: false,
+            is_margined: false,
+        })
+    });
+
+    item
+}
+
+pub unsafe fn uiMenuAppendItem(m: *mut uiMenu, name: *const c_char) -> *mut uiMenuItem {
+    push_menu_item(m, MenuItemRole::Normal, to_string(name))
+}
+
+pub unsafe fn uiMenuAppendCheckItem(m: *mut uiMenu, name: *const c_char) -> *mut uiMenuItem {
+    push_menu_item(m, MenuItemRole::Check, to_string(name))
+}
+
+pub unsafe fn uiMenuAppendQuitItem(m: *mut uiMenu) -> *mut uiMenuItem {
+    push_menu_item(m, MenuItemRole::Quit, "Quit".into())
+}
+
+pub unsafe fn uiMenuAppendPreferencesItem(m: *mut uiMenu) -> *mut uiMenuItem {
+    push_menu_item(m, MenuItemRole::Preferences, "Preferences".into())
+}
+
+pub unsafe fn uiMenuAppendAboutItem(m: *mut uiMenu) -> *mut uiMenuItem {
+    push_menu_item(m, MenuItemRole::About, "About".into())
+}
+
+pub unsafe fn uiMenuAppendSeparator(m: *mut uiMenu) {
+    push_menu_item(m, MenuItemRole::Separator, String::new());
+}
+
+pub unsafe fn uiMenuItemEnable(i: *mut uiMenuItem) {
+    with(i, |s| s.is_enabled = true);
+}
+
+pub unsafe fn uiMenuItemDisable(i: *mut uiMenuItem) {
+    with(i, |s| s.is_enabled = false);
+}
+
+pub unsafe fn uiMenuItemOnClicked(i: *mut uiMenuItem, f: Option<MenuCallback>, data: *mut c_void) {
+    with(i, |s| s.on_clicked = handler(f, data));
+}
+
+pub unsafe fn uiMenuItemChecked(i: *mut uiMenuItem) -> c_int {
+    with(i, |s| s.is_checked).into()
+}
+
+pub unsafe fn uiMenuItemSetChecked(i: *mut uiMenuItem, value: c_int) {
+    with(i, |s| s.is_checked = to_bool(value));
+}
+
+// Images and tables.
+
+pub unsafe fn uiNewImage(width: f64, height: f64) -> *mut uiImage {
+    new_widget(WidgetKind::Image, |s| s.size = (width as c_int, height as c_int))
+}
+
+pub unsafe fn uiFreeImage(i: *mut uiImage) {
+    drop(Box::from_raw(i));
+}
+
+pub unsafe fn uiImageAppend(
+    i: *mut uiImage,
+    _pixels: *mut c_void,
+    width: c_int,
+    height: c_int,
+    _byte_stride: c_int,
+) {
+    with(i, |s| s.bitmaps.push((width, height)));
+}
+
+pub unsafe fn uiNewTableModel(mh: *mut uiTableModelHandler) -> *mut uiTableModel {
+    new_widget(WidgetKind::TableModel, |s| s.model_handler = mh)
+}
+
+// Tables always ask their models for the current rows, so there is nothing to update.
+
+pub unsafe fn uiTableModelRowInserted(_: *mut uiTableModel, _: c_int) {}
+
+pub unsafe fn uiTableModelRowChanged(_: *mut uiTableModel, _: c_int) {}
+
+pub unsafe fn uiTableModelRowDeleted(_: *mut uiTableModel, _: c_int) {}
+
+pub unsafe fn uiNewTableValueString(text: *const c_char) -> *mut uiTableValue {
+    Box::into_raw(Box::new(uiTableValue::String(to_string(text))))
+}
+
+pub unsafe fn uiNewTableValueImage(image: *mut uiImage) -> *mut uiTableValue {
+    Box::into_raw(Box::new(uiTableValue::Image(image)))
+}
+
+pub unsafe fn uiNewTableValueInt(value: c_int) -> *mut uiTableValue {
+    Box::into_raw(Box::new(uiTableValue::Int(value)))
+}
+
+pub unsafe fn uiNewTableValueColor(r: f64, g: f64, b: f64, a: f64) -> *mut uiTableValue {
+    Box::into_raw(Box::new(uiTableValue::Color([r, g, b, a])))
+}
+
+pub unsafe fn uiNewTable(params: *mut uiTableParams) -> *mut uiTable {
+    new_widget(WidgetKind::Table, |s| s.model = (*params).Model)
+}
+
+pub unsafe fn uiTableAppendTextColumn(
+    t: *mut uiTable,
+    name: *const c_char,
+    column: c_int,
+    _editable_column: c_int,
+    _params: *mut uiTableTextColumnOptionalParams,
+) {
+    let name = to_string(name);
+    with(t, |s| s.columns.push((name, TableColumn::Text(column))));
+}
+
+pub unsafe fn uiTableAppendImageColumn(t: *mut uiTable, name: *const c_char, column: c_int) {
+    let name = to_string(name);
+    with(t, |s| s.columns.push((name, TableColumn::Image(column))));
+}
+
+pub unsafe fn uiTableHeaderVisible(t: *mut uiTable) -> c_int {
+    with(t, |s| s.is_header_visible).into()
+}
+
+pub unsafe fn uiTableHeaderSetVisible(t: *mut uiTable, value: c_int) {
+    with(t, |s| s.is_header_visible = to_bool(value));
+}
+
+pub unsafe fn uiTableColumnWidth(t: *mut uiTable, column: c_int) -> c_int {
+    with(t, |s| s.column_widths.get(&column).copied().unwrap_or(-1))
+}
+
+pub unsafe fn uiTableColumnSetWidth(t: *mut uiTable, column: c_int, width: c_int) {
+    with(t, |s| s.column_widths.insert(column, width));
+}
+
+// Areas and drawing.
+
+pub unsafe fn uiNewArea(ah: *mut uiAreaHandler) -> *mut uiArea {
+    new_widget(WidgetKind::Area, |s| s.area_handler = ah)
+}
+
+pub unsafe fn uiAreaQueueRedrawAll(a: *mut uiArea) {
+    with(a, |s| s.redraw_count += 1);
+}
+
+fn color(r: f64, g: f64, b: f64, a: f64) -> Color {
+    Color {
+        red: r,
+        green: g,
+        blue: b,
+        alpha: a,
+    }
+}
+
+fn arc(
+    x_center: f64,
+    y_center: f64,
+    radius: f64,
+    start_angle: f64,
+    sweep: f64,
+    negative: c_int,
+) -> Arc {
+    Arc {
+        x_center,
+        y_center,
+        radius,
+        start_angle,
+        sweep,
+        is_negative: to_bool(negative),
+    }
+}
+
+pub unsafe fn uiDrawNewPath(fill_mode: uiDrawFillMode) -> *mut uiDrawPath {
+    let fill_mode = match fill_mode {
+        uiDrawFillModeAlternate => FillMode::Alternate,
+        _ => FillMode::Winding,
+    };
+
+    Box::into_raw(Box::new(uiDrawPath {
+        path: Path::new(fill_mode),
+    }))
+}
+
+pub unsafe fn uiDrawFreePath(p: *mut uiDrawPath) {
+    drop(Box::from_raw(p));
+}
+
+pub unsafe fn uiDrawPathNewFigure(p: *mut uiDrawPath, x: f64, y: f64) {
+    (*p).path.new_figure(x, y);
+}
+
+pub unsafe fn uiDrawPathNewFigureWithArc(
+    p: *mut uiDrawPath,
+    x_center: f64,
+    y_center: f64,
+    radius: f64,
+    start_angle: f64,
+    sweep: f64,
+    negative: c_int,
+) {
+    (*p).path
+        .new_figure_with_arc(arc(x_center, y_center, radius, start_angle, sweep, negative));
+}
+
+pub unsafe fn uiDrawPathLineTo(p: *mut uiDrawPath, x: f64, y: f64) {
+    (*p).path.line_to(x, y);
+}
+
+pub unsafe fn uiDrawPathArcTo(
+    p: *mut uiDrawPath,
+    x_center: f64,
+    y_center: f64,
+    radius: f64,
+    start_angle: f64,
+    sweep: f64,
+    negative: c_int,
+) {
+    (*p).path.arc_to(arc(x_center, y_center, radius, start_angle, sweep, negative));
+}
+
+pub unsafe fn uiDrawPathBezierTo(
+    p: *mut uiDrawPath,
+    c1x: f64,
+    c1y: f64,
+    c2x: f64,
+    c2y: f64,
+    x: f64,
+    y: f64,
+) {
+    (*p).path.bezier_to(c1x, c1y, c2x, c2y, x, y);
+}
+
+pub unsafe fn uiDrawPathCloseFigure(p: *mut uiDrawPath) {
+    (*p).path.close_figure();
+}
+
+pub unsafe fn uiDrawPathAddRectangle(p: *mut uiDrawPath, x: f64, y: f64, width: f64, height: f64) {
+    (*p).path.add_rectangle(x, y, width, height);
+}
+
+pub unsafe fn uiDrawPathEnd(_: *mut uiDrawPath) {}
+
+unsafe fn to_brush(b: &uiDrawBrush) -> Brush {
+    let stops = || {
+        if b.Stops.is_null() {
+            return Vec::new();
+        }
+        std::slice::from_raw_parts(b.Stops, b.NumStops)
+            .iter()
+            .map(|stop| GradientStop {
+                pos: stop.Pos,
+                color: color(stop.R, stop.G, stop.B, stop.A),
+            })
+            .collect()
+    };
+
+    match b.Type {
+        uiDrawBrushTypeLinearGradient => Brush::LinearGradient {
+            start: (b.X0, b.Y0),
+            end: (b.X1, b.Y1),
+            stops: stops(),
+        },
+        uiDrawBrushTypeRadialGradient => Brush::RadialGradient {
+            start: (b.X0, b.Y0),
+            outer_center: (b.X1, b.Y1),
+            outer_radius: b.OuterRadius,
+            stops: stops(),
+        },
+        _ => Brush::Solid(color(b.R, b.G, b.B, b.A)),
+    }
+}
+
+pub unsafe fn uiDrawStroke(
+    c: *mut uiDrawContext,
+    path: *mut uiDrawPath,
+    b: *mut uiDrawBrush,
+    p: *mut uiDrawStrokeParams,
+) {
+    let p = &*p;
+    let params = StrokeParams {
+        cap: match p.Cap {
+            uiDrawLineCapRound => LineCap::Round,
+            uiDrawLineCapSquare => LineCap::Square,
+            _ => LineCap::Flat,
+        },
+        join: match p.Join {
+            uiDrawLineJoinRound => LineJoin::Round,
+            uiDrawLineJoinBevel => LineJoin::Bevel,
+            _ => LineJoin::Miter,
+        },
+        thickness: p.Thickness,
+        miter_limit: p.MiterLimit,
+        dashes: if p.Dashes.is_null() {
+            Vec::new()
+        } else {
+            std::slice::from_raw_parts(p.Dashes, p.NumDashes).to_vec()
+        },
+        dash_phase: p.DashPhase,
+    };
+    (*c).list.stroke(&(*path).path, &to_brush(&*b), &params);
+}
+
+pub unsafe fn uiDrawFill(c: *mut uiDrawContext, path: *mut uiDrawPath, b: *mut uiDrawBrush) {
+    (*c).list.fill(&(*path).path, &to_brush(&*b));
+}
+
+pub unsafe fn uiDrawTransform(c: *mut uiDrawContext, m: *mut uiDrawMatrix) {
+    let m = &*m;
+    (*c).list.transform(&Matrix {
+        m11: m.M11,
+        m12: m.M12,
+        m21: m.M21,
+        m22: m.M22,
+        m31: m.M31,
+        m32: m.M32,
+    });
+}
+
+pub unsafe fn uiDrawClip(c: *mut uiDrawContext, path: *mut uiDrawPath) {
+    (*c).list.clip(&(*path).path);
+}
+
+pub unsafe fn uiDrawSave(c: *mut uiDrawContext) {
+    (*c).list.save();
+}
+
+pub unsafe fn uiDrawRestore(c: *mut uiDrawContext) {
+    (*c).list.restore();
+}
+
+pub unsafe fn uiNewAttributedString(text: *const c_char) -> *mut uiAttributedString {
+    Box::into_raw(Box::new(uiAttributedString {
+        text: to_string(text),
+        color: None,
+    }))
+}
+
+pub unsafe fn uiFreeAttributedString(s: *mut uiAttributedString) {
+    drop(Box::from_raw(s));
+}
+
+pub unsafe fn uiAttributedStringLen(s: *mut uiAttributedString) -> usize {
+    let s = &*s;
+    s.text.len()
+}
+
+pub unsafe fn uiNewColorAttribute(r: f64, g: f64, b: f64, a: f64) -> *mut uiAttribute {
+    Box::into_raw(Box::new(uiAttribute {
+        color: color(r, g, b, a),
+    }))
+}
+
+/// Sets an attribute on an attributed string, taking ownership of the attribute.
+///
+/// Attributes apply to the whole string, whatever the given range.
+pub unsafe fn uiAttributedStringSetAttribute(
+    s: *mut uiAttributedString,
+    a: *mut uiAttribute,
+    _start: usize,
+    _end: usize,
+) {
+    (*s).color = Some(Box::from_raw(a).color);
+}
+
+pub unsafe fn uiDrawNewTextLayout(params: *mut uiDrawTextLayoutParams) -> *mut uiDrawTextLayout {
+    let params = &*params;
+    let string = &*params.String;
+    let font = Font::from_desc(&*params.DefaultFont).expect("invalid font descriptor");
+
+    Box::into_raw(Box::new(uiDrawTextLayout {
+        layout: TextLayout {
+            text: string.text.clone(),
+            font,
+            color: string.color.unwrap_or(Color::BLACK),
+            width: (params.Width >= 0.0).then_some(params.Width),
+            align: match params.Align {
+                uiDrawTextAlignCenter => TextAlign::Center,
+                uiDrawTextAlignRight => TextAlign::Right,
+                _ => TextAlign::Left,
+            },
+        },
+    }))
+}
+
+pub unsafe fn uiDrawFreeTextLayout(tl: *mut uiDrawTextLayout) {
+    drop(Box::from_raw(tl));
+}
+
+pub unsafe fn uiDrawText(c: *mut uiDrawContext, tl: *mut uiDrawTextLayout, x: f64, y: f64) {
+    (*c).list.draw_text(&(*tl).layout, x, y);
+}
+
+/// Measures a text layout as [`TextLayout::estimated_size`] does, as the mock has no fonts.
+pub unsafe fn uiDrawTextLayoutExtents(
+    tl: *mut uiDrawTextLayout,
+    width: *mut f64,
+    height: *mut f64,
+) {
+    (*width, *height) = (*tl).layout.estimated_size();
+}
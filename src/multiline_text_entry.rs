@@ -10,7 +10,7 @@ macro_rules! impl_text_entry {
     ) => {
         impl Ui {
             #[doc = indoc::indoc!($docs)]
-            pub fn $self_fn(&self) -> Result<&mut MultilineTextEntry, crate::Error> {
+            pub fn $self_fn(&self) -> Result<&MultilineTextEntry, crate::Error> {
                 call_libui_new_fn!(
                     ui: self,
                     fn: $libui_fn() -> MultilineTextEntry,
@@ -55,11 +55,22 @@ def_subcontrol!(
         ```
     ",
     ty: MultilineTextEntry,
+    kind: MultilineTextEntry,
     handle: uiMultilineEntry,
     cb_fns: [ on_changed() ],
 );
 
 impl<'ui> MultilineTextEntry<'ui> {
+    /// The [`Ui`] that created this entry.
+    pub(crate) fn ui(&self) -> &'ui Ui {
+        self.ui
+    }
+
+    /// Unsets the callback set by [`MultilineTextEntry::on_changed`], returning it.
+    pub(crate) fn take_on_changed(&self) -> Option<&'ui mut (dyn 'ui + FnMut(&mut Self))> {
+        self.on_changed.take()
+    }
+
     bind_text_fn!(
         docs: "
             The text displayed in this entry.
@@ -120,6 +131,7 @@ impl<'ui> MultilineTextEntry<'ui> {
             ty: MultilineTextEntry<'ui>,
             handle: uiMultilineEntry,
             fn: on_changed(),
+            emit: emit_changed(),
             cb: { sig: f -> () },
         },
         libui: {
@@ -128,6 +140,25 @@ impl<'ui> MultilineTextEntry<'ui> {
         },
     );
 
+    /// Simulates typing, replacing the text of this entry and then calling the callback set by
+    /// [`MultilineTextEntry::on_changed`] as though the user had typed `text`.
+    ///
+    /// Read-only entries are left untouched, as the user couldn't type into them either.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn simulate_typing(&self, text: impl AsRef<str>) -> Result<(), crate::Error> {
+        if !self.is_read_only() {
+            self.set_text(text)?;
+            self.emit_changed();
+        }
+
+        Ok(())
+    }
+
     bind_bool_fn!(
         docs: "
             Determines if this entry is read-only.
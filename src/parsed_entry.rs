@@ -0,0 +1,311 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! [`ParsedEntry`] and the input masks it supports.
+
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    rc::Rc,
+    str::FromStr,
+};
+
+use crate::{
+    callback::{warn_on_error, Callback},
+    prelude::*,
+    Label,
+    TextEntry,
+};
+
+impl Ui {
+    /// Creates a new [`ParsedEntry`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), boing::Error> {
+    /// use std::net::Ipv4Addr;
+    ///
+    /// use boing::{parsed_entry::Mask, Ui};
+    ///
+    /// let ui: Ui;
+    /// # ui = Ui::new()?;
+    ///
+    /// let error_label = ui.create_label("")?;
+    /// let mut address = ui.create_parsed_entry::<Ipv4Addr>()?;
+    /// address.set_mask(Some(Mask::IpAddress));
+    /// address.set_validator(|address| {
+    ///     if address.is_unspecified() {
+    ///         Err("Please enter a specific address.".into())
+    ///     } else {
+    ///         Ok(())
+    ///     }
+    /// });
+    /// address.set_error_label(error_label);
+    /// address.on_changed(|address| {
+    ///     if let Ok(address) = address {
+    ///         println!("Connecting to {}", address);
+    ///     }
+    /// });
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_parsed_entry<'ui, T>(&'ui self) -> Result<ParsedEntry<'ui, T>, crate::Error>
+    where
+        T: 'ui + FromStr,
+        T::Err: fmt::Display,
+    {
+        let entry = self.create_text_entry()?;
+        let inner = Rc::new(Inner {
+            mask: Cell::new(None),
+            validator: RefCell::new(None),
+            error_label: Cell::new(None),
+            last_text: RefCell::new(entry.text()),
+            on_changed: Callback::default(),
+        });
+
+        let handler = Rc::clone(&inner);
+        entry.on_changed(move |entry| handler.handle_change(entry));
+
+        Ok(ParsedEntry { entry, inner })
+    }
+}
+
+/// A [`TextEntry`] whose text is parsed into a value of type `T`.
+///
+/// The text is parsed with [`FromStr`] and then checked by an optional validator. Should either
+/// step fail, the error is displayed in an optional companion [`Label`]. An optional [`Mask`]
+/// rejects keystrokes that could never lead to valid input.
+///
+/// This is created with [`Ui::create_parsed_entry`].
+pub struct ParsedEntry<'ui, T: FromStr> {
+    entry: &'ui TextEntry<'ui>,
+    inner: Rc<Inner<'ui, T>>,
+}
+
+/// A function that rejects parsed values for reasons other than their syntax.
+type Validator<'ui, T> = Box<dyn 'ui + Fn(&T) -> Result<(), String>>;
+
+/// A callback invoked with the result of parsing the text after every change.
+type OnChanged<'ui, T> = dyn 'ui + FnMut(Result<T, ParseError<<T as FromStr>::Err>>);
+
+struct Inner<'ui, T: FromStr> {
+    mask: Cell<Option<Mask>>,
+    validator: RefCell<Option<Validator<'ui, T>>>,
+    error_label: Cell<Option<&'ui Label<'ui>>>,
+    /// The last text that satisfied the mask, which is restored when the user enters text that
+    /// doesn't.
+    last_text: RefCell<String>,
+    on_changed: Callback<'ui, OnChanged<'ui, T>>,
+}
+
+impl<'ui, T> Inner<'ui, T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fn parse(&self, text: &str) -> Result<T, ParseError<T::Err>> {
+        let value = text.parse().map_err(ParseError::Parse)?;
+        if let Some(validator) = self.validator.borrow().as_ref() {
+            validator(&value).map_err(ParseError::Invalid)?;
+        }
+
+        Ok(value)
+    }
+
+    fn handle_change(&self, entry: &mut TextEntry) {
+        let text = entry.text();
+
+        if let Some(mask) = self.mask.get() {
+            if !mask.accepts(&text) {
+                // *libui-ng* doesn't report programmatic changes, so this doesn't recurse.
+                warn_on_error(entry.set_text(&*self.last_text.borrow()));
+                return;
+            }
+        }
+        *self.last_text.borrow_mut() = text.clone();
+
+        let result = self.parse(&text);
+        self.show_error(result.as_ref().err());
+
+        self.on_changed.call(|f| f(result));
+    }
+
+    fn show_error(&self, error: Option<&ParseError<T::Err>>) {
+        if let Some(label) = self.error_label.get() {
+            let text = error.map(ToString::to_string).unwrap_or_default();
+            warn_on_error(label.set_text(text));
+        }
+    }
+}
+
+impl<'ui, T> ParsedEntry<'ui, T>
+where
+    T: 'ui + FromStr,
+    T::Err: fmt::Display,
+{
+    /// The underlying text entry.
+    pub fn entry(&self) -> &TextEntry<'ui> {
+        self.entry
+    }
+
+    /// Parses and validates the current text.
+    pub fn value(&self) -> Result<T, ParseError<T::Err>> {
+        self.inner.parse(&self.entry.text())
+    }
+
+    /// Determines if the current text parses and validates successfully.
+    pub fn is_valid(&self) -> bool {
+        self.value().is_ok()
+    }
+
+    /// Replaces the current text with the given value.
+    pub fn set_value(&self, value: &T) -> Result<(), crate::Error>
+    where
+        T: fmt::Display,
+    {
+        let text = value.to_string();
+        self.entry.set_text(&text)?;
+        *self.inner.last_text.borrow_mut() = text;
+        self.refresh_error();
+
+        Ok(())
+    }
+
+    /// Sets the input mask, or removes it if `mask` is `None`.
+    ///
+    /// The current text is not checked against the new mask.
+    pub fn set_mask(&self, mask: Option<Mask>) {
+        self.inner.mask.set(mask);
+    }
+
+    /// Sets a function that further checks parsed values, returning a message describing the
+    /// problem if the value is invalid.
+    pub fn set_validator(&self, f: impl 'ui + Fn(&T) -> Result<(), String>) {
+        *self.inner.validator.borrow_mut() = Some(Box::new(f));
+        self.refresh_error();
+    }
+
+    /// Sets the label in which errors are displayed.
+    ///
+    /// The label is cleared while the text is valid. Note that the label must be placed in a
+    /// container separately.
+    pub fn set_error_label(&self, label: &'ui Label<'ui>) {
+        self.inner.error_label.set(Some(label));
+        self.refresh_error();
+    }
+
+    /// Sets a callback for when the text changes.
+    ///
+    /// The callback receives the result of parsing and validating the new text. It is not called
+    /// for keystrokes rejected by the mask.
+    pub fn on_changed(&self, f: impl 'ui + FnMut(Result<T, ParseError<T::Err>>)) {
+        self.inner.on_changed.set(self.entry.ui().alloc_object(f));
+    }
+
+    /// Simulates typing as though the user had entered `text`.
+    ///
+    /// The text is subject to the mask, just like a keystroke. See [`TextEntry::simulate_typing`].
+    pub fn simulate_typing(&self, text: impl AsRef<str>) -> Result<(), crate::Error> {
+        self.entry.simulate_typing(text)
+    }
+
+    fn refresh_error(&self) {
+        self.inner.show_error(self.value().as_ref().err());
+    }
+}
+
+impl<'ui, T: FromStr> std::ops::Deref for ParsedEntry<'ui, T> {
+    type Target = Control<'ui>;
+
+    fn deref(&self) -> &Self::Target {
+        self.entry
+    }
+}
+
+/// The error returned by [`ParsedEntry::value`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError<E> {
+    /// The text could not be parsed.
+    Parse(E),
+    /// The text was parsed but rejected by the validator, which gave this message.
+    Invalid(String),
+}
+
+impl<E: fmt::Display> fmt::Display for ParseError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(e) => e.fmt(f),
+            Self::Invalid(message) => f.write_str(message),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for ParseError<E> {}
+
+/// Restricts the text that may be typed into a [`ParsedEntry`].
+///
+/// Masks accept incomplete input, e.g., `192.168.` is accepted by [`Mask::IpAddress`], so they do
+/// not replace parsing.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Mask {
+    /// A decimal integer with an optional leading minus sign.
+    Integer,
+    /// A hexadecimal integer with an optional `0x` prefix, as parsed by [`Hex`].
+    Hex,
+    /// A dotted-decimal IPv4 address, as parsed by [`std::net::Ipv4Addr`].
+    IpAddress,
+}
+
+impl Mask {
+    /// Determines if the given text is, or could be the beginning of, input matching this mask.
+    pub fn accepts(&self, text: &str) -> bool {
+        match self {
+            Self::Integer => {
+                let digits = text.strip_prefix('-').unwrap_or(text);
+
+                digits.bytes().all(|b| b.is_ascii_digit())
+            }
+            Self::Hex => {
+                strip_hex_prefix(text)
+                    .bytes().all(|b| b.is_ascii_hexdigit())
+            }
+            Self::IpAddress => {
+                let octets: Vec<&str> = text.split('.').collect();
+
+                octets.len() <= 4
+                    && octets.iter().all(|octet| {
+                        octet.len() <= 3
+                            && octet.bytes().all(|b| b.is_ascii_digit())
+                            && octet.parse::<u16>().map_or(true, |value| value <= 255)
+                    })
+            }
+        }
+    }
+}
+
+/// An unsigned integer written in hexadecimal.
+///
+/// This parses text like `ff` or `0xFF` and displays as `0xff`. It pairs with [`Mask::Hex`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Hex(pub u64);
+
+impl FromStr for Hex {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        u64::from_str_radix(strip_hex_prefix(s), 16).map(Self)
+    }
+}
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#x}", self.0)
+    }
+}
+
+fn strip_hex_prefix(text: &str) -> &str {
+    text.strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .unwrap_or(text)
+}
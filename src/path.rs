@@ -13,5 +13,6 @@ def_subcontrol!(
         ```
     ",
     ty: Path,
+    kind: Path,
     handle: uiDrawPath,
 );
@@ -1,7 +1,10 @@
 // SPDX-License-Identifier: MPL-2.0
 
-pub(crate) use std::ops::DerefMut;
+pub(crate) use std::ops::Deref;
 
+#[cfg(not(feature = "mock"))]
 pub(crate) use libui_ng_sys::*;
+#[cfg(feature = "mock")]
+pub(crate) use crate::mock::ffi::*;
 
 pub(crate) use crate::{Control, Ui};
@@ -12,7 +12,7 @@ impl Ui {
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn create_progress_bar<'ui>(&'ui self) -> Result<&'ui mut ProgressBar, crate::Error> {
+    pub fn create_progress_bar<'ui>(&'ui self) -> Result<&'ui ProgressBar, crate::Error> {
         call_libui_new_fn!(
             ui: self,
             fn: uiNewProgressBar() -> ProgressBar,
@@ -31,15 +31,44 @@ def_subcontrol!(
         ```
     ",
     ty: ProgressBar,
+    kind: ProgressBar,
     handle: uiProgressBar,
 );
 
+/// The value of a [`ProgressBar`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Progress {
+    /// The action is the given percentage complete, from 0 to 100.
+    Determinate(u8),
+    /// It is unknown when the action will complete.
+    Indeterminate,
+}
+
+impl Progress {
+    fn from_param(value: i32) -> Self {
+        // *libui-ng* represents indeterminate progress as -1.
+        if value < 0 {
+            Self::Indeterminate
+        } else {
+            Self::Determinate(u8::try_from(value).unwrap_or(100).min(100))
+        }
+    }
+
+    fn into_param(self) -> i32 {
+        match self {
+            // *libui-ng* rejects values above 100, so we clamp them here.
+            Self::Determinate(percent) => percent.min(100).into(),
+            Self::Indeterminate => -1,
+        }
+    }
+}
+
 impl ProgressBar<'_> {
     bind_fn!(
         docs: "
             The current value of this progress bar.
 
-            This is 0 by default.
+            This is `Progress::Determinate(0)` by default.
 
             # Examples
 
@@ -48,8 +77,8 @@ impl ProgressBar<'_> {
             ```
         ",
         self: {
-            fn: value() -> u16,
-            map_out: |_, value| to_u16!(value),
+            fn: value() -> Progress,
+            map_out: |_, value| Progress::from_param(value),
         },
         libui: { fn: uiProgressBarValue() },
     );
@@ -58,13 +87,15 @@ impl ProgressBar<'_> {
         docs: "
             Sets the value of this progress bar.
 
+            Percentages above 100 are clamped to 100.
+
             # Examples
 
             ```no_run
             // TODO
             ```
         ",
-        self: { fn: set_value(value: u16) },
+        self: { fn: set_value(value: Progress => Progress::into_param) },
         libui: { fn: uiProgressBarSetValue() },
     );
 
@@ -76,6 +107,8 @@ impl ProgressBar<'_> {
             inner bar, indicating that an action is in progress but it is unknown when the action
             will complete.
 
+            This is equivalent to `set_value(Progress::Indeterminate)`.
+
             # Examples
 
             ```no_run
@@ -12,7 +12,7 @@ impl Ui {
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn create_pushbutton<'ui>(&'ui self, text: impl AsRef<str>) -> Result<&'ui mut Pushbutton, crate::Error> {
+    pub fn create_pushbutton<'ui>(&'ui self, text: impl AsRef<str>) -> Result<&'ui Pushbutton, crate::Error> {
         let text = make_cstring!(text.as_ref());
 
         call_libui_new_fn!(
@@ -58,6 +58,7 @@ def_subcontrol!(
         ## Linux
     "#,
     ty: Pushbutton,
+    kind: Pushbutton,
     handle: uiButton,
     cb_fns: [ on_clicked() ],
 );
@@ -109,6 +110,7 @@ impl<'ui> Pushbutton<'ui> {
             ty: Pushbutton<'ui>,
             handle: uiButton,
             fn: on_clicked(),
+            emit: emit_clicked(),
             cb: { sig: f -> () },
         },
         libui: {
@@ -116,4 +118,16 @@ impl<'ui> Pushbutton<'ui> {
             cb: { sig: () -> () },
         },
     );
+
+    /// Simulates a click, calling the callback set by [`Pushbutton::on_clicked`] as though the user
+    /// had clicked this pushbutton.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn simulate_click(&self) {
+        self.emit_clicked();
+    }
 }
@@ -2,7 +2,12 @@
 
 //! [`RadioButtons`].
 
-use crate::prelude::*;
+use std::{cell::RefCell, fmt, rc::Rc};
+
+use crate::{
+    combobox::{selection_from_param, selection_into_param},
+    prelude::*,
+};
 
 impl Ui {
     /// Creates a new set of [`RadioButtons`].
@@ -12,12 +17,28 @@ impl Ui {
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn create_radio_buttons<'ui>(&'ui self) -> Result<&'ui mut RadioButtons, crate::Error> {
+    pub fn create_radio_buttons<'ui>(&'ui self) -> Result<&'ui RadioButtons, crate::Error> {
         call_libui_new_fn!(
             ui: self,
             fn: uiNewRadioButtons() -> RadioButtons,
         )
     }
+
+    /// Creates a new set of [`TypedRadioButtons`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_typed_radio_buttons<'ui, T: fmt::Display>(
+        &'ui self,
+    ) -> Result<TypedRadioButtons<'ui, T>, crate::Error> {
+        Ok(TypedRadioButtons {
+            control: self.create_radio_buttons()?,
+            items: Rc::default(),
+        })
+    }
 }
 
 def_subcontrol!(
@@ -31,9 +52,10 @@ def_subcontrol!(
         ```
     ",
     ty: RadioButtons,
+    kind: RadioButtons,
     handle: uiRadioButtons,
-    cb_fns: [ on_item_selected() ],
-    fields: [ item_count: u16 = 0 ],
+    cb_fns: [ on_item_selected(Option<u16>) ],
+    fields: [ item_count: std::cell::Cell<u16> = std::cell::Cell::new(0) ],
 );
 
 impl<'ui> RadioButtons<'ui> {
@@ -47,14 +69,14 @@ impl<'ui> RadioButtons<'ui> {
             // TODO
             ```
         ",
-        self(mut): {
+        self: {
             fn: push_new_item(text) -> u16,
-            map_out: |this: &mut Self, _| {
+            map_out: |this: &Self, _| {
                 // *libui-ng* doesn't provide a function to get the item count, so we have to keep
                 // track ourselves.
 
-                let index = this.item_count;
-                this.item_count += 1;
+                let index = this.item_count.get();
+                this.item_count.set(index + 1);
 
                 index
             },
@@ -64,7 +86,9 @@ impl<'ui> RadioButtons<'ui> {
 
     bind_fn!(
         docs: "
-            The index of the currently-selected item.
+            The index of the currently-selected item, or `None` if no item is selected.
+
+            No item is selected by default.
 
             # Examples
 
@@ -73,15 +97,15 @@ impl<'ui> RadioButtons<'ui> {
             ```
         ",
         self: {
-            fn: selected_item() -> u16,
-            map_out: |_, index| to_u16!(index),
+            fn: selected_item() -> Option<u16>,
+            map_out: |_, index| selection_from_param(index),
         },
         libui: { fn: uiRadioButtonsSelected() },
     );
 
     bind_fn!(
         docs: "
-            Selects the item with the given index.
+            Selects the item with the given index, or clears the selection if `index` is `None`.
 
             # Examples
 
@@ -89,7 +113,7 @@ impl<'ui> RadioButtons<'ui> {
             // TODO
             ```
         ",
-        self: { fn: set_selected_item(index: u16) },
+        self: { fn: set_selected_item(index: Option<u16> => selection_into_param) },
         libui: { fn: uiRadioButtonsSetSelected() },
     );
 
@@ -97,6 +121,9 @@ impl<'ui> RadioButtons<'ui> {
         docs: "
             Sets a callback for when an item is selected.
 
+            The callback receives the index of the newly-selected item, or `None` if the selection
+            was cleared.
+
             This callback is unset by default.
 
             # Examples
@@ -109,11 +136,54 @@ impl<'ui> RadioButtons<'ui> {
             ty: RadioButtons<'ui>,
             handle: uiRadioButtons,
             fn: on_item_selected(),
-            cb: { sig: f -> () },
+            emit: emit_item_selected(),
+            cb: {
+                sig: f(Option<u16>) -> (),
+                arg: |this| this.selected_item(),
+            },
         },
         libui: {
             fn: uiRadioButtonsOnSelected(),
             cb: { sig: () -> () },
         },
     );
+
+    /// Simulates a selection, selecting the given item and then calling the callback set by
+    /// [`RadioButtons::on_item_selected`] as though the user had picked it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn simulate_selection(&self, item: Option<u16>) {
+        self.set_selected_item(item);
+        self.emit_item_selected();
+    }
+}
+
+/// A set of [`RadioButtons`] whose items are values of type `T`.
+///
+/// Items are displayed with their [`Display`](fmt::Display) implementation, and the values
+/// themselves are kept alongside the buttons so that selections may be mapped back to them.
+///
+/// Unlike [`TypedCombobox`](crate::TypedCombobox), items cannot be removed once added as
+/// *libui-ng* provides no means of doing so.
+///
+/// This is created with [`Ui::create_typed_radio_buttons`].
+pub struct TypedRadioButtons<'ui, T> {
+    control: &'ui RadioButtons<'ui>,
+    items: Rc<RefCell<Vec<T>>>,
+}
+
+impl_typed_selection!(
+    ty: TypedRadioButtons,
+    control: RadioButtons { select: set_selected_item() },
+);
+
+impl<'ui, T> TypedRadioButtons<'ui, T> {
+    /// The underlying radio buttons.
+    pub fn radio_buttons(&self) -> &RadioButtons<'ui> {
+        self.control
+    }
 }
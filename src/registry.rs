@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The controls created by a [`Ui`], which are forgotten once *libui-ng* destroys them.
+//!
+//! *libui-ng* destroys controls on its own, e.g., child controls are destroyed along with their
+//! parents. To learn when this happens, the `Destroy` method of every registered control is
+//! replaced by one that forgets the control before calling the original.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::{Rc, Weak},
+};
+
+use crate::{locale::Message, prelude::*, ControlKind, Subcontrol};
+
+thread_local! {
+    // *libui-ng* is only ever used from one thread, so the hooks of all controls are kept here
+    // rather than in each [`Ui`], which may be dropped before its controls are destroyed.
+    static HOOKS: RefCell<HashMap<*mut uiControl, Hook>> = RefCell::default();
+}
+
+/// The original `Destroy` method of a registered control.
+struct Hook {
+    destroy: unsafe extern "C" fn(*mut uiControl),
+    registry: Weak<Registry>,
+}
+
+unsafe extern "C" fn destroy(control: *mut uiControl) {
+    let hook = HOOKS
+        .with(|hooks| hooks.borrow_mut().remove(&control))
+        .expect("destroyed control has no hook");
+    if let Some(registry) = hook.registry.upgrade() {
+        registry.forget(control);
+    }
+
+    (*control).Destroy = Some(hook.destroy);
+    (hook.destroy)(control);
+}
+
+/// The controls created by a [`Ui`].
+#[derive(Default)]
+pub(crate) struct Registry {
+    /// The registered controls, keyed by their *libui-ng* handles.
+    ///
+    /// Entries are removed when their controls are destroyed, so their handles are always valid.
+    entries: RefCell<HashMap<*mut uiControl, Entry>>,
+    /// Controls given IDs with [`Ui::set_id`], keyed by ID.
+    ids: RefCell<HashMap<String, *mut uiControl>>,
+}
+
+/// A registered control.
+pub(crate) struct Entry {
+    /// The handle returned by the `Ui::create_*` method that created the control, of the concrete
+    /// type that its kind names.
+    ///
+    /// The handle is allocated in the arena of the [`Ui`], so it lives for as long as it does.
+    concrete_handle: *const (),
+    /// The [`Control`] within the concrete handle.
+    handle: *const Control<'static>,
+    kind: ControlKind,
+    /// The titles of the pages of a [`Tab`](crate::Tab).
+    ///
+    /// These are kept here rather than in the handle so that they are shared by all handles to the
+    /// tab, including those passed to callbacks.
+    pub(crate) page_titles: Vec<String>,
+    /// The message displayed by a widget localized with [`Ui::localize`].
+    pub(crate) message: Option<Message>,
+    /// Functions called once the control is destroyed.
+    pub(crate) on_destroy: Vec<Box<dyn FnOnce()>>,
+}
+
+impl Registry {
+    /// Registers a handle allocated in the arena of the [`Ui`] that owns this registry.
+    ///
+    /// # Safety
+    ///
+    /// `concrete_handle` must point to a handle that was just allocated in the arena.
+    pub(crate) unsafe fn register<'ui, T: Subcontrol<'ui>>(
+        self: &Rc<Self>,
+        concrete_handle: *const T,
+    ) {
+        let handle: *const Control<'ui> = &**concrete_handle;
+        let control = (*handle).as_ptr();
+        HOOKS.with(|hooks| {
+            let mut hooks = hooks.borrow_mut();
+            if let Some(hook) = hooks.get_mut(&control) {
+                hook.registry = Rc::downgrade(self);
+                return;
+            }
+
+            // SAFETY: `control` points to a valid `uiControl`, as `handle` was just created.
+            if let Some(original) = unsafe { (*control).Destroy.replace(destroy) } {
+                hooks.insert(
+                    control,
+                    Hook {
+                        destroy: original,
+                        registry: Rc::downgrade(self),
+                    },
+                );
+            }
+        });
+
+        self.entries.borrow_mut().insert(
+            control,
+            Entry {
+                concrete_handle: concrete_handle.cast(),
+                handle: handle.cast(),
+                kind: T::KIND,
+                page_titles: Vec::new(),
+                message: None,
+                on_destroy: Vec::new(),
+            },
+        );
+    }
+
+    /// The handle to the given control, or `None` if it wasn't created by the [`Ui`] or has been
+    /// destroyed.
+    pub(crate) fn handle(&self, control: *mut uiControl) -> Option<*const Control<'static>> {
+        self.entries.borrow().get(&control).map(|entry| entry.handle)
+    }
+
+    /// The concrete handle to the given control; see [`Entry::concrete_handle`].
+    pub(crate) fn concrete_handle(&self, control: *mut uiControl) -> Option<*const ()> {
+        self.entries
+            .borrow()
+            .get(&control)
+            .map(|entry| entry.concrete_handle)
+    }
+
+    /// All registered controls of the given kind.
+    pub(crate) fn controls_of_kind(&self, kind: ControlKind) -> Vec<*mut uiControl> {
+        self.entries
+            .borrow()
+            .iter()
+            .filter(|(_, entry)| entry.kind == kind)
+            .map(|(&control, _)| control)
+            .collect()
+    }
+
+    /// Calls `f` with the entry of the given control, or returns `None` if it isn't registered.
+    ///
+    /// The registry is borrowed while `f` runs, so `f` must not call into it.
+    pub(crate) fn with_entry<T>(
+        &self,
+        control: *mut uiControl,
+        f: impl FnOnce(&mut Entry) -> T,
+    ) -> Option<T> {
+        self.entries.borrow_mut().get_mut(&control).map(f)
+    }
+
+    /// Assigns an ID to a registered control, returning whether or not it is registered.
+    pub(crate) fn set_id(&self, id: String, control: *mut uiControl) -> bool {
+        let is_registered = self.entries.borrow().contains_key(&control);
+        if is_registered {
+            self.ids.borrow_mut().insert(id, control);
+        }
+
+        is_registered
+    }
+
+    pub(crate) fn remove_id(&self, id: &str) -> bool {
+        self.ids.borrow_mut().remove(id).is_some()
+    }
+
+    /// The handle to the control with the given ID.
+    pub(crate) fn find(&self, id: &str) -> Option<*mut uiControl> {
+        self.ids.borrow().get(id).copied()
+    }
+
+    /// An ID of the given control, if it has any.
+    pub(crate) fn id_of(&self, control: *mut uiControl) -> Option<String> {
+        self.ids
+            .borrow()
+            .iter()
+            .find(|(_, &other)| other == control)
+            .map(|(id, _)| id.clone())
+    }
+
+    /// All IDs, along with the controls they refer to.
+    pub(crate) fn ids(&self) -> Vec<(String, *mut uiControl)> {
+        self.ids
+            .borrow()
+            .iter()
+            .map(|(id, &control)| (id.clone(), control))
+            .collect()
+    }
+
+    /// The messages of all localized controls, along with the controls.
+    pub(crate) fn messages(&self) -> Vec<(*mut uiControl, Message)> {
+        self.entries
+            .borrow()
+            .iter()
+            .filter_map(|(&control, entry)| Some((control, entry.message.clone()?)))
+            .collect()
+    }
+
+    /// Forgets a control that is being destroyed.
+    fn forget(&self, control: *mut uiControl) {
+        tracing::debug!("Forgetting destroyed control @ {:#?}", control);
+        // The entry is dropped, and its functions called, once the registry is no longer borrowed.
+        let entry = self.entries.borrow_mut().remove(&control);
+        self.ids.borrow_mut().retain(|_, &mut other| other != control);
+        for f in entry.into_iter().flat_map(|entry| entry.on_destroy) {
+            f();
+        }
+    }
+}
@@ -0,0 +1,895 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Recording and replaying user interaction.
+//!
+//! While a recording is in progress, every event that originates from the user is appended to a
+//! [`Trace`], along with the new state of the control, e.g., the text of a [`TextEntry`]. After the
+//! callbacks for the event have run, the state of every control with an ID (see [`Ui::set_id`]) is
+//! recorded as well. Only events on controls with IDs are recorded, as there would be no way to
+//! find the control again during replay. Likewise, events are only observed on controls with the
+//! corresponding callback set, e.g., a [`Checkbox`] is only recorded being toggled if it has a
+//! callback set by [`Checkbox::on_toggled`].
+//!
+//! A trace may be saved to a file, e.g., to attach it to a bug report, and later replayed with
+//! [`Ui::replay`]. Events are simulated with the same timing as they were recorded, and after each
+//! event, the state of the controls is compared to the recorded state. The first difference stops
+//! the replay.
+//!
+//! Menu items cannot be given IDs, so clicks on them are not recorded.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use boing::{session::Trace, Ui};
+//!
+//! let ui = Ui::new()?;
+//! let window = ui.create_window("Bug Report", 320, 240, false, true)?;
+//! let entry = ui.create_text_entry()?;
+//! ui.set_id("name", entry)?;
+//! window.set_child(entry);
+//! window.show();
+//!
+//! // Record a session...
+//! ui.start_recording();
+//! ui.run();
+//! if let Some(trace) = ui.stop_recording() {
+//!     trace.save("bug-report.trace")?;
+//! }
+//!
+//! // ...and replay it later.
+//! ui.replay(Trace::load("bug-report.trace")?, |result| {
+//!     if let Err(e) = result {
+//!         eprintln!("Replay diverged: {}", e);
+//!     }
+//! });
+//! ui.run();
+//! #
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`TextEntry`]: crate::TextEntry
+//! [`Checkbox`]: crate::Checkbox
+//! [`Checkbox::on_toggled`]: crate::Checkbox::on_toggled
+
+use std::{
+    fmt,
+    fs,
+    io,
+    os::raw::{c_int, c_void},
+    path::Path,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    color,
+    font::{ItalicKind, StretchKind},
+    prelude::*,
+    Checkbox,
+    Color,
+    Combobox,
+    DateTime,
+    DateTimePicker,
+    EditableCombobox,
+    Font,
+    FontPicker,
+    Label,
+    MenuItem,
+    MultilineTextEntry,
+    Pushbutton,
+    RadioButtons,
+    Slider,
+    Spinbox,
+    Subcontrol,
+    Tab,
+    TextEntry,
+    Window,
+};
+
+/// The first line of every trace file.
+const HEADER: &str = "boing-trace 1";
+
+impl Ui {
+    /// Starts recording user interaction, discarding any recording already in progress.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn start_recording(&self) {
+        *self.recorder.borrow_mut() = Some(Recorder {
+            start: Instant::now(),
+            trace: Trace::default(),
+        });
+    }
+
+    /// Determines if a recording is in progress.
+    pub fn is_recording(&self) -> bool {
+        self.recorder.borrow().is_some()
+    }
+
+    /// Stops recording user interaction, returning the recorded trace, or `None` if no recording
+    /// was in progress.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn stop_recording(&self) -> Option<Trace> {
+        self.recorder.borrow_mut().take().map(|recorder| recorder.trace)
+    }
+
+    /// Replays a trace, calling `on_finished` once it has been replayed or has diverged.
+    ///
+    /// The replay is driven by timers, so it only progresses while the UI is running, e.g., during
+    /// [`Ui::run`]. The controls named by the trace must have been given their IDs by then.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn replay<'ui>(
+        &'ui self,
+        trace: Trace,
+        on_finished: impl 'ui + FnOnce(Result<(), ReplayError>),
+    ) {
+        let replay = self.alloc_object(Replay {
+            ui: self,
+            trace,
+            next: 0,
+            on_finished: Some(Box::new(on_finished)),
+        });
+        replay.schedule();
+    }
+}
+
+/// An in-progress recording.
+pub(crate) struct Recorder {
+    start: Instant,
+    trace: Trace,
+}
+
+/// A widget that may be the source of recorded events.
+pub(crate) trait Source {
+    /// Records an event on this widget, returning whether or not it was recorded.
+    fn record_event(&self, ui: &Ui, callback: &'static str) -> bool;
+}
+
+impl<'ui, T: Subcontrol<'ui>> Source for T {
+    fn record_event(&self, ui: &Ui, callback: &'static str) -> bool {
+        let mut recorder = ui.recorder.borrow_mut();
+        let recorder = match recorder.as_mut() {
+            Some(recorder) => recorder,
+            None => return false,
+        };
+
+        let event = match Event::from_callback(callback) {
+            Some(event) => event,
+            None => return false,
+        };
+        let id = match id_of(ui, self) {
+            Some(id) => id,
+            None => {
+                tracing::debug!("Not recording `{}` of control without an ID", callback);
+                return false;
+            }
+        };
+
+        // Traces are saved with millisecond precision, so they are recorded that way too.
+        let elapsed = recorder.start.elapsed().as_millis();
+        recorder.trace.events.push(TraceEvent {
+            time: Duration::from_millis(u64::try_from(elapsed).unwrap_or(u64::MAX)),
+            id,
+            event,
+            value: Value::of(self),
+            checks: Vec::new(),
+        });
+
+        true
+    }
+}
+
+impl Source for MenuItem<'_> {
+    fn record_event(&self, _: &Ui, _: &'static str) -> bool {
+        false
+    }
+}
+
+/// Records the state of all controls with IDs after the most recent event.
+pub(crate) fn record_checks(ui: &Ui) {
+    let checks = checks(ui);
+    if let Some(event) = ui
+        .recorder
+        .borrow_mut()
+        .as_mut()
+        .and_then(|recorder| recorder.trace.events.last_mut())
+    {
+        event.checks = checks;
+    }
+}
+
+/// The ID of the given control.
+fn id_of(ui: &Ui, control: &Control) -> Option<String> {
+    ui.registry.id_of(control.as_ptr())
+}
+
+/// The state of all controls with IDs, sorted by ID.
+fn checks(ui: &Ui) -> Vec<Check> {
+    let mut checks: Vec<Check> = ui
+        .controls_with_ids()
+        .into_iter()
+        .filter_map(|(id, control)| {
+            let value = Value::of(control);
+
+            (value != Value::None).then_some(Check { id, value })
+        })
+        .collect();
+    checks.sort_by(|a, b| a.id.cmp(&b.id));
+
+    checks
+}
+
+/// An in-progress replay.
+struct Replay<'ui> {
+    ui: &'ui Ui,
+    trace: Trace,
+    /// The index of the next event to replay.
+    next: usize,
+    on_finished: Option<OnFinished<'ui>>,
+}
+
+type OnFinished<'ui> = Box<dyn 'ui + FnOnce(Result<(), ReplayError>)>;
+
+impl Replay<'_> {
+    /// Schedules the next event.
+    fn schedule(&mut self) {
+        unsafe extern "C" fn tick(data: *mut c_void) -> c_int {
+            let replay: &mut Replay = &mut *data.cast();
+            replay.tick();
+
+            // Each event schedules its own timer, as events aren't evenly spaced.
+            0
+        }
+
+        let delay = self.delay();
+        unsafe { uiTimer(delay, Some(tick), (self as *mut Self).cast()) };
+    }
+
+    /// The delay, in milliseconds, before the next event.
+    fn delay(&self) -> c_int {
+        let previous = match self.next {
+            0 => Duration::ZERO,
+            next => self.trace.events[next - 1].time,
+        };
+        let delay = match self.trace.events.get(self.next) {
+            Some(event) => event.time.saturating_sub(previous),
+            None => Duration::ZERO,
+        };
+
+        c_int::try_from(delay.as_millis()).unwrap_or(c_int::MAX)
+    }
+
+    fn tick(&mut self) {
+        let result = match self.trace.events.get(self.next) {
+            Some(event) => replay_event(self.ui, self.next, event),
+            None => {
+                self.finish(Ok(()));
+                return;
+            }
+        };
+
+        match result {
+            Ok(()) => {
+                self.next += 1;
+                if self.next < self.trace.events.len() {
+                    self.schedule();
+                } else {
+                    self.finish(Ok(()));
+                }
+            }
+            Err(e) => self.finish(Err(e)),
+        }
+    }
+
+    fn finish(&mut self, result: Result<(), ReplayError>) {
+        if let Some(on_finished) = self.on_finished.take() {
+            on_finished(result);
+        }
+    }
+}
+
+fn replay_event(ui: &Ui, index: usize, event: &TraceEvent) -> Result<(), ReplayError> {
+    let control = match ui.find_control(&event.id) {
+        Some(control) => control,
+        None => {
+            return Err(ReplayError::MissingControl {
+                index,
+                id: event.id.clone(),
+            })
+        }
+    };
+
+    let unsupported = || ReplayError::Unsupported {
+        index,
+        id: event.id.clone(),
+        event: event.event,
+    };
+    let failed = |error| ReplayError::Failed { index, error };
+
+    match (event.event, &event.value) {
+        (Event::Clicked, _) => downcast::<Pushbutton>(control)
+            .ok_or_else(unsupported)?
+            .simulate_click(),
+        (Event::Toggled, &Value::Bool(value)) => {
+            let checkbox = downcast::<Checkbox>(control).ok_or_else(unsupported)?;
+            // Toggling flips the checkbox, so start from the opposite of the recorded state.
+            checkbox.set_checked(!value);
+            checkbox.simulate_toggle();
+        }
+        (Event::Changed, Value::Text(text)) => {
+            if let Some(entry) = control.downcast_ref::<TextEntry>() {
+                entry.simulate_typing(text).map_err(failed)?;
+            } else if let Some(entry) = control.downcast_ref::<MultilineTextEntry>() {
+                entry.simulate_typing(text).map_err(failed)?;
+            } else {
+                downcast::<EditableCombobox>(control)
+                    .ok_or_else(unsupported)?
+                    .simulate_typing(text)
+                    .map_err(failed)?;
+            }
+        }
+        (Event::Changed, &Value::Int(value)) => {
+            if let Some(slider) = control.downcast_ref::<Slider>() {
+                slider.simulate_slide(value);
+            } else {
+                downcast::<Spinbox>(control)
+                    .ok_or_else(unsupported)?
+                    .simulate_change(value);
+            }
+        }
+        (Event::Changed, &Value::DateTime(value)) => downcast::<DateTimePicker>(control)
+            .ok_or_else(unsupported)?
+            .simulate_change(value),
+        (Event::Released, _) => downcast::<Slider>(control)
+            .ok_or_else(unsupported)?
+            .simulate_release(),
+        (Event::ItemSelected, &Value::Index(index)) => {
+            if let Some(combobox) = control.downcast_ref::<Combobox>() {
+                combobox.simulate_selection(index);
+            } else {
+                downcast::<RadioButtons>(control)
+                    .ok_or_else(unsupported)?
+                    .simulate_selection(index);
+            }
+        }
+        (Event::PageSelected, &Value::Index(Some(index))) => downcast::<Tab>(control)
+            .ok_or_else(unsupported)?
+            .simulate_page_selection(index),
+        (Event::Selected, &Value::Color(color)) => downcast::<color::Picker>(control)
+            .ok_or_else(unsupported)?
+            .simulate_selection(color),
+        (Event::Selected, Value::Font(font)) => {
+            let picker = downcast::<FontPicker>(control).ok_or_else(unsupported)?;
+            #[cfg(feature = "mock")]
+            crate::mock::select_font(picker, font);
+            // *libui-ng* can't select a font on its own, so a replay with a real backend only
+            // succeeds if the recorded font is already selected.
+            let actual = picker.selected_font().map_err(failed)?;
+            if actual != *font {
+                return Err(ReplayError::Mismatch {
+                    index,
+                    id: event.id.clone(),
+                    expected: event.value.clone(),
+                    actual: Value::Font(actual),
+                });
+            }
+            picker.simulate_selection();
+        }
+        (Event::Selected, _) => downcast::<FontPicker>(control)
+            .ok_or_else(unsupported)?
+            .simulate_selection(),
+        (Event::Resized, &Value::Size(width, height)) => downcast::<Window>(control)
+            .ok_or_else(unsupported)?
+            .simulate_resize(width, height),
+        (Event::Closing, _) => downcast::<Window>(control)
+            .ok_or_else(unsupported)?
+            .simulate_close(),
+        _ => return Err(unsupported()),
+    }
+
+    for expected in &event.checks {
+        let actual = match ui.find_control(&expected.id) {
+            Some(control) => Value::of(control),
+            None => {
+                return Err(ReplayError::MissingControl {
+                    index,
+                    id: expected.id.clone(),
+                })
+            }
+        };
+        if actual != expected.value {
+            return Err(ReplayError::Mismatch {
+                index,
+                id: expected.id.clone(),
+                expected: expected.value.clone(),
+                actual,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn downcast<'a, 'ui, T: Subcontrol<'ui>>(control: &'a Control<'ui>) -> Option<&'a T> {
+    control.downcast_ref()
+}
+
+/// A recorded session.
+///
+/// A trace is saved as text, with one line per event followed by one line per check:
+///
+/// ```text
+/// boing-trace 1
+/// event 1520 "name" changed text "Ferris"
+/// check "greeting" text "Hello, Ferris!"
+/// check "name" text "Ferris"
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Trace {
+    pub events: Vec<TraceEvent>,
+}
+
+impl Trace {
+    /// Loads a trace from a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LoadTraceError> {
+        let text = fs::read_to_string(path).map_err(LoadTraceError::Io)?;
+
+        text.parse().map_err(LoadTraceError::Parse)
+    }
+
+    /// Saves this trace to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+        for event in &self.events {
+            writeln!(
+                f,
+                "event {} {:?} {} {}",
+                event.time.as_millis(),
+                event.id,
+                event.event,
+                event.value,
+            )?;
+            for check in &event.checks {
+                writeln!(f, "check {:?} {}", check.id, check.value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Trace {
+    type Err = ParseTraceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate().map(|(i, line)| (i + 1, line));
+        match lines.next() {
+            Some((_, HEADER)) => {}
+            _ => return Err(ParseTraceError { line: 1 }),
+        }
+
+        let mut trace = Trace::default();
+        for (number, line) in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let error = ParseTraceError { line: number };
+            let mut tokens = Tokens(line);
+            match tokens.word().ok_or(error)? {
+                "event" => {
+                    let time = tokens.word().and_then(|t| t.parse().ok()).ok_or(error)?;
+                    let id = tokens.string().ok_or(error)?;
+                    let event = tokens.word().and_then(|e| e.parse().ok()).ok_or(error)?;
+                    let value = tokens.value().ok_or(error)?;
+                    trace.events.push(TraceEvent {
+                        time: Duration::from_millis(time),
+                        id,
+                        event,
+                        value,
+                        checks: Vec::new(),
+                    });
+                }
+                "check" => {
+                    let id = tokens.string().ok_or(error)?;
+                    let value = tokens.value().ok_or(error)?;
+                    trace
+                        .events
+                        .last_mut()
+                        .ok_or(error)?
+                        .checks
+                        .push(Check { id, value });
+                }
+                _ => return Err(error),
+            }
+            if !tokens.0.trim().is_empty() {
+                return Err(error);
+            }
+        }
+
+        Ok(trace)
+    }
+}
+
+/// A tokenizer for the lines of a trace.
+struct Tokens<'a>(&'a str);
+
+impl<'a> Tokens<'a> {
+    fn word(&mut self) -> Option<&'a str> {
+        let rest = self.0.trim_start();
+        let end = rest.find(' ').unwrap_or(rest.len());
+        let (word, rest) = rest.split_at(end);
+        self.0 = rest;
+
+        (!word.is_empty()).then_some(word)
+    }
+
+    /// Reads a string quoted and escaped as by `{:?}`.
+    fn string(&mut self) -> Option<String> {
+        let mut chars = self.0.trim_start().strip_prefix('"')?.char_indices();
+        let mut string = String::new();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.0 = &self.0.trim_start()[i + 2..];
+                    return Some(string);
+                }
+                '\\' => string.push(match chars.next()?.1 {
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    '0' => '\0',
+                    '\\' => '\\',
+                    '\'' => '\'',
+                    '"' => '"',
+                    'u' => {
+                        let rest = chars.as_str().strip_prefix('{')?;
+                        let end = rest.find('}')?;
+                        let c = u32::from_str_radix(&rest[..end], 16).ok()?;
+                        // Skip over the braces and digits.
+                        for _ in 0..end + 2 {
+                            chars.next();
+                        }
+                        char::from_u32(c)?
+                    }
+                    _ => return None,
+                }),
+                c => string.push(c),
+            }
+        }
+
+        None
+    }
+
+    fn value(&mut self) -> Option<Value> {
+        let value = match self.word()? {
+            "none" => Value::None,
+            "bool" => Value::Bool(self.word()?.parse().ok()?),
+            "int" => Value::Int(self.word()?.parse().ok()?),
+            "index" => Value::Index(match self.word()? {
+                "none" => None,
+                index => Some(index.parse().ok()?),
+            }),
+            "text" => Value::Text(self.string()?),
+            "color" => Value::Color(Color::from_hex(self.word()?).ok()?),
+            "datetime" => Value::DateTime(parse_date_time(self.word()?)?),
+            "font" => Value::Font(Font {
+                family: self.string()?,
+                size: self.word()?.parse().ok()?,
+                weight: self.word()?.parse().ok()?,
+                italic_kind: ItalicKind::try_from_desc(self.word()?.parse().ok()?).ok()?,
+                stretch: StretchKind::try_from_desc(self.word()?.parse().ok()?).ok()?,
+            }),
+            "size" => {
+                let (width, height) = self.word()?.split_once('x')?;
+                Value::Size(width.parse().ok()?, height.parse().ok()?)
+            }
+            _ => return None,
+        };
+
+        Some(value)
+    }
+}
+
+/// Parses a date and time as formatted by [`DateTime`]'s [`fmt::Display`] implementation.
+fn parse_date_time(s: &str) -> Option<DateTime> {
+    let (date, time) = s.split_once('T')?;
+    let mut date = date.rsplitn(3, '-');
+    let day = date.next()?.parse().ok()?;
+    let month = date.next()?.parse().ok()?;
+    let year = date.next()?.parse().ok()?;
+    let mut time = time.splitn(3, ':');
+    let hour = time.next()?.parse().ok()?;
+    let minute = time.next()?.parse().ok()?;
+    let second = time.next()?.parse().ok()?;
+
+    DateTime::new(year, month, day, hour, minute, second)
+}
+
+/// An event in a [`Trace`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TraceEvent {
+    /// The time of the event, relative to the start of the recording.
+    pub time: Duration,
+    /// The ID of the control that received the event.
+    pub id: String,
+    pub event: Event,
+    /// The state of the control as the user left it, before any callbacks ran.
+    pub value: Value,
+    /// The state of all controls with IDs after the callbacks ran.
+    pub checks: Vec<Check>,
+}
+
+/// The recorded state of a control.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Check {
+    pub id: String,
+    pub value: Value,
+}
+
+/// The kind of a recorded event.
+///
+/// Each kind corresponds to the callback that the event triggers, e.g., [`Event::Clicked`]
+/// corresponds to [`Pushbutton::on_clicked`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Event {
+    Clicked,
+    Toggled,
+    Changed,
+    Released,
+    ItemSelected,
+    PageSelected,
+    Selected,
+    Resized,
+    Closing,
+}
+
+impl Event {
+    const NAMES: [(Self, &'static str); 9] = [
+        (Self::Clicked, "clicked"),
+        (Self::Toggled, "toggled"),
+        (Self::Changed, "changed"),
+        (Self::Released, "released"),
+        (Self::ItemSelected, "item_selected"),
+        (Self::PageSelected, "page_selected"),
+        (Self::Selected, "selected"),
+        (Self::Resized, "content_size_changed"),
+        (Self::Closing, "closing"),
+    ];
+
+    /// The event triggering the callback set by the method of the given name, e.g., `on_clicked`.
+    fn from_callback(name: &str) -> Option<Self> {
+        name.strip_prefix("on_")?.parse().ok()
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (_, name) = Self::NAMES.iter().find(|(event, _)| event == self).unwrap();
+
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Event {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::NAMES
+            .iter()
+            .find(|(_, name)| *name == s)
+            .map(|(event, _)| *event)
+            .ok_or(())
+    }
+}
+
+/// The state of a control.
+///
+/// Controls without any state worth recording, like [`Pushbutton`]s, have the state
+/// [`Value::None`].
+///
+/// Colors are compared at the 8-bit precision they are saved with, so that a color read back from
+/// a saved trace equals the one that was recorded.
+#[derive(Clone, Debug)]
+pub enum Value {
+    None,
+    /// Whether or not a [`Checkbox`] is checked.
+    Bool(bool),
+    /// The value of a [`Slider`] or [`Spinbox`].
+    Int(i32),
+    /// The selected item of a [`Combobox`] or [`RadioButtons`], or the selected page of a [`Tab`].
+    Index(Option<u16>),
+    /// The text of a [`TextEntry`], [`MultilineTextEntry`], [`EditableCombobox`], or [`Label`].
+    Text(String),
+    /// The color of a color picker.
+    Color(Color),
+    /// The font of a [`FontPicker`].
+    Font(Font),
+    /// The date and time of a [`DateTimePicker`].
+    DateTime(DateTime),
+    /// The content size of a [`Window`].
+    Size(u16, u16),
+}
+
+impl Value {
+    /// The current state of the given control.
+    fn of(control: &Control) -> Self {
+        if let Some(checkbox) = control.downcast_ref::<Checkbox>() {
+            Self::Bool(checkbox.checked())
+        } else if let Some(slider) = control.downcast_ref::<Slider>() {
+            Self::Int(slider.value())
+        } else if let Some(spinbox) = control.downcast_ref::<Spinbox>() {
+            Self::Int(spinbox.value())
+        } else if let Some(combobox) = control.downcast_ref::<Combobox>() {
+            Self::Index(combobox.selected_item())
+        } else if let Some(buttons) = control.downcast_ref::<RadioButtons>() {
+            Self::Index(buttons.selected_item())
+        } else if let Some(tab) = control.downcast_ref::<Tab>() {
+            Self::Index(tab.selected_page())
+        } else if let Some(entry) = control.downcast_ref::<TextEntry>() {
+            Self::Text(entry.text())
+        } else if let Some(entry) = control.downcast_ref::<MultilineTextEntry>() {
+            Self::Text(entry.text())
+        } else if let Some(combobox) = control.downcast_ref::<EditableCombobox>() {
+            Self::Text(combobox.text())
+        } else if let Some(label) = control.downcast_ref::<Label>() {
+            Self::Text(label.text())
+        } else if let Some(picker) = control.downcast_ref::<color::Picker>() {
+            Self::Color(picker.selected_color())
+        } else if let Some(picker) = control.downcast_ref::<FontPicker>() {
+            // A font that can't be read is recorded without a value, and replayed as it was.
+            picker.selected_font().map_or(Self::None, Self::Font)
+        } else if let Some(picker) = control.downcast_ref::<DateTimePicker>() {
+            Self::DateTime(picker.time())
+        } else if let Some(window) = control.downcast_ref::<Window>() {
+            let (width, height) = window.content_size();
+            Self::Size(width, height)
+        } else {
+            Self::None
+        }
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::None, Self::None) => true,
+            (Self::Bool(a), Self::Bool(b)) => a == b,
+            (Self::Int(a), Self::Int(b)) => a == b,
+            (Self::Index(a), Self::Index(b)) => a == b,
+            (Self::Text(a), Self::Text(b)) => a == b,
+            (Self::Color(a), Self::Color(b)) => a.to_rgba8() == b.to_rgba8(),
+            (Self::Font(a), Self::Font(b)) => a == b,
+            (Self::DateTime(a), Self::DateTime(b)) => a == b,
+            (Self::Size(a, b), Self::Size(c, d)) => (a, b) == (c, d),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Bool(value) => write!(f, "bool {}", value),
+            Self::Int(value) => write!(f, "int {}", value),
+            Self::Index(Some(index)) => write!(f, "index {}", index),
+            Self::Index(None) => write!(f, "index none"),
+            Self::Text(text) => write!(f, "text {:?}", text),
+            Self::Color(color) => write!(f, "color {}", color.to_hex()),
+            Self::Font(font) => write!(
+                f,
+                "font {:?} {} {} {} {}",
+                font.family,
+                font.size,
+                font.weight,
+                ItalicKind::into_desc(font.italic_kind),
+                font.stretch.into_desc(),
+            ),
+            Self::DateTime(value) => write!(f, "datetime {}", value),
+            Self::Size(width, height) => write!(f, "size {}x{}", width, height),
+        }
+    }
+}
+
+/// The error returned by [`Trace::from_str`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ParseTraceError {
+    /// The one-based number of the line that failed to parse.
+    pub line: usize,
+}
+
+impl fmt::Display for ParseTraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid trace at line {}", self.line)
+    }
+}
+
+impl std::error::Error for ParseTraceError {}
+
+/// The error returned by [`Trace::load`].
+#[derive(Debug)]
+pub enum LoadTraceError {
+    Io(io::Error),
+    Parse(ParseTraceError),
+}
+
+impl fmt::Display for LoadTraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "failed to read trace: {}", e),
+            Self::Parse(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for LoadTraceError {}
+
+/// The reason a replay diverged from its [`Trace`].
+///
+/// `index` is the index of the offending event in [`Trace::events`].
+#[derive(Debug)]
+pub enum ReplayError {
+    /// No control has the given ID.
+    MissingControl { index: usize, id: String },
+    /// The control with the given ID cannot receive the event.
+    Unsupported { index: usize, id: String, event: Event },
+    /// The event could not be simulated.
+    Failed { index: usize, error: crate::Error },
+    /// The state of a control differs from the recorded state.
+    Mismatch {
+        index: usize,
+        id: String,
+        expected: Value,
+        actual: Value,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingControl { index, id } => {
+                write!(f, "event {}: no control has ID {:?}", index, id)
+            }
+            Self::Unsupported { index, id, event } => {
+                write!(f, "event {}: control {:?} cannot receive `{}`", index, id, event)
+            }
+            Self::Failed { index, error } => write!(f, "event {}: {}", index, error),
+            Self::Mismatch {
+                index,
+                id,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "event {}: expected control {:?} to have {}, but it has {}",
+                    index, id, expected, actual,
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
@@ -0,0 +1,762 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Forms generated from structs.
+//!
+//! A struct that implements [`Settings`] can be edited in a [`SettingsForm`], which shows one
+//! labeled widget per field, and reads the struct back from the widgets. With feature `derive`,
+//! [`Settings`] may be derived, and each field is given a widget according to its type:
+//!
+//! | Type                           | Widget                                              |
+//! |--------------------------------|-----------------------------------------------------|
+//! | `bool`                         | [`Checkbox`]                                        |
+//! | Integers                       | [`Spinbox`], or [`Slider`] with `slider`            |
+//! | `String`                       | [`TextEntry`], or [`MultilineTextEntry`] with `multiline` |
+//! | [`Color`]                      | [color `Picker`](crate::color::Picker)              |
+//! | Enums that derive [`Settings`] | [`Combobox`], or [`RadioButtons`] with `radio`      |
+//!
+//! Other types may be supported by implementing [`Field`] for them.
+//!
+//! # Attributes
+//!
+//! The derived form is configured with `#[settings(...)]` attributes:
+//!
+//! - On a struct, `title = "..."` places the form in a [`Group`] with the given title.
+//! - On a field:
+//!   - `label = "..."` sets the label of the field, which is otherwise derived from its name;
+//!   - `min = ...` and `max = ...` set the range of integers, which is also limited to that of
+//!     `i32`, though a value outside of the range is kept unless the user changes it;
+//!   - `slider`, `radio`, `multiline`, and `password` choose another widget (see [`Style`]); and
+//!   - `skip` leaves the field out of the form, so that it keeps the value it was given.
+//! - On a variant of an enum, `label = "..."` sets the text shown for the variant, which is
+//!   otherwise its name.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> Result<(), boing::Error> {
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use boing::{Color, Settings, Ui};
+//!
+//! #[derive(Clone, Settings)]
+//! #[settings(title = "Editor")]
+//! struct Preferences {
+//!     #[settings(label = "Font size", min = 6, max = 72)]
+//!     font_size: u32,
+//!     #[settings(label = "Show line numbers")]
+//!     shows_line_numbers: bool,
+//!     #[settings(radio)]
+//!     theme: Theme,
+//!     accent: Color,
+//!     #[settings(skip)]
+//!     recent_files: Vec<String>,
+//! }
+//!
+//! #[derive(Clone, Settings)]
+//! enum Theme {
+//!     Light,
+//!     Dark,
+//!     #[settings(label = "Match system")]
+//!     System,
+//! }
+//!
+//! let prefs = Preferences {
+//!     font_size: 12,
+//!     shows_line_numbers: true,
+//!     theme: Theme::System,
+//!     accent: Color::rgb(0.2, 0.4, 0.8),
+//!     recent_files: Vec::new(),
+//! };
+//!
+//! let ui = Ui::new()?;
+//! let window = ui.create_window("Preferences", 320, 240, false, true)?;
+//! let form = ui.create_settings_form(&prefs)?;
+//! form.on_changed(|prefs| println!("Font size: {}", prefs.font_size));
+//! window.set_child(form);
+//! window.show();
+//! ui.run();
+//! # }
+//! #
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Checkbox`]: crate::Checkbox
+//! [`Spinbox`]: crate::Spinbox
+//! [`Slider`]: crate::Slider
+//! [`TextEntry`]: crate::TextEntry
+//! [`MultilineTextEntry`]: crate::MultilineTextEntry
+//! [`Color`]: crate::Color
+//! [`Combobox`]: crate::Combobox
+//! [`RadioButtons`]: crate::RadioButtons
+//! [`Group`]: crate::Group
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+};
+
+use crate::{callback::Observers, color::Picker as ColorPicker, prelude::*, Color, Form};
+
+impl Ui {
+    /// Creates a new [`SettingsForm`] that initially shows the given value.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_settings_form<'ui, S: Settings>(
+        &'ui self,
+        value: &S,
+    ) -> Result<&'ui SettingsForm<'ui, S>, crate::Error> {
+        let form = self.create_form()?;
+        form.set_padded(true);
+
+        let state = Rc::new(FormState {
+            base: RefCell::new(value.clone()),
+            bindings: RefCell::default(),
+            observers: Observers::default(),
+        });
+        let weak = Rc::downgrade(&state);
+        let mut builder = Builder {
+            ui: self,
+            form,
+            value,
+            bindings: Vec::new(),
+            changed: Notifier::new(move || notify(&weak)),
+        };
+        S::build(&mut builder)?;
+        *state.bindings.borrow_mut() = builder.bindings;
+
+        let control: &'ui Control = match S::title() {
+            Some(title) => {
+                let group = self.create_group(title)?;
+                group.set_margined(true);
+                group.set_child(form);
+
+                group
+            }
+            None => form,
+        };
+
+        Ok(self.alloc_object(SettingsForm { control, state }))
+    }
+}
+
+/// A struct that may be edited in a [`SettingsForm`].
+///
+/// This trait is usually derived; see the [module documentation](crate::settings).
+pub trait Settings: 'static + Clone {
+    /// The title of the [`Group`](crate::Group) that the form is placed in, if any.
+    ///
+    /// By default, the form isn't placed in a group.
+    fn title() -> Option<&'static str> {
+        None
+    }
+
+    /// Adds the fields of this struct to a form, with [`Builder::field`].
+    fn build(builder: &mut Builder<'_, '_, Self>) -> Result<(), crate::Error>;
+}
+
+/// Adds fields to a [`SettingsForm`] being created.
+pub struct Builder<'a, 'ui, S> {
+    ui: &'ui Ui,
+    form: &'a Form<'ui>,
+    value: &'a S,
+    bindings: Vec<Binding<'ui, S>>,
+    changed: Notifier<'ui>,
+}
+
+impl<S: 'static> Builder<'_, '_, S> {
+    /// Adds a field with the given label, which is accessed with `get` and `get_mut`.
+    pub fn field<T: Field>(
+        &mut self,
+        label: &str,
+        options: &Options,
+        get: fn(&S) -> &T,
+        get_mut: fn(&mut S) -> &mut T,
+    ) -> Result<(), crate::Error> {
+        let widget = T::create_widget(self.ui, get(self.value), options, self.changed.clone())?;
+        self.form.append(label, widget.control, false)?;
+
+        let Widget {
+            get: read, set: write, ..
+        } = widget;
+        self.bindings.push(Binding {
+            read: Box::new(move |value| *get_mut(value) = read()),
+            write: Box::new(move |value| write(get(value))),
+        });
+
+        Ok(())
+    }
+}
+
+/// Reads a field from its widget into a struct, and writes it back.
+struct Binding<'ui, S> {
+    read: Box<dyn 'ui + Fn(&mut S)>,
+    write: Box<dyn 'ui + Fn(&S)>,
+}
+
+/// A form that edits a struct.
+///
+/// See the [module documentation](crate::settings).
+pub struct SettingsForm<'ui, S> {
+    control: &'ui Control<'ui>,
+    state: Rc<FormState<'ui, S>>,
+}
+
+struct FormState<'ui, S> {
+    /// The last value given to the form, which provides the fields that aren't shown.
+    base: RefCell<S>,
+    bindings: RefCell<Vec<Binding<'ui, S>>>,
+    observers: Observers<dyn 'ui + FnMut(&S)>,
+}
+
+impl<S: Clone> FormState<'_, S> {
+    fn value(&self) -> S {
+        let mut value = self.base.borrow().clone();
+        for binding in self.bindings.borrow().iter() {
+            (binding.read)(&mut value);
+        }
+
+        value
+    }
+}
+
+fn notify<S: Clone>(state: &Weak<FormState<'_, S>>) {
+    let Some(state) = state.upgrade() else {
+        return;
+    };
+    let value = state.value();
+    state.observers.notify(|observer| observer(&value));
+}
+
+impl<'ui, S: Settings> SettingsForm<'ui, S> {
+    /// The value shown by this form.
+    pub fn value(&self) -> S {
+        self.state.value()
+    }
+
+    /// Shows the given value in this form.
+    ///
+    /// Like setting widgets programmatically, this doesn't call the callbacks set by
+    /// [`SettingsForm::on_changed`].
+    pub fn set_value(&self, value: &S) {
+        *self.state.base.borrow_mut() = value.clone();
+        for binding in self.state.bindings.borrow().iter() {
+            (binding.write)(value);
+        }
+    }
+
+    /// Registers a callback for when the user changes any field, which is given the new value.
+    pub fn on_changed(&self, f: impl 'ui + FnMut(&S)) {
+        self.state.observers.push(Box::new(f));
+    }
+}
+
+impl<'ui, S> std::ops::Deref for SettingsForm<'ui, S> {
+    type Target = Control<'ui>;
+
+    fn deref(&self) -> &Self::Target {
+        self.control
+    }
+}
+
+/// How a field is shown, as configured by its `#[settings(...)]` attribute.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Options {
+    /// The least value of an integer.
+    pub min: Option<i64>,
+    /// The greatest value of an integer.
+    ///
+    /// If this is less than [`Options::min`], the two are swapped.
+    pub max: Option<i64>,
+    pub style: Style,
+}
+
+impl Options {
+    /// The range of an integer, with the bounds swapped if `min` is greater than `max`.
+    fn range(&self) -> (i128, i128) {
+        let min = self.min.map_or(i128::MIN, i128::from);
+        let max = self.max.map_or(i128::MAX, i128::from);
+
+        (min.min(max), min.max(max))
+    }
+}
+
+/// An alternative widget for a field.
+///
+/// Styles that don't apply to the type of a field are ignored.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Style {
+    /// The usual widget for the type.
+    #[default]
+    Default,
+    /// A [`Slider`](crate::Slider) for an integer.
+    Slider,
+    /// [`RadioButtons`](crate::RadioButtons) for an enum.
+    Radio,
+    /// A [`MultilineTextEntry`](crate::MultilineTextEntry) for a string.
+    Multiline,
+    /// A password [`TextEntry`](crate::TextEntry) for a string.
+    Password,
+}
+
+/// A type that may be a field of [`Settings`].
+pub trait Field: 'static + Clone {
+    /// Creates a widget that initially shows the given value.
+    ///
+    /// The widget must call [`Notifier::notify`] on `changed` whenever the user changes it.
+    fn create_widget<'ui>(
+        ui: &'ui Ui,
+        value: &Self,
+        options: &Options,
+        changed: Notifier<'ui>,
+    ) -> Result<Widget<'ui, Self>, crate::Error>;
+}
+
+/// A widget created by [`Field::create_widget`], and functions that get and set the value it
+/// shows.
+pub struct Widget<'ui, T> {
+    pub control: &'ui Control<'ui>,
+    pub get: Box<dyn 'ui + Fn() -> T>,
+    pub set: Box<dyn 'ui + Fn(&T)>,
+}
+
+/// Reports that the user changed a field of a [`SettingsForm`].
+#[derive(Clone)]
+pub struct Notifier<'ui> {
+    f: Rc<dyn 'ui + Fn()>,
+}
+
+impl<'ui> Notifier<'ui> {
+    fn new(f: impl 'ui + Fn()) -> Self {
+        Self { f: Rc::new(f) }
+    }
+
+    /// Calls the callbacks set by [`SettingsForm::on_changed`].
+    pub fn notify(&self) {
+        (self.f)();
+    }
+}
+
+impl Field for bool {
+    fn create_widget<'ui>(
+        ui: &'ui Ui,
+        value: &Self,
+        _: &Options,
+        changed: Notifier<'ui>,
+    ) -> Result<Widget<'ui, Self>, crate::Error> {
+        let checkbox = ui.create_checkbox("")?;
+        checkbox.set_checked(*value);
+        checkbox.on_toggled(move |_| changed.notify());
+
+        let checkbox: &'ui _ = checkbox;
+        Ok(Widget {
+            control: checkbox,
+            get: Box::new(move || checkbox.checked()),
+            set: Box::new(move |value| checkbox.set_checked(*value)),
+        })
+    }
+}
+
+macro_rules! impl_integer_field {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Field for $ty {
+                fn create_widget<'ui>(
+                    ui: &'ui Ui,
+                    value: &Self,
+                    options: &Options,
+                    changed: Notifier<'ui>,
+                ) -> Result<Widget<'ui, Self>, crate::Error> {
+                    // Spinboxes and sliders hold `i32`s, so the range is limited to values that
+                    // both `i32` and this type can represent.
+                    let clamp = |bound: i128| {
+                        bound.clamp(
+                            (<$ty>::MIN as i128).max(i32::MIN.into()),
+                            (<$ty>::MAX as i128).min(i32::MAX.into()),
+                        ) as i32
+                    };
+                    let (min, max) = options.range();
+                    let (min, max) = (clamp(min), clamp(max));
+                    let to_i32 = move |value: &$ty| {
+                        (*value as i128).clamp(min.into(), max.into()) as i32
+                    };
+
+                    integer_widget(
+                        ui,
+                        options.style,
+                        (min, max),
+                        *value,
+                        changed,
+                        move |value| value as $ty,
+                        to_i32,
+                    )
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_field!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// Creates the widget of an integer field, given its range as `i32`s and its initial value, and
+/// functions that convert between `i32` and the type of the field.
+///
+/// The widget can only show values within its range, so a value outside of it is shown clamped.
+/// Such a value is still read back as it was given unless the user changes the widget, so that
+/// merely opening a form doesn't change it.
+fn integer_widget<'ui, T: 'ui + Copy>(
+    ui: &'ui Ui,
+    style: Style,
+    (min, max): (i32, i32),
+    value: T,
+    changed: Notifier<'ui>,
+    from_i32: impl 'ui + Copy + Fn(i32) -> T,
+    to_i32: impl 'ui + Copy + Fn(&T) -> i32,
+) -> Result<Widget<'ui, T>, crate::Error> {
+    // The last value given to the widget, and how it is shown.
+    let given = Rc::new(Cell::new((value, to_i32(&value))));
+    let read = {
+        let given = Rc::clone(&given);
+        move |shown: i32| match given.get() {
+            (value, unchanged) if shown == unchanged => value,
+            _ => from_i32(shown),
+        }
+    };
+    let write = move |value: &T| {
+        given.set((*value, to_i32(value)));
+
+        to_i32(value)
+    };
+
+    if style == Style::Slider {
+        let slider = ui.create_slider(min, max)?;
+        slider.set_value(write(&value));
+        slider.on_changed(move |_| changed.notify());
+
+        let slider: &'ui _ = slider;
+        Ok(Widget {
+            control: slider,
+            get: Box::new(move || read(slider.value())),
+            set: Box::new(move |value| slider.set_value(write(value))),
+        })
+    } else {
+        let spinbox = ui.create_spinbox(min, max)?;
+        spinbox.set_value(write(&value));
+        spinbox.on_changed(move |_| changed.notify());
+
+        let spinbox: &'ui _ = spinbox;
+        Ok(Widget {
+            control: spinbox,
+            get: Box::new(move || read(spinbox.value())),
+            set: Box::new(move |value| spinbox.set_value(write(value))),
+        })
+    }
+}
+
+impl Field for String {
+    fn create_widget<'ui>(
+        ui: &'ui Ui,
+        value: &Self,
+        options: &Options,
+        changed: Notifier<'ui>,
+    ) -> Result<Widget<'ui, Self>, crate::Error> {
+        macro_rules! entry_widget {
+            ($entry:expr) => {{
+                let entry = $entry;
+                entry.set_text(value)?;
+                entry.on_changed(move |_| changed.notify());
+
+                let entry: &'ui _ = entry;
+                Ok(Widget {
+                    control: entry,
+                    get: Box::new(move || entry.text()),
+                    set: Box::new(move |value| {
+                        // Setting text can only fail if it contains a NUL byte, in which case
+                        // there's nothing sensible to display.
+                        if let Err(e) = entry.set_text(value) {
+                            tracing::warn!("Failed to set text of settings field: {}", e);
+                        }
+                    }),
+                })
+            }};
+        }
+
+        match options.style {
+            Style::Multiline => entry_widget!(ui.create_wrapping_multiline_text_entry()?),
+            Style::Password => entry_widget!(ui.create_password_text_entry()?),
+            _ => entry_widget!(ui.create_text_entry()?),
+        }
+    }
+}
+
+impl Field for Color {
+    fn create_widget<'ui>(
+        ui: &'ui Ui,
+        value: &Self,
+        _: &Options,
+        changed: Notifier<'ui>,
+    ) -> Result<Widget<'ui, Self>, crate::Error> {
+        let picker: &'ui ColorPicker = ui.create_color_picker()?;
+        picker.select_color(*value);
+        picker.on_selected(move |_| changed.notify());
+
+        let picker: &'ui _ = picker;
+        Ok(Widget {
+            control: picker,
+            get: Box::new(move || picker.selected_color()),
+            set: Box::new(move |value| picker.select_color(*value)),
+        })
+    }
+}
+
+/// An enum without fields, which may be a field of [`Settings`].
+///
+/// This trait is implemented by deriving [`Settings`] for the enum, which also implements
+/// [`Field`] with [`create_choice_widget`].
+pub trait Choice: 'static + Clone {
+    /// The text shown for each variant, in order.
+    const LABELS: &'static [&'static str];
+
+    /// The index of this variant in [`Choice::LABELS`].
+    fn index(&self) -> usize;
+
+    /// The variant at the given index in [`Choice::LABELS`].
+    fn from_index(index: usize) -> Self;
+}
+
+/// Creates the widget of a [`Choice`] field, which is a [`Combobox`](crate::Combobox), or
+/// [`RadioButtons`](crate::RadioButtons) with [`Style::Radio`].
+pub fn create_choice_widget<'ui, T: Choice>(
+    ui: &'ui Ui,
+    value: &T,
+    options: &Options,
+    changed: Notifier<'ui>,
+) -> Result<Widget<'ui, T>, crate::Error> {
+    let to_index = |value: &T| u16::try_from(value.index()).ok();
+    let from_index = |index: Option<u16>| T::from_index(index.map_or(0, usize::from));
+
+    if options.style == Style::Radio {
+        let buttons = ui.create_radio_buttons()?;
+        for label in T::LABELS {
+            buttons.push_new_item(label)?;
+        }
+        buttons.set_selected_item(to_index(value));
+        buttons.on_item_selected(move |_, _| changed.notify());
+
+        let buttons: &'ui _ = buttons;
+        Ok(Widget {
+            control: buttons,
+            get: Box::new(move || from_index(buttons.selected_item())),
+            set: Box::new(move |value| buttons.set_selected_item(to_index(value))),
+        })
+    } else {
+        let combobox = ui.create_combobox()?;
+        for label in T::LABELS {
+            combobox.push_new_item(label)?;
+        }
+        combobox.select_item(to_index(value));
+        combobox.on_item_selected(move |_, _| changed.notify());
+
+        let combobox: &'ui _ = combobox;
+        Ok(Widget {
+            control: combobox,
+            get: Box::new(move || from_index(combobox.selected_item())),
+            set: Box::new(move |value| combobox.select_item(to_index(value))),
+        })
+    }
+}
+
+/// The output of `#[derive(Settings)]`.
+///
+/// ```
+/// use boing::{mock, settings::Choice, Settings, Ui};
+///
+/// #[derive(Clone, Debug, PartialEq, Settings)]
+/// #[settings(title = "Sync")]
+/// struct Sync {
+///     is_enabled: bool,
+///     #[settings(label = "Interval (minutes)", min = 1, max = 60, slider)]
+///     interval: u8,
+///     quota: u64,
+///     offset: i64,
+///     #[settings(skip)]
+///     token: Vec<u8>,
+///     r#mode: Mode,
+/// }
+///
+/// #[derive(Clone, Debug, PartialEq, Settings)]
+/// enum Mode {
+///     Push,
+///     #[settings(label = "Pull only")]
+///     Pull,
+/// }
+///
+/// assert_eq!(Some("Sync"), <Sync as boing::settings::Settings>::title());
+/// assert_eq!(&["Push", "Pull only"], Mode::LABELS);
+/// assert_eq!(1, Mode::Pull.index());
+/// assert_eq!(Mode::Pull, Mode::from_index(1));
+/// assert_eq!(Mode::Push, Mode::from_index(2));
+///
+/// let sync = Sync {
+///     is_enabled: true,
+///     interval: 90,
+///     quota: u64::MAX,
+///     offset: i64::MIN,
+///     token: vec![1, 2, 3],
+///     mode: Mode::Pull,
+/// };
+/// let ui = Ui::new()?;
+/// let form = ui.create_settings_form(&sync)?;
+/// let group = mock::snapshot(form);
+/// assert_eq!("Sync", group.text);
+/// let labels: Vec<_> = group.children[0]
+///     .children
+///     .iter()
+///     .map(|field| field.label.as_deref().unwrap())
+///     .collect();
+/// assert_eq!(["Is enabled", "Interval (minutes)", "Quota", "Offset", "Mode"], *labels);
+///
+/// let values: Vec<_> = group.children[0].children.iter().map(|field| field.value).collect();
+/// assert_eq!([0, 60, i32::MAX, i32::MIN, 0], *values);
+///
+/// // Values that the widgets can't show are kept until the user changes them.
+/// assert_eq!(sync, form.value());
+/// let sync = Sync { quota: 7, offset: -7, ..sync };
+/// form.set_value(&sync);
+/// assert_eq!(sync, form.value());
+/// # Ok::<(), boing::Error>(())
+/// ```
+#[cfg(all(doctest, feature = "derive", feature = "mock"))]
+pub struct DeriveOutput;
+
+/// Attributes and types rejected by `#[derive(Settings)]`.
+///
+/// An unknown option.
+///
+/// ```compile_fail
+/// #[derive(Clone, boing::Settings)]
+/// struct Window {
+///     #[settings(hidden)]
+///     width: u32,
+/// }
+/// ```
+///
+/// More than one style.
+///
+/// ```compile_fail
+/// #[derive(Clone, boing::Settings)]
+/// struct Window {
+///     #[settings(slider, radio)]
+///     width: u32,
+/// }
+/// ```
+///
+/// A field option on a struct.
+///
+/// ```compile_fail
+/// #[derive(Clone, boing::Settings)]
+/// #[settings(min = 0)]
+/// struct Window {
+///     width: u32,
+/// }
+/// ```
+///
+/// A `min` greater than `max`.
+///
+/// ```compile_fail
+/// #[derive(Clone, boing::Settings)]
+/// struct Window {
+///     #[settings(min = 10, max = 5)]
+///     width: u32,
+/// }
+/// ```
+///
+/// A struct option on a field.
+///
+/// ```compile_fail
+/// #[derive(Clone, boing::Settings)]
+/// struct Window {
+///     #[settings(title = "Width")]
+///     width: u32,
+/// }
+/// ```
+///
+/// A label that isn't a string.
+///
+/// ```compile_fail
+/// #[derive(Clone, boing::Settings)]
+/// struct Window {
+///     #[settings(label = Width)]
+///     width: u32,
+/// }
+/// ```
+///
+/// A struct without named fields.
+///
+/// ```compile_fail
+/// #[derive(Clone, boing::Settings)]
+/// struct Size(u32, u32);
+/// ```
+///
+/// An option other than `label` on a variant.
+///
+/// ```compile_fail
+/// #[derive(Clone, boing::Settings)]
+/// enum Theme {
+///     #[settings(radio)]
+///     Light,
+///     Dark,
+/// }
+/// ```
+///
+/// An option on an enum.
+///
+/// ```compile_fail
+/// #[derive(Clone, boing::Settings)]
+/// #[settings(title = "Theme")]
+/// enum Theme {
+///     Light,
+///     Dark,
+/// }
+/// ```
+///
+/// A variant with fields.
+///
+/// ```compile_fail
+/// #[derive(Clone, boing::Settings)]
+/// enum Theme {
+///     Light,
+///     Custom(boing::Color),
+/// }
+/// ```
+///
+/// An enum without variants.
+///
+/// ```compile_fail
+/// #[derive(Clone, boing::Settings)]
+/// enum Theme {}
+/// ```
+///
+/// A union.
+///
+/// ```compile_fail
+/// #[derive(Clone, Copy, boing::Settings)]
+/// union Size {
+///     width: u32,
+/// }
+/// ```
+///
+/// A field whose type isn't a `Field`.
+///
+/// ```compile_fail
+/// #[derive(Clone, boing::Settings)]
+/// struct Window {
+///     title: Vec<String>,
+/// }
+/// ```
+#[cfg(all(doctest, feature = "derive"))]
+pub struct RejectedInput;
@@ -2,6 +2,8 @@
 
 //! [`Slider`].
 
+use std::{cell::Cell, rc::Rc};
+
 use crate::prelude::*;
 
 impl Ui {
@@ -9,19 +11,62 @@ impl Ui {
     ///
     /// # Arguments
     ///
-    /// `min` is the minimum value and `max` is the maximum value of the slider.
+    /// `min` is the minimum value and `max` is the maximum value of the slider. Both may be
+    /// negative.
     ///
     /// # Examples
     ///
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn create_slider<'ui>(&'ui self, min: u16, max: u16) -> Result<&'ui mut Slider, crate::Error> {
+    pub fn create_slider<'ui>(&'ui self, min: i32, max: i32) -> Result<&'ui Slider, crate::Error> {
         call_libui_new_fn!(
             ui: self,
-            fn: uiNewSlider(min.into(), max.into()) -> Slider,
+            fn: uiNewSlider(min, max) -> Slider,
         )
     }
+
+    /// Creates a new [`ScaledSlider`] that selects values between `min` and `max`, inclusive, in
+    /// increments of `step`.
+    ///
+    /// The tooltip of the underlying [`Slider`] is hidden as it would display the raw, unscaled
+    /// value.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `step` is not positive or if `min` is greater than `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), boing::Error> {
+    /// use boing::Ui;
+    ///
+    /// let ui: Ui;
+    /// # ui = Ui::new()?;
+    ///
+    /// // Selects a gain between -1 and 1 in increments of 0.05.
+    /// let gain = ui.create_scaled_slider(-1.0, 1.0, 0.05)?;
+    /// gain.set_value(0.5);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_scaled_slider<'ui>(
+        &'ui self,
+        min: f64,
+        max: f64,
+        step: f64,
+    ) -> Result<&'ui ScaledSlider<'ui>, crate::Error> {
+        let scale = Scale::new(min, max, step);
+        let slider = self.create_slider(0, scale.step_count())?;
+        slider.set_has_tooltip(false);
+
+        Ok(self.alloc_object(ScaledSlider {
+            slider,
+            scale: Rc::new(Cell::new(scale)),
+        }))
+    }
 }
 
 def_subcontrol!(
@@ -35,9 +80,11 @@ def_subcontrol!(
         ```
     ",
     ty: Slider,
+    kind: Slider,
     handle: uiSlider,
     cb_fns: [
         on_changed(),
+        on_released(),
     ],
 );
 
@@ -53,8 +100,7 @@ impl<'ui> Slider<'ui> {
             ```
         ",
         self: {
-            fn: value() -> u16,
-            map_out: |_, value| to_u16!(value),
+            fn: value() -> i32,
         },
         libui: { fn: uiSliderValue() },
     );
@@ -69,7 +115,7 @@ impl<'ui> Slider<'ui> {
             // TODO
             ```
         ",
-        self: { fn: set_value(value: u16) },
+        self: { fn: set_value(value: i32) },
         libui: { fn: uiSliderSetValue() },
     );
 
@@ -106,6 +152,9 @@ impl<'ui> Slider<'ui> {
         docs: "
             Sets a callback for when this slider changes.
 
+            While the slider is being dragged, this callback is called continuously; consider
+            [`Slider::on_released`] for expensive work.
+
             This callback is unset by default.
 
             # Examples
@@ -118,6 +167,7 @@ impl<'ui> Slider<'ui> {
             ty: Slider<'ui>,
             handle: uiSlider,
             fn: on_changed(),
+            emit: emit_changed(),
             cb: { sig: f -> () },
         },
         libui: {
@@ -126,6 +176,58 @@ impl<'ui> Slider<'ui> {
         },
     );
 
+    /// Simulates a drag, moving this slider to the given value and then calling the callback set by
+    /// [`Slider::on_changed`] as though the user had dragged it there.
+    ///
+    /// Call [`Slider::simulate_release`] afterwards to complete the drag.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn simulate_slide(&self, value: i32) {
+        self.set_value(value);
+        self.emit_changed();
+    }
+
+    bind_callback_fn!(
+        docs: "
+            Sets a callback for when the user releases this slider after dragging it.
+
+            This callback is unset by default.
+
+            # Examples
+
+            ```no_run
+            // TODO
+            ```
+        ",
+        self: {
+            ty: Slider<'ui>,
+            handle: uiSlider,
+            fn: on_released(),
+            emit: emit_released(),
+            cb: { sig: f -> () },
+        },
+        libui: {
+            fn: uiSliderOnReleased(),
+            cb: { sig: () -> () },
+        },
+    );
+
+    /// Simulates the end of a drag, calling the callback set by [`Slider::on_released`] as though
+    /// the user had let go of this slider.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn simulate_release(&self) {
+        self.emit_released();
+    }
+
     bind_fn!(
         docs: "
             Sets the minimum and maximum values of this slider.
@@ -136,7 +238,133 @@ impl<'ui> Slider<'ui> {
             // TODO
             ```
         ",
-        self: { fn: set_range(min: u16, max: u16) },
+        self: { fn: set_range(min: i32, max: i32) },
         libui: { fn: uiSliderSetRange() },
     );
 }
+
+/// A mapping between a range of `f64` values and the integer steps of a control.
+///
+/// *libui-ng* controls such as [`Slider`] only operate on integers. A [`Scale`] presents the
+/// integers `0..=step_count` as the values `min`, `min + step`, `min + 2 * step`, and so on, up to
+/// `max`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Scale {
+    min: f64,
+    max: f64,
+    step: f64,
+}
+
+impl Scale {
+    /// Creates a new [`Scale`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `step` is not positive or if `min` is greater than `max`.
+    pub fn new(min: f64, max: f64, step: f64) -> Self {
+        assert!(step > 0.0, "the step of a scale must be positive");
+        assert!(min <= max, "the minimum of a scale must not exceed its maximum");
+
+        Self { min, max, step }
+    }
+
+    /// The minimum value.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// The maximum value.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// The difference between adjacent values.
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// The number of steps between the minimum and maximum values.
+    pub fn step_count(&self) -> i32 {
+        ((self.max - self.min) / self.step).round() as i32
+    }
+
+    /// Converts a value to the nearest integer step, clamping it to the range of this scale.
+    pub fn to_step(&self, value: f64) -> i32 {
+        (((value - self.min) / self.step).round() as i32).clamp(0, self.step_count())
+    }
+
+    /// Converts an integer step to its value.
+    pub fn to_value(&self, step: i32) -> f64 {
+        // The maximum is returned exactly so that rounding error doesn't cause it to be missed.
+        if step >= self.step_count() {
+            self.max
+        } else {
+            self.min + (f64::from(step) * self.step)
+        }
+    }
+}
+
+/// A [`Slider`] that selects `f64` values according to a [`Scale`].
+///
+/// This is created with [`Ui::create_scaled_slider`].
+pub struct ScaledSlider<'ui> {
+    slider: &'ui Slider<'ui>,
+    // This is shared with the callback set by [`ScaledSlider::on_changed`] so that it reports
+    // values according to the current scale.
+    scale: Rc<Cell<Scale>>,
+}
+
+impl<'ui> ScaledSlider<'ui> {
+    /// The underlying slider, which operates on integer steps.
+    pub fn slider(&self) -> &Slider<'ui> {
+        self.slider
+    }
+
+    /// The mapping between the values of this slider and the steps of the underlying slider.
+    pub fn scale(&self) -> Scale {
+        self.scale.get()
+    }
+
+    /// The current value of this slider.
+    pub fn value(&self) -> f64 {
+        self.scale().to_value(self.slider.value())
+    }
+
+    /// Sets the value of this slider, rounding it to the nearest step.
+    pub fn set_value(&self, value: f64) {
+        self.slider.set_value(self.scale().to_step(value));
+    }
+
+    /// Replaces the scale of this slider, preserving its value as closely as possible.
+    pub fn set_scale(&self, scale: Scale) {
+        let value = self.value();
+        self.scale.set(scale);
+        self.slider.set_range(0, scale.step_count());
+        self.set_value(value);
+    }
+
+    /// Sets a callback for when this slider changes.
+    ///
+    /// The callback receives the new value, according to the scale at the time of the change.
+    pub fn on_changed(&self, mut f: impl 'ui + FnMut(f64)) {
+        let scale = Rc::clone(&self.scale);
+        self.slider
+            .on_changed(move |slider| f(scale.get().to_value(slider.value())));
+    }
+
+    /// Simulates a drag as though the user had moved this slider to the given value, rounded to
+    /// the nearest step.
+    ///
+    /// See [`Slider::simulate_slide`].
+    pub fn simulate_slide(&self, value: f64) {
+        self.slider.simulate_slide(self.scale().to_step(value));
+    }
+}
+
+impl<'ui> std::ops::Deref for ScaledSlider<'ui> {
+    type Target = Control<'ui>;
+
+    fn deref(&self) -> &Self::Target {
+        self.slider
+    }
+}
@@ -7,15 +7,20 @@ use crate::prelude::*;
 impl Ui {
     /// Creates a new [`Spinbox`].
     ///
+    /// # Arguments
+    ///
+    /// `min` is the minimum value and `max` is the maximum value of the spinbox. Both may be
+    /// negative.
+    ///
     /// # Examples
     ///
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn create_spinbox<'ui>(&'ui self, min: u16, max: u16) -> Result<&'ui mut Spinbox, crate::Error> {
+    pub fn create_spinbox<'ui>(&'ui self, min: i32, max: i32) -> Result<&'ui Spinbox, crate::Error> {
         call_libui_new_fn!(
             ui: self,
-            fn: uiNewSpinbox(min.into(), max.into()) -> Spinbox,
+            fn: uiNewSpinbox(min, max) -> Spinbox,
         )
     }
 }
@@ -31,6 +36,7 @@ def_subcontrol!(
         ```
     ",
     ty: Spinbox,
+    kind: Spinbox,
     handle: uiSpinbox,
     cb_fns: [ on_changed() ],
 );
@@ -47,8 +53,7 @@ impl<'ui> Spinbox<'ui> {
             ```
         ",
         self: {
-            fn: value() -> u16,
-            map_out: |_, value| to_u16!(value),
+            fn: value() -> i32,
         },
         libui: { fn: uiSpinboxValue() },
     );
@@ -63,7 +68,7 @@ impl<'ui> Spinbox<'ui> {
             // TODO
             ```
         ",
-        self: { fn: set_value(value: u16) },
+        self: { fn: set_value(value: i32) },
         libui: { fn: uiSpinboxSetValue() },
     );
 
@@ -83,6 +88,7 @@ impl<'ui> Spinbox<'ui> {
             ty: Spinbox<'ui>,
             handle: uiSpinbox,
             fn: on_changed(),
+            emit: emit_changed(),
             cb: { sig: f -> () },
         },
         libui: {
@@ -90,4 +96,17 @@ impl<'ui> Spinbox<'ui> {
             cb: { sig: () -> () },
         },
     );
+
+    /// Simulates a change, setting the value of this spinbox and then calling the callback set by
+    /// [`Spinbox::on_changed`] as though the user had entered `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn simulate_change(&self, value: i32) {
+        self.set_value(value);
+        self.emit_changed();
+    }
 }
@@ -0,0 +1,352 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Observable values that may be bound to widgets.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    rc::Rc,
+};
+
+use crate::{
+    callback::Observers,
+    Checkbox,
+    Combobox,
+    Progress,
+    ProgressBar,
+    Slider,
+    Spinbox,
+    TextEntry,
+};
+
+/// An observable value.
+///
+/// A [`State`] notifies its observers whenever its value changes. It may be bound to widgets with
+/// [`State::bind`] such that changes to the state are reflected in the widget and, for widgets that
+/// accept user input, changes made by the user are reflected in the state.
+///
+/// Cloning a [`State`] is cheap and produces a handle to the *same* value.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), boing::Error> {
+/// use boing::{State, Ui};
+///
+/// let ui: Ui;
+/// # ui = Ui::new()?;
+///
+/// let is_enabled = State::new(false);
+/// is_enabled.bind(ui.create_checkbox("Enabled")?);
+/// is_enabled.bind(ui.create_checkbox("Also Enabled")?);
+///
+/// // Both checkboxes are now checked, and toggling either toggles the other.
+/// is_enabled.set(true);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub struct State<'a, T> {
+    inner: Rc<Inner<'a, T>>,
+}
+
+struct Inner<'a, T> {
+    value: RefCell<T>,
+    observers: Observers<dyn 'a + FnMut(&T)>,
+    is_notifying: Cell<bool>,
+    /// Values set by observers, which are applied in order once they have all been notified.
+    pending: RefCell<VecDeque<T>>,
+}
+
+impl<T> Clone for State<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Rc::clone(&self.inner),
+        }
+    }
+}
+
+impl<'a, T: 'a + Clone + PartialEq> State<'a, T> {
+    /// Creates a new [`State`] with the given initial value.
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Rc::new(Inner {
+                value: RefCell::new(value),
+                observers: Observers::default(),
+                is_notifying: Cell::new(false),
+                pending: RefCell::default(),
+            }),
+        }
+    }
+
+    /// The current value.
+    pub fn get(&self) -> T {
+        self.inner.value.borrow().clone()
+    }
+
+    /// Calls the given function with a reference to the current value.
+    pub fn with<U>(&self, f: impl FnOnce(&T) -> U) -> U {
+        f(&self.inner.value.borrow())
+    }
+
+    /// Replaces the current value, notifying all observers if it changed.
+    ///
+    /// Calls made from within an observer of this state, e.g., to clamp the value, take effect
+    /// once every observer has been notified of the current change, after which observers are
+    /// notified again. A value equal to the current one is ignored, so bound widgets that report
+    /// programmatic changes don't cause further notifications.
+    pub fn set(&self, value: T) {
+        if self.inner.is_notifying.get() {
+            self.inner.pending.borrow_mut().push_back(value);
+            return;
+        }
+
+        if *self.inner.value.borrow() == value {
+            return;
+        }
+        *self.inner.value.borrow_mut() = value;
+
+        self.notify();
+    }
+
+    /// Modifies the current value in place, notifying all observers if it changed.
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        let mut value = self.get();
+        f(&mut value);
+        self.set(value);
+    }
+
+    /// Registers a function to be called with the new value whenever it changes.
+    ///
+    /// The function is not called with the current value.
+    pub fn observe(&self, f: impl 'a + FnMut(&T)) {
+        self.inner.observers.push(Box::new(f));
+    }
+
+    /// Binds the given widget to this state.
+    ///
+    /// The widget is immediately updated to reflect the current value. See [`Binding`] for which
+    /// widgets may be bound.
+    pub fn bind(&self, widget: impl Binding<'a, Value = T>) {
+        widget.bind(self);
+    }
+
+    /// Creates a new state whose value is computed from the value of this state.
+    ///
+    /// The derived state is updated whenever this state changes. Changes to the derived state
+    /// itself are not propagated back to this state.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use boing::State;
+    ///
+    /// let celsius = State::new(20.0);
+    /// let fahrenheit = celsius.map(|c| c * 1.8 + 32.0);
+    ///
+    /// celsius.set(100.0);
+    /// assert_eq!(212.0, fahrenheit.get());
+    /// ```
+    pub fn map<U: 'a + Clone + PartialEq>(&self, f: impl 'a + Fn(&T) -> U) -> State<'a, U> {
+        let derived = State::new(self.with(&f));
+        let weak = Rc::downgrade(&derived.inner);
+        self.observe(move |value| {
+            if let Some(inner) = weak.upgrade() {
+                State { inner }.set(f(value));
+            }
+        });
+
+        derived
+    }
+
+    /// Creates a new state whose value is computed from the values of this state and another.
+    ///
+    /// The derived state is updated whenever either state changes.
+    pub fn zip<U: 'a + Clone + PartialEq>(&self, other: &State<'a, U>) -> State<'a, (T, U)> {
+        let derived = State::new((self.get(), other.get()));
+
+        let weak = Rc::downgrade(&derived.inner);
+        self.observe(move |value| {
+            if let Some(inner) = weak.upgrade() {
+                State { inner }.update(|(this, _)| *this = value.clone());
+            }
+        });
+
+        let weak = Rc::downgrade(&derived.inner);
+        other.observe(move |value| {
+            if let Some(inner) = weak.upgrade() {
+                State { inner }.update(|(_, other)| *other = value.clone());
+            }
+        });
+
+        derived
+    }
+
+    fn notify(&self) {
+        loop {
+            let value = self.get();
+            {
+                let _notifying = Notifying::new(&self.inner);
+                self.inner.observers.notify(|observer| observer(&value));
+            }
+
+            // Apply the first value set by an observer that differs from the current one, if any.
+            let next = loop {
+                let Some(next) = self.inner.pending.borrow_mut().pop_front() else {
+                    return;
+                };
+                if *self.inner.value.borrow() != next {
+                    break next;
+                }
+            };
+            *self.inner.value.borrow_mut() = next;
+        }
+    }
+}
+
+/// Marks a state as notifying its observers for as long as this lives, even if one of them panics.
+struct Notifying<'s, 'a, T> {
+    inner: &'s Inner<'a, T>,
+}
+
+impl<'s, 'a, T> Notifying<'s, 'a, T> {
+    fn new(inner: &'s Inner<'a, T>) -> Self {
+        inner.is_notifying.set(true);
+
+        Self { inner }
+    }
+}
+
+impl<T> Drop for Notifying<'_, '_, T> {
+    fn drop(&mut self) {
+        self.inner.is_notifying.set(false);
+        if std::thread::panicking() {
+            // Values set by the observers that ran are stale once notification is cut short.
+            self.inner.pending.borrow_mut().clear();
+        }
+    }
+}
+
+/// A widget that may be bound to a [`State`].
+///
+/// Bindings to widgets that accept user input are two-way, and as such, they take over the widget's
+/// change callback (e.g., [`Checkbox::on_toggled`]); setting that callback afterwards breaks the
+/// binding.
+pub trait Binding<'a> {
+    /// The type of value displayed by this widget.
+    type Value;
+
+    /// Binds this widget to the given state.
+    fn bind(self, state: &State<'a, Self::Value>);
+}
+
+impl<'a> Binding<'a> for &'a Checkbox<'a> {
+    type Value = bool;
+
+    fn bind(self, state: &State<'a, Self::Value>) {
+        self.set_checked(state.get());
+
+        let source = state.clone();
+        self.on_toggled(move |checkbox| source.set(checkbox.checked()));
+
+        let checkbox: &'a Checkbox = self;
+        state.observe(move |value| {
+            if checkbox.checked() != *value {
+                checkbox.set_checked(*value);
+            }
+        });
+    }
+}
+
+impl<'a> Binding<'a> for &'a Combobox<'a> {
+    type Value = Option<u16>;
+
+    fn bind(self, state: &State<'a, Self::Value>) {
+        self.select_item(state.get());
+
+        let source = state.clone();
+        self.on_item_selected(move |_, selection| source.set(selection));
+
+        let combobox: &'a Combobox = self;
+        state.observe(move |value| {
+            if combobox.selected_item() != *value {
+                combobox.select_item(*value);
+            }
+        });
+    }
+}
+
+impl<'a> Binding<'a> for &'a ProgressBar<'a> {
+    type Value = Progress;
+
+    fn bind(self, state: &State<'a, Self::Value>) {
+        // Progress bars don't accept user input, so this binding is one-way.
+        self.set_value(state.get());
+
+        let progress_bar: &'a ProgressBar = self;
+        state.observe(move |value| progress_bar.set_value(*value));
+    }
+}
+
+impl<'a> Binding<'a> for &'a Slider<'a> {
+    type Value = i32;
+
+    fn bind(self, state: &State<'a, Self::Value>) {
+        self.set_value(state.get());
+
+        let source = state.clone();
+        self.on_changed(move |slider| source.set(slider.value()));
+
+        let slider: &'a Slider = self;
+        state.observe(move |value| {
+            if slider.value() != *value {
+                slider.set_value(*value);
+            }
+        });
+    }
+}
+
+impl<'a> Binding<'a> for &'a Spinbox<'a> {
+    type Value = i32;
+
+    fn bind(self, state: &State<'a, Self::Value>) {
+        self.set_value(state.get());
+
+        let source = state.clone();
+        self.on_changed(move |spinbox| source.set(spinbox.value()));
+
+        let spinbox: &'a Spinbox = self;
+        state.observe(move |value| {
+            if spinbox.value() != *value {
+                spinbox.set_value(*value);
+            }
+        });
+    }
+}
+
+impl<'a> Binding<'a> for &'a TextEntry<'a> {
+    type Value = String;
+
+    fn bind(self, state: &State<'a, Self::Value>) {
+        set_entry_text(self, &state.get());
+
+        let source = state.clone();
+        self.on_changed(move |entry| source.set(entry.text()));
+
+        let entry: &'a TextEntry = self;
+        state.observe(move |value| {
+            // Replacing the text of the entry the user is typing into would move their cursor.
+            if entry.text() != *value {
+                set_entry_text(entry, value);
+            }
+        });
+    }
+}
+
+fn set_entry_text(entry: &TextEntry, text: &str) {
+    // The only way this can fail is if `text` contains a NUL byte, in which case there's nothing
+    // sensible to display.
+    if let Err(e) = entry.set_text(text) {
+        tracing::warn!("Failed to bind text entry: {}", e);
+    }
+}
@@ -2,7 +2,7 @@
 
 //! [`Tab`].
 
-use crate::prelude::*;
+use crate::{combobox::selection_from_param, prelude::*};
 
 impl Ui {
     /// Creates a new [`Tab`].
@@ -12,7 +12,7 @@ impl Ui {
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn create_tab<'ui>(&'ui self) -> Result<&'ui mut Tab, crate::Error> {
+    pub fn create_tab<'ui>(&'ui self) -> Result<&'ui Tab, crate::Error> {
         call_libui_new_fn!(
             ui: self,
             fn: uiNewTab() -> Tab,
@@ -39,13 +39,52 @@ def_subcontrol!(
         ## Linux
     ",
     ty: Tab,
+    kind: Tab,
     handle: uiTab,
+    cb_fns: [ on_page_selected(Option<u16>) ],
 );
 
-impl Tab<'_> {
+impl<'ui> Tab<'ui> {
+    /// Deletes the page at the given index.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn remove_page(&self, index: u16) {
+        unsafe { uiTabDelete(self.as_ptr(), index.into()) };
+
+        self.with_page_titles(|titles| {
+            if usize::from(index) < titles.len() {
+                titles.remove(index.into());
+            }
+        });
+    }
+
+    /// The title of the page at the given index, or `None` if there is no such page.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn page_title(&self, index: u16) -> Option<String> {
+        self.with_page_titles(|titles| titles.get(usize::from(index)).cloned())
+            .flatten()
+    }
+
+    // *libui-ng* doesn't provide a function to get the title of a page, so we have to keep track
+    // ourselves. The titles are kept by the [`Ui`] so that every handle to this tab sees them.
+    fn with_page_titles<T>(&self, f: impl FnOnce(&mut Vec<String>) -> T) -> Option<T> {
+        self.ui
+            .registry
+            .with_entry(self.as_ptr().cast(), |entry| f(&mut entry.page_titles))
+    }
+
     bind_fn!(
         docs: "
-            Deletes the page at the given index.
+            The index of the currently-selected page, or `None` if this tab has no pages.
 
             # Examples
 
@@ -53,10 +92,95 @@ impl Tab<'_> {
             // TODO
             ```
         ",
-        self: { fn: remove_page(index: u16) },
-        libui: { fn: uiTabDelete() },
+        self: {
+            fn: selected_page() -> Option<u16>,
+            map_out: |_, index| selection_from_param(index),
+        },
+        libui: { fn: uiTabSelected() },
     );
 
+    bind_fn!(
+        docs: "
+            Shows the page at the given index.
+
+            # Examples
+
+            ```no_run
+            // TODO
+            ```
+        ",
+        self: { fn: select_page(index: u16) },
+        libui: { fn: uiTabSetSelected() },
+    );
+
+    bind_callback_fn!(
+        docs: "
+            Sets a callback for when a page is selected, either by the user or by
+            [`Tab::select_page`].
+
+            The callback receives the index of the newly-selected page, or `None` if this tab has
+            no pages.
+
+            This callback is unset by default.
+
+            # Examples
+
+            ```no_run
+            # fn main() -> Result<(), boing::Error> {
+            use boing::Ui;
+
+            let ui: Ui;
+            # ui = Ui::new()?;
+
+            let tab = ui.create_tab()?;
+            tab.push_new_page(\"Summary\", ui.create_label(\"Nothing to see here.\")?)?;
+            tab.push_new_page(\"Details\", ui.create_vertical_axis()?)?;
+
+            let mut is_details_loaded = false;
+            tab.on_page_selected(move |tab, page| {
+                if page == Some(1) && !is_details_loaded {
+                    is_details_loaded = true;
+                    println!(\"Loading {}...\", tab.page_title(1).unwrap());
+                }
+            });
+            #
+            # Ok(())
+            # }
+            ```
+        ",
+        self: {
+            ty: Tab<'ui>,
+            handle: uiTab,
+            fn: on_page_selected(),
+            emit: emit_page_selected(),
+            cb: {
+                sig: f(Option<u16>) -> (),
+                arg: |this| this.selected_page(),
+            },
+        },
+        libui: {
+            fn: uiTabOnSelected(),
+            cb: { sig: () -> () },
+        },
+    );
+
+    /// Simulates a selection, selecting the given page and then calling the callback set by
+    /// [`Tab::on_page_selected`] as though the user had picked it.
+    ///
+    /// Unlike [`Tab::select_page`], this calls the callback exactly once on every platform.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn simulate_page_selection(&self, page: u16) {
+        // Only some platforms report switching pages programmatically, so such a report is muted
+        // in favor of calling the callback here.
+        self.on_page_selected.mute(|| self.select_page(page));
+        self.emit_page_selected();
+    }
+
     bind_fn!(
         docs: "
             The number of pages contained within this tab.
@@ -113,22 +237,27 @@ impl Tab<'_> {
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn push_new_page(
+    pub fn push_new_page<'c>(
         &self,
         name: impl AsRef<str>,
-        control: &mut impl DerefMut<Target = Control>,
+        control: &impl Deref<Target = Control<'c>>,
     ) -> Result<u16, crate::Error> {
         let index = self.page_count();
 
+        let name = name.as_ref();
+        // The name is converted first so that the control isn't marked as a child if this fails.
+        let c_name = make_cstring!(name);
         control.make_child();
-        let name = make_cstring!(name.as_ref());
-        unsafe { uiTabAppend(self.as_ptr(), name.as_ptr(), control.as_ptr()) };
+        unsafe { uiTabAppend(self.as_ptr(), c_name.as_ptr(), control.as_ptr()) };
+        self.with_page_titles(|titles| titles.push(name.to_owned()));
 
         Ok(index)
     }
 
     /// Inserts a new page before the item at the given index, returning the index of the new page.
     ///
+    /// The returned index is equivalent to `before`.
+    ///
     /// Note that the returned index may be invalidated after deletion operations. *boing* provides
     /// no mechanism to update the index in these cases, so it is the programmer's responsibility to
     /// keep track of each child's current index.
@@ -138,25 +267,30 @@ impl Tab<'_> {
     /// ```no_run
     /// // TODO
     /// ```
-    pub fn insert_new_page(
+    pub fn insert_new_page<'c>(
         &self,
         name: impl AsRef<str>,
         before: u16,
-        control: &mut impl DerefMut<Target = Control>,
+        control: &impl Deref<Target = Control<'c>>,
     ) -> Result<u16, crate::Error> {
-        let index = self.page_count();
-
+        let name = name.as_ref();
+        // See [`Tab::push_new_page`].
+        let c_name = make_cstring!(name);
         control.make_child();
-        let name = make_cstring!(name.as_ref());
         unsafe {
             uiTabInsertAt(
                 self.as_ptr(),
-                name.as_ptr(),
+                c_name.as_ptr(),
                 before.into(),
                 control.as_ptr(),
             )
         }
 
-        Ok(index)
+        self.with_page_titles(|titles| {
+            let at = usize::from(before).min(titles.len());
+            titles.insert(at, name.to_owned());
+        });
+
+        Ok(before)
     }
 }
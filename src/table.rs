@@ -1,16 +1,80 @@
 // SPDX-License-Identifier: MPL-2.0
 
-//! [`Table`].
+//! [`Table`] and the [`Model`] it shows.
+//!
+//! A table shows the rows of a [`Model`], whose cells are provided by a [`Source`]. Each column of
+//! a table shows one or more columns of its model, e.g., a text column shows a column of strings,
+//! and an image column shows a column of [`Image`]s.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use boing::{
+//!     table::{RowBackgroundColor, Source, Value, ValueType},
+//!     Image,
+//!     Ui,
+//! };
+//!
+//! struct Files<'ui> {
+//!     icon: Image<'ui>,
+//!     names: Vec<String>,
+//! }
+//!
+//! impl Source for Files<'_> {
+//!     fn column_count(&self) -> u16 {
+//!         2
+//!     }
+//!
+//!     fn column_type(&self, column: u16) -> ValueType {
+//!         match column {
+//!             0 => ValueType::Image,
+//!             _ => ValueType::String,
+//!         }
+//!     }
+//!
+//!     fn row_count(&self) -> u16 {
+//!         self.names.len() as u16
+//!     }
+//!
+//!     fn cell(&self, row: u16, column: u16) -> Value<'_> {
+//!         match column {
+//!             0 => Value::Image(&self.icon),
+//!             _ => Value::String(&self.names[usize::from(row)]),
+//!         }
+//!     }
+//! }
+//!
+//! let ui = Ui::new()?;
+//! let icon = ui.create_image(16.0, 16.0)?;
+//! icon.push_rgba(&[0x80; 4 * 16 * 16], 16, 16, 4 * 16)?;
+//!
+//! let model = ui.create_table_model(Files {
+//!     icon,
+//!     names: vec!["notes.txt".into(), "todo.txt".into()],
+//! })?;
+//! let table = ui.create_table(model, RowBackgroundColor::Default)?;
+//! table.push_new_image_column("", 0)?;
+//! table.push_new_text_column("Name", 1)?;
+//! #
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Image`]: crate::Image
 
 mod model;
 
-pub use model::Model;
+pub use model::{Model, Source, Value, ValueType};
 
 use crate::prelude::*;
 
+/// The background color of the rows of a [`Table`].
 #[derive(Clone, Copy, Debug)]
 pub enum RowBackgroundColor {
+    /// The default background color.
     Default,
+    /// The color held by a column of the model, which must be of [`ValueType::Color`].
     SameAsColumn { index: u16 },
 }
 
@@ -24,28 +88,38 @@ impl RowBackgroundColor {
 }
 
 impl Ui {
-    // pub fn create_table<'ui>(
-    //     &'ui self,
-    //     model: Model,
-    //     row_bg: RowBackgroundColor,
-    // ) -> Result<&'ui mut Table, crate::Error> {
-    //     let mut params = uiTableParams {
-    //         // TODO: `model` is dropped at the end of scope, so this will always be a
-    //         // use-after-free.
-    //         Model: model.as_ptr(),
-    //         RowBackgroundColorModelColumn: row_bg.into_param(),
-    //     };
-
-    //     call_libui_new_fn!(
-    //         ui: self,
-    //         fn: uiNewTable(std::ptr::addr_of_mut!(params)) -> Table,
-    //     )
-    // }
+    /// Creates a new [`Table`] that shows the given model.
+    ///
+    /// A table initially has no columns; see [`Table::push_new_text_column`] and
+    /// [`Table::push_new_image_column`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_table<'ui>(
+        &'ui self,
+        model: &'ui Model<'ui>,
+        row_bg: RowBackgroundColor,
+    ) -> Result<&'ui Table<'ui>, crate::Error> {
+        // *libui-ng* copies the parameters, and the model lives for as long as the `Ui`, and thus
+        // the table, does.
+        let mut params = uiTableParams {
+            Model: model.as_ptr(),
+            RowBackgroundColorModelColumn: row_bg.into_param(),
+        };
+
+        call_libui_new_fn!(
+            ui: self,
+            fn: uiNewTable(std::ptr::addr_of_mut!(params)) -> Table,
+        )
+    }
 }
 
 def_subcontrol!(
     docs: "
-
+        A list of rows with columns, which shows a [`Model`].
 
         # Examples
 
@@ -54,6 +128,7 @@ def_subcontrol!(
         ```
     ",
     ty: Table,
+    kind: Table,
     handle: uiTable,
 );
 
@@ -113,4 +188,48 @@ impl Table<'_> {
         self: { fn: set_column_width(index: u16, width: u16) },
         libui: { fn: uiTableColumnSetWidth() },
     );
+
+    /// Appends a new column that shows the strings of the given column of the model.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn push_new_text_column(
+        &self,
+        name: impl AsRef<str>,
+        column: u16,
+    ) -> Result<(), crate::Error> {
+        let name = make_cstring!(name.as_ref());
+        unsafe {
+            uiTableAppendTextColumn(
+                self.as_ptr(),
+                name.as_ptr(),
+                column.into(),
+                uiTableModelColumnNeverEditable,
+                std::ptr::null_mut(),
+            )
+        };
+
+        Ok(())
+    }
+
+    /// Appends a new column that shows the images of the given column of the model.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn push_new_image_column(
+        &self,
+        name: impl AsRef<str>,
+        column: u16,
+    ) -> Result<(), crate::Error> {
+        let name = make_cstring!(name.as_ref());
+        unsafe { uiTableAppendImageColumn(self.as_ptr(), name.as_ptr(), column.into()) };
+
+        Ok(())
+    }
 }
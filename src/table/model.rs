@@ -1,22 +1,232 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use crate::prelude::*;
+//! [`Model`].
+
+use std::{os::raw::c_int, ptr};
+
+use crate::{prelude::*, Color, Image};
 
 impl Ui {
-    // pub fn create_table_model(&self) -> Model {
-    //     call_libui_new_fn!(
-    //         ui: self,
-    //         fn: uiNewTableModel() -> Model,
-    //     )
-    // }
+    /// Creates a new [`Model`] whose cells are provided by the given source.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn create_table_model<'ui>(
+        &'ui self,
+        source: impl 'ui + Source,
+    ) -> Result<&'ui Model<'ui>, crate::Error> {
+        let raw: &mut RawHandler = self.alloc_object(RawHandler {
+            raw: uiTableModelHandler {
+                NumColumns: Some(num_columns),
+                ColumnType: Some(column_type),
+                NumRows: Some(num_rows),
+                CellValue: Some(cell_value),
+                SetCellValue: Some(set_cell_value),
+            },
+            source: Box::new(source),
+        });
+
+        // `RawHandler` begins with a `uiTableModelHandler`, so *libui-ng* may use it as one.
+        let ptr = call_fallible_libui_fn!(uiNewTableModel(ptr::addr_of_mut!(*raw).cast()))?;
+
+        Ok(self.alloc_object(Model { ptr, _ui: self }))
+    }
 }
 
-pub struct Model {
+/// The rows and columns shown by one or more [`Table`](crate::Table)s.
+///
+/// A model asks its [`Source`] for the contents of its cells whenever a table needs them. Tables
+/// must be told when rows are inserted, changed, or deleted with [`Model::notify_row_inserted`],
+/// [`Model::notify_row_changed`], and [`Model::notify_row_deleted`].
+pub struct Model<'ui> {
     ptr: *mut uiTableModel,
+    _ui: &'ui Ui,
 }
 
-impl Model {
+impl Model<'_> {
     pub(crate) fn as_ptr(&self) -> *mut uiTableModel {
-        todo!()
+        self.ptr
     }
+
+    bind_fn!(
+        docs: "
+            Tells tables that a row was inserted at the given index.
+
+            The source must already report the new row.
+
+            # Examples
+
+            ```no_run
+            // TODO
+            ```
+        ",
+        self: { fn: notify_row_inserted(index: u16) },
+        libui: { fn: uiTableModelRowInserted() },
+    );
+
+    bind_fn!(
+        docs: "
+            Tells tables that the cells of the row at the given index changed.
+
+            # Examples
+
+            ```no_run
+            // TODO
+            ```
+        ",
+        self: { fn: notify_row_changed(index: u16) },
+        libui: { fn: uiTableModelRowChanged() },
+    );
+
+    bind_fn!(
+        docs: "
+            Tells tables that the row at the given index was deleted.
+
+            The source must no longer report the deleted row.
+
+            # Examples
+
+            ```no_run
+            // TODO
+            ```
+        ",
+        self: { fn: notify_row_deleted(index: u16) },
+        libui: { fn: uiTableModelRowDeleted() },
+    );
+}
+
+/// The contents of a [`Model`].
+///
+/// Every row has the same columns, and each column holds values of one [`ValueType`]. The columns
+/// of a model needn't match those of the tables that show it; a table column refers to model
+/// columns by index.
+pub trait Source {
+    /// The number of columns.
+    fn column_count(&self) -> u16;
+
+    /// The type of the values in the column at the given index.
+    fn column_type(&self, column: u16) -> ValueType;
+
+    /// The number of rows.
+    fn row_count(&self) -> u16;
+
+    /// The value of the cell at the given row and column, which must be of the type of the column.
+    fn cell(&self, row: u16, column: u16) -> Value<'_>;
+}
+
+/// The type of the values in a column of a [`Model`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ValueType {
+    String,
+    Image,
+    Int,
+    Color,
+}
+
+impl ValueType {
+    fn into_raw(self) -> uiTableValueType {
+        match self {
+            Self::String => uiTableValueTypeString,
+            Self::Image => uiTableValueTypeImage,
+            Self::Int => uiTableValueTypeInt,
+            Self::Color => uiTableValueTypeColor,
+        }
+    }
+}
+
+/// The value of a cell of a [`Model`].
+#[derive(Clone, Copy)]
+pub enum Value<'a> {
+    String(&'a str),
+    /// An image, which is borrowed from the [`Source`] while the table draws it.
+    Image(&'a Image<'a>),
+    Int(i32),
+    Color(Color),
+}
+
+impl Value<'_> {
+    /// Creates a `uiTableValue`, which *libui-ng* takes ownership of.
+    fn into_raw(self) -> Result<*mut uiTableValue, crate::Error> {
+        let value = match self {
+            Self::String(text) => {
+                // *libui-ng* copies the string.
+                let text = make_cstring!(text);
+                unsafe { uiNewTableValueString(text.as_ptr()) }
+            }
+            Self::Image(image) => unsafe { uiNewTableValueImage(image.as_ptr()) },
+            Self::Int(value) => unsafe { uiNewTableValueInt(value) },
+            Self::Color(color) => unsafe {
+                uiNewTableValueColor(color.red, color.green, color.blue, color.alpha)
+            },
+        };
+
+        Ok(value)
+    }
+}
+
+/// The memory layout of the `uiTableModelHandler` given to *libui-ng* by
+/// [`Ui::create_table_model`].
+#[repr(C)]
+struct RawHandler<'ui> {
+    raw: uiTableModelHandler,
+    source: Box<dyn 'ui + Source>,
+}
+
+impl<'ui> RawHandler<'ui> {
+    /// Reinterprets a `uiTableModelHandler` created by [`Ui::create_table_model`] as a
+    /// [`RawHandler`].
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a handler created by [`Ui::create_table_model`].
+    unsafe fn source(ptr: *mut uiTableModelHandler) -> &'ui dyn Source {
+        debug_assert!(!ptr.is_null());
+
+        &*(*ptr.cast::<Self>()).source
+    }
+}
+
+unsafe extern "C" fn num_columns(mh: *mut uiTableModelHandler, _: *mut uiTableModel) -> c_int {
+    RawHandler::source(mh).column_count().into()
+}
+
+unsafe extern "C" fn column_type(
+    mh: *mut uiTableModelHandler,
+    _: *mut uiTableModel,
+    column: c_int,
+) -> uiTableValueType {
+    RawHandler::source(mh).column_type(column as u16).into_raw()
+}
+
+unsafe extern "C" fn num_rows(mh: *mut uiTableModelHandler, _: *mut uiTableModel) -> c_int {
+    RawHandler::source(mh).row_count().into()
+}
+
+unsafe extern "C" fn cell_value(
+    mh: *mut uiTableModelHandler,
+    _: *mut uiTableModel,
+    row: c_int,
+    column: c_int,
+) -> *mut uiTableValue {
+    let value = RawHandler::source(mh).cell(row as u16, column as u16);
+
+    // *libui-ng* can't cope with a missing value, so a string that can't be converted is shown as
+    // an empty one instead.
+    value.into_raw().unwrap_or_else(|e| {
+        tracing::warn!("Failed to convert table cell: {}", e);
+        uiNewTableValueString(c"".as_ptr())
+    })
+}
+
+unsafe extern "C" fn set_cell_value(
+    _: *mut uiTableModelHandler,
+    _: *mut uiTableModel,
+    _: c_int,
+    _: c_int,
+    _: *const uiTableValue,
+) {
+    // Columns are never editable, so there is nothing to set.
 }
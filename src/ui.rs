@@ -2,9 +2,9 @@
 
 //! A graphical user interface provided by *libui-ng*.
 
-use std::{ffi::CStr, os::raw::c_char, ptr};
+use std::{cell::RefCell, ffi::CStr, os::raw::c_char, ptr, rc::Rc};
 
-use crate::prelude::*;
+use crate::{prelude::*, Subcontrol};
 
 impl Ui {
     /// Creates a new [`Ui`].
@@ -12,7 +12,8 @@ impl Ui {
     /// # Errors
     ///
     /// This function may only be called once. Calling [`Ui::new`] a second time will return
-    /// [`crate::Error::AlreadyInitedLibui`].
+    /// [`crate::Error::AlreadyInitedLibui`]. This restriction is lifted when *boing* is compiled
+    /// with feature `mock`.
     ///
     /// # Examples
     ///
@@ -25,6 +26,19 @@ impl Ui {
     /// assert!(Ui::new().is_ok());
     /// ```
     pub fn new() -> Result<Self, crate::Error> {
+        Self::init().map(|_| {
+            Self {
+                arena: bumpalo::Bump::new(),
+                registry: Rc::default(),
+                recorder: RefCell::default(),
+                localizer: RefCell::default(),
+                dialogs: Default::default(),
+            }
+        })
+    }
+
+    #[cfg(not(feature = "mock"))]
+    fn init() -> Result<(), crate::Error> {
         use std::sync::Once;
 
         static INIT: Once = Once::new();
@@ -36,11 +50,14 @@ impl Ui {
             result = Self::init_unchecked();
         });
 
-        result.map(|_| {
-            Self {
-                arena: bumpalo::Bump::new(),
-            }
-        })
+        result
+    }
+
+    // The mock backend keeps its state per thread, so it may be initialized any number of times.
+    // This lets every test create its own [`Ui`].
+    #[cfg(feature = "mock")]
+    fn init() -> Result<(), crate::Error> {
+        unsafe { Self::init_unchecked() }
     }
 
     // Initializes *libui-ng* with the assumption that *libui-ng* is not already initialized.
@@ -123,6 +140,14 @@ pub struct Ui {
     // destroyed, as well as store callback data for [`MenuItem`]s, which is the express purpose
     // for the `arena` field.
     arena: bumpalo::Bump,
+    // The controls created by this [`Ui`], and their IDs.
+    pub(crate) registry: Rc<crate::registry::Registry>,
+    // The recording started by [`Ui::start_recording`], if any.
+    pub(crate) recorder: RefCell<Option<crate::session::Recorder>>,
+    // The catalogs and localized controls of [`crate::locale`].
+    pub(crate) localizer: RefCell<crate::locale::Localizer>,
+    // The reusable windows of [`crate::dialog`], and the one that is open, if any.
+    pub(crate) dialogs: crate::dialog::Dialogs,
 }
 
 impl Ui {
@@ -163,10 +188,28 @@ impl Ui {
         unsafe { uiMainStep(0) == 1 }
     }
 
+    /// Like [`Ui::step`], but waits for an event if none is pending rather than returning
+    /// immediately.
+    pub(crate) fn step_blocking(&self) -> bool {
+        unsafe { uiMainStep(1) == 1 }
+    }
+
     /// Allocates an object.
     ///
     /// Wrap a value in this method when you need it to live for as long as [`Ui`].
     pub(crate) fn alloc_object<T>(&self, value: T) -> &mut T {
         self.arena.alloc(value)
     }
+
+    /// Allocates a control, registering it so that it may be found by its *libui-ng* handle.
+    pub(crate) fn alloc_control<'ui, T: Subcontrol<'ui>>(&'ui self, control: T) -> &'ui T {
+        let control: *const T = self.alloc_object(control);
+        // SAFETY: `control` was just allocated in the arena. The registry keeps it as a raw pointer
+        // to the whole handle, rather than to the inner [`Control`], so that it may be downcast.
+        unsafe {
+            self.registry.register(control);
+
+            &*control
+        }
+    }
 }
@@ -0,0 +1,698 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Undoing and redoing changes.
+//!
+//! An [`UndoStack`] records reversible [`Command`]s as they are made. Changes may be recorded in
+//! three ways:
+//!
+//! - as arbitrary commands, with [`UndoStack::push`], [`UndoStack::execute`], or
+//!   [`UndoStack::push_fn`];
+//! - as values given to widgets programmatically, with [`UndoStack::set`]; and
+//! - as text typed by the user into an entry being tracked with [`UndoStack::track`]. Keystrokes
+//!   in quick succession are coalesced into one command, so that undoing removes a burst of typing
+//!   rather than a single character.
+//!
+//! A stack can also keep "Undo" and "Redo" menu items up to date; see
+//! [`UndoStack::push_menu_items`].
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> Result<(), boing::Error> {
+//! use boing::{undo::UndoStack, Ui};
+//!
+//! let ui = Ui::new()?;
+//! let undo = UndoStack::new();
+//! undo.push_menu_items(ui.create_menu("Edit")?, "Undo", "Redo")?;
+//!
+//! let window = ui.create_window("Notes", 640, 480, true, true)?;
+//! let notes = ui.create_wrapping_multiline_text_entry()?;
+//! let status = ui.create_label("")?;
+//! notes.on_changed(|notes| {
+//!     let _ = status.set_text(&format!("{} characters", notes.text().chars().count()));
+//! });
+//! undo.track(notes);
+//!
+//! let bold = ui.create_checkbox("Bold")?;
+//! let clear = ui.create_pushbutton("Clear")?;
+//! clear.on_clicked(|_| undo.set(bold, false));
+//! #
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    cell::{Cell, RefCell},
+    mem,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    callback::{warn_on_error, Observers},
+    prelude::*,
+    Checkbox,
+    Combobox,
+    Menu,
+    MenuItem,
+    MultilineTextEntry,
+    Slider,
+    Spinbox,
+    TextEntry,
+};
+
+/// A reversible change.
+///
+/// A command is either recorded once it has been made, with [`UndoStack::push`], or made by the
+/// stack itself, with [`UndoStack::execute`], which calls [`Command::redo`].
+pub trait Command {
+    /// Reverts this change.
+    fn undo(&mut self);
+
+    /// Makes this change again.
+    fn redo(&mut self);
+}
+
+/// A stack of [`Command`]s that may be undone and redone.
+///
+/// Cloning an [`UndoStack`] is cheap and produces a handle to the *same* stack.
+pub struct UndoStack<'a> {
+    inner: Rc<Inner<'a>>,
+}
+
+struct Inner<'a> {
+    undo: RefCell<Vec<Entry<'a>>>,
+    redo: RefCell<Vec<Entry<'a>>>,
+    limit: Cell<Option<usize>>,
+    coalesce_interval: Cell<Duration>,
+    editors: RefCell<Vec<Tracked<'a>>>,
+    menu_items: RefCell<Vec<(&'a MenuItem<'a>, &'a MenuItem<'a>)>>,
+    observers: Observers<dyn 'a + FnMut()>,
+    is_applying: Cell<bool>,
+}
+
+/// A recorded command.
+struct Entry<'a> {
+    command: Box<dyn 'a + Command>,
+    /// For typing, the text from before it, which typing that follows may be coalesced into.
+    typing: Option<Typing>,
+}
+
+struct Typing {
+    control: *mut uiControl,
+    /// Whether or not the editor is still alive; see [`Tracked::is_alive`].
+    is_alive: Rc<Cell<bool>>,
+    before: String,
+    time: Instant,
+}
+
+/// An editor tracked with [`UndoStack::track`], and the text it was last known to have.
+///
+/// The editor is looked up by its handle whenever it is needed rather than borrowed, so once it is
+/// destroyed, it is left alone.
+struct Tracked<'a> {
+    control: *mut uiControl,
+    /// Whether or not the editor is still alive.
+    ///
+    /// This is cleared when the editor is destroyed, as another control may then take its address,
+    /// and the editor is forgotten the next time the stack records typing.
+    is_alive: Rc<Cell<bool>>,
+    text: Box<dyn 'a + Fn() -> Option<String>>,
+    set_text: Rc<dyn 'a + Fn(&str)>,
+    last_text: RefCell<String>,
+}
+
+impl<'a> Clone for UndoStack<'a> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Rc::clone(&self.inner),
+        }
+    }
+}
+
+impl Default for UndoStack<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> UndoStack<'a> {
+    /// Creates a new, empty stack.
+    ///
+    /// The stack is unlimited, and typing is coalesced while keystrokes are at most one second
+    /// apart.
+    pub fn new() -> Self {
+        Self {
+            inner: Rc::new(Inner {
+                undo: RefCell::default(),
+                redo: RefCell::default(),
+                limit: Cell::new(None),
+                coalesce_interval: Cell::new(Duration::from_secs(1)),
+                editors: RefCell::default(),
+                menu_items: RefCell::default(),
+                observers: Observers::default(),
+                is_applying: Cell::new(false),
+            }),
+        }
+    }
+
+    /// Sets the maximum number of commands that may be undone, or `None` for no limit.
+    ///
+    /// The oldest commands are forgotten first.
+    pub fn set_limit(&self, limit: Option<usize>) {
+        self.inner.limit.set(limit);
+        self.trim();
+        self.notify();
+    }
+
+    /// Sets the longest pause between keystrokes for which they are coalesced into one command.
+    pub fn set_coalesce_interval(&self, interval: Duration) {
+        self.inner.coalesce_interval.set(interval);
+    }
+
+    /// Records a command that has already been made.
+    ///
+    /// This discards the commands that could have been redone.
+    ///
+    /// # Panics
+    ///
+    /// This panics if called from within [`Command::undo`] or [`Command::redo`].
+    pub fn push(&self, command: impl 'a + Command) {
+        self.push_entry(Entry {
+            command: Box::new(command),
+            typing: None,
+        });
+    }
+
+    /// Makes a change with [`Command::redo`] and records it.
+    ///
+    /// # Panics
+    ///
+    /// See [`UndoStack::push`].
+    pub fn execute(&self, mut command: impl 'a + Command) {
+        self.apply(|| command.redo());
+        self.push(command);
+    }
+
+    /// Records a change that has already been made, given functions that revert and remake it.
+    ///
+    /// # Panics
+    ///
+    /// See [`UndoStack::push`].
+    pub fn push_fn(&self, undo: impl 'a + FnMut(), redo: impl 'a + FnMut()) {
+        self.push(FnCommand { undo, redo });
+    }
+
+    /// Sets the value of a widget, recording the change.
+    ///
+    /// Nothing is recorded if the widget already has the value.
+    ///
+    /// # Panics
+    ///
+    /// See [`UndoStack::push`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn set<W: Property>(&self, widget: &'a W, value: W::Value) {
+        let old = widget.value();
+        if old != value {
+            self.execute(SetProperty { widget, old, new: value });
+        }
+    }
+
+    /// Records the text typed by the user into an editor.
+    ///
+    /// This sets the change callback of the editor (e.g., [`TextEntry::on_changed`]) to one that
+    /// calls [`UndoStack::record_typing`] and then the callback set before, if any. A callback set
+    /// afterwards replaces both, so it must call [`UndoStack::record_typing`] itself.
+    ///
+    /// Changes to the text that are not made through this stack, e.g., with
+    /// [`TextEntry::set_text`], are not recorded, and become part of the next typing command.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn track(&self, editor: &impl Editor<'a>) {
+        editor.track(self);
+    }
+
+    /// Records the text of an editor tracked with [`UndoStack::track`] as typed by the user.
+    ///
+    /// A change callback set after [`UndoStack::track`] replaces the one it set, so it must call
+    /// this for typing to be recorded. It should do so before anything else, so that the typing is
+    /// recorded before any command that the callback pushes, and so that the callback sees the
+    /// stack as already including the typing, e.g., in [`UndoStack::can_undo`].
+    ///
+    /// Nothing is recorded if the editor isn't tracked, or if its text hasn't changed since it was
+    /// last recorded.
+    ///
+    /// # Panics
+    ///
+    /// See [`UndoStack::push`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn record_typing(&self, editor: &Control) {
+        let control = editor.as_ptr();
+        let (before, text, set_text, is_alive) = {
+            let mut editors = self.inner.editors.borrow_mut();
+            editors.retain(|editor| editor.is_alive.get());
+            let Some(editor) = editors.iter().find(|editor| editor.control == control) else {
+                return;
+            };
+            let Some(text) = (editor.text)() else {
+                return;
+            };
+
+            (
+                editor.last_text.replace(text.clone()),
+                text,
+                Rc::clone(&editor.set_text),
+                Rc::clone(&editor.is_alive),
+            )
+        };
+        if before == text {
+            return;
+        }
+
+        // Typing is coalesced into the previous command if it, too, was recent typing into this
+        // editor.
+        let now = Instant::now();
+        let interval = self.inner.coalesce_interval.get();
+        let previous = {
+            let mut undo = self.inner.undo.borrow_mut();
+            let is_coalesced = undo
+                .last()
+                .and_then(|entry| entry.typing.as_ref())
+                .is_some_and(|typing| {
+                    typing.control == control
+                        && typing.is_alive.get()
+                        && now.duration_since(typing.time) <= interval
+                });
+
+            if is_coalesced {
+                undo.pop()
+            } else {
+                None
+            }
+        };
+        let before = previous
+            .and_then(|entry| entry.typing)
+            .map_or(before, |typing| typing.before);
+
+        self.push_entry(Entry {
+            command: Box::new(TextEdit {
+                set_text,
+                before: before.clone(),
+                after: text,
+            }),
+            typing: Some(Typing {
+                control,
+                is_alive,
+                before,
+                time: now,
+            }),
+        });
+    }
+
+    /// Undoes the most recent command, returning whether or not there was one.
+    ///
+    /// # Panics
+    ///
+    /// This panics if called from within [`Command::undo`] or [`Command::redo`].
+    pub fn undo(&self) -> bool {
+        self.move_top(&self.inner.undo, &self.inner.redo, |command| command.undo())
+    }
+
+    /// Redoes the most recently undone command, returning whether or not there was one.
+    ///
+    /// # Panics
+    ///
+    /// See [`UndoStack::undo`].
+    pub fn redo(&self) -> bool {
+        self.move_top(&self.inner.redo, &self.inner.undo, |command| command.redo())
+    }
+
+    /// Determines if there is a command to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.inner.undo.borrow().is_empty()
+    }
+
+    /// Determines if there is a command to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.inner.redo.borrow().is_empty()
+    }
+
+    /// Forgets all commands.
+    pub fn clear(&self) {
+        // Commands are dropped outside of the borrows, as dropping them may run arbitrary code.
+        let undo = mem::take(&mut *self.inner.undo.borrow_mut());
+        let redo = mem::take(&mut *self.inner.redo.borrow_mut());
+        drop((undo, redo));
+        self.notify();
+    }
+
+    /// Prevents the most recent typing from being coalesced with what is typed next, e.g., because
+    /// the document was saved.
+    pub fn seal(&self) {
+        if let Some(entry) = self.inner.undo.borrow_mut().last_mut() {
+            entry.typing = None;
+        }
+    }
+
+    /// Appends "Undo" and "Redo" items with the given texts to a menu.
+    ///
+    /// Clicking the items undoes and redoes commands, and they are only enabled while there is a
+    /// command to undo or redo, respectively.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn push_menu_items(
+        &self,
+        menu: &Menu<'a>,
+        undo_text: impl AsRef<str>,
+        redo_text: impl AsRef<str>,
+    ) -> Result<(), crate::Error> {
+        let undo_item = menu.push_new_item(undo_text)?;
+        let redo_item = menu.push_new_item(redo_text)?;
+
+        let stack = self.clone();
+        undo_item.on_clicked(move |_| {
+            stack.undo();
+        });
+        let stack = self.clone();
+        redo_item.on_clicked(move |_| {
+            stack.redo();
+        });
+
+        self.inner.menu_items.borrow_mut().push((undo_item, redo_item));
+        self.update_menu_items();
+
+        Ok(())
+    }
+
+    /// Registers a function to be called whenever a command is recorded, undone, or redone, or the
+    /// stack is cleared.
+    ///
+    /// This is a good place to mark a document as modified.
+    pub fn observe(&self, f: impl 'a + FnMut()) {
+        self.inner.observers.push(Box::new(f));
+    }
+
+    fn push_entry(&self, entry: Entry<'a>) {
+        assert!(!self.inner.is_applying.get(), "commands cannot be recorded while one is applied");
+
+        let redo = mem::take(&mut *self.inner.redo.borrow_mut());
+        self.inner.undo.borrow_mut().push(entry);
+        drop(redo);
+        self.trim();
+        self.notify();
+    }
+
+    /// Moves the top command of one stack to the other, applying it along the way.
+    fn move_top(
+        &self,
+        from: &RefCell<Vec<Entry<'a>>>,
+        to: &RefCell<Vec<Entry<'a>>>,
+        f: impl FnOnce(&mut dyn Command),
+    ) -> bool {
+        assert!(!self.inner.is_applying.get(), "commands cannot be applied recursively");
+
+        let Some(mut entry) = from.borrow_mut().pop() else {
+            return false;
+        };
+        self.apply(|| f(&mut *entry.command));
+        // Typing that was undone is complete.
+        entry.typing = None;
+        to.borrow_mut().push(entry);
+        self.notify();
+
+        true
+    }
+
+    /// Calls a function that applies a command, then updates the tracked editors.
+    fn apply(&self, f: impl FnOnce()) {
+        {
+            let _applying = Applying::new(&self.inner.is_applying);
+            f();
+        }
+
+        for editor in self.inner.editors.borrow().iter() {
+            if let Some(text) = (editor.text)() {
+                *editor.last_text.borrow_mut() = text;
+            }
+        }
+    }
+
+    fn trim(&self) {
+        if let Some(limit) = self.inner.limit.get() {
+            let mut undo = self.inner.undo.borrow_mut();
+            let excess = undo.len().saturating_sub(limit);
+            let forgotten: Vec<_> = undo.drain(..excess).collect();
+            drop(undo);
+            drop(forgotten);
+        }
+    }
+
+    fn notify(&self) {
+        self.update_menu_items();
+        self.inner.observers.notify(|observer| observer());
+    }
+
+    fn update_menu_items(&self) {
+        let (can_undo, can_redo) = (self.can_undo(), self.can_redo());
+        for (undo_item, redo_item) in self.inner.menu_items.borrow().iter() {
+            for (item, is_enabled) in [(undo_item, can_undo), (redo_item, can_redo)] {
+                if is_enabled {
+                    item.enable();
+                } else {
+                    item.disable();
+                }
+            }
+        }
+    }
+}
+
+/// A [`Command`] made of closures.
+struct FnCommand<U, R> {
+    undo: U,
+    redo: R,
+}
+
+impl<U: FnMut(), R: FnMut()> Command for FnCommand<U, R> {
+    fn undo(&mut self) {
+        (self.undo)();
+    }
+
+    fn redo(&mut self) {
+        (self.redo)();
+    }
+}
+
+/// Typing into an editor tracked with [`UndoStack::track`].
+struct TextEdit<'a> {
+    set_text: Rc<dyn 'a + Fn(&str)>,
+    before: String,
+    after: String,
+}
+
+impl Command for TextEdit<'_> {
+    fn undo(&mut self) {
+        (self.set_text)(&self.before);
+    }
+
+    fn redo(&mut self) {
+        (self.set_text)(&self.after);
+    }
+}
+
+/// A widget with a value that may be set with [`UndoStack::set`].
+pub trait Property {
+    /// The type of value displayed by this widget.
+    type Value: Clone + PartialEq;
+
+    /// The current value of this widget.
+    fn value(&self) -> Self::Value;
+
+    /// Sets the value of this widget.
+    fn set_value(&self, value: &Self::Value);
+}
+
+struct SetProperty<'a, W: Property> {
+    widget: &'a W,
+    old: W::Value,
+    new: W::Value,
+}
+
+impl<W: Property> Command for SetProperty<'_, W> {
+    fn undo(&mut self) {
+        self.widget.set_value(&self.old);
+    }
+
+    fn redo(&mut self) {
+        self.widget.set_value(&self.new);
+    }
+}
+
+impl Property for Checkbox<'_> {
+    type Value = bool;
+
+    fn value(&self) -> Self::Value {
+        self.checked()
+    }
+
+    fn set_value(&self, value: &Self::Value) {
+        self.set_checked(*value);
+    }
+}
+
+impl Property for Combobox<'_> {
+    type Value = Option<u16>;
+
+    fn value(&self) -> Self::Value {
+        self.selected_item()
+    }
+
+    fn set_value(&self, value: &Self::Value) {
+        self.select_item(*value);
+    }
+}
+
+impl Property for Slider<'_> {
+    type Value = i32;
+
+    fn value(&self) -> Self::Value {
+        Slider::value(self)
+    }
+
+    fn set_value(&self, value: &Self::Value) {
+        Slider::set_value(self, *value);
+    }
+}
+
+impl Property for Spinbox<'_> {
+    type Value = i32;
+
+    fn value(&self) -> Self::Value {
+        Spinbox::value(self)
+    }
+
+    fn set_value(&self, value: &Self::Value) {
+        Spinbox::set_value(self, *value);
+    }
+}
+
+impl Property for TextEntry<'_> {
+    type Value = String;
+
+    fn value(&self) -> Self::Value {
+        self.text()
+    }
+
+    fn set_value(&self, value: &Self::Value) {
+        warn_on_error(self.set_text(value));
+    }
+}
+
+impl Property for MultilineTextEntry<'_> {
+    type Value = String;
+
+    fn value(&self) -> Self::Value {
+        self.text()
+    }
+
+    fn set_value(&self, value: &Self::Value) {
+        warn_on_error(self.set_text(value));
+    }
+}
+
+/// Marks a stack as applying a command for as long as this lives, even if the command panics.
+struct Applying<'s>(&'s Cell<bool>);
+
+impl<'s> Applying<'s> {
+    fn new(is_applying: &'s Cell<bool>) -> Self {
+        is_applying.set(true);
+
+        Self(is_applying)
+    }
+}
+
+impl Drop for Applying<'_> {
+    fn drop(&mut self) {
+        self.0.set(false);
+    }
+}
+
+/// A text editor whose typing may be recorded with [`UndoStack::track`].
+pub trait Editor<'a> {
+    /// Starts recording typing into this editor.
+    fn track(&self, stack: &UndoStack<'a>);
+}
+
+macro_rules! impl_editor {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl<'a> Editor<'a> for $ty<'a> {
+                fn track(&self, stack: &UndoStack<'a>) {
+                    let control = (**self).as_ptr();
+
+                    let source = stack.clone();
+                    let mut previous = self.take_on_changed();
+                    self.on_changed(move |editor| {
+                        source.record_typing(editor);
+                        if let Some(previous) = previous.as_mut() {
+                            previous(editor);
+                        }
+                    });
+
+                    let ui = self.ui();
+                    let is_alive = Rc::new(Cell::new(true));
+                    ui.registry.with_entry(control, |entry| {
+                        let is_alive = Rc::clone(&is_alive);
+                        entry.on_destroy.push(Box::new(move || is_alive.set(false)));
+                    });
+
+                    let find = {
+                        let is_alive = Rc::clone(&is_alive);
+                        move || {
+                            if !is_alive.get() {
+                                return None;
+                            }
+
+                            ui.handle_of(control)?.downcast_ref::<$ty>()
+                        }
+                    };
+                    let mut editors = stack.inner.editors.borrow_mut();
+                    editors.retain(|tracked| tracked.control != control && tracked.is_alive.get());
+                    editors.push(Tracked {
+                        control,
+                        is_alive,
+                        text: Box::new({
+                            let find = find.clone();
+                            move || Some(find()?.text())
+                        }),
+                        set_text: Rc::new(move |text| {
+                            if let Some(editor) = find() {
+                                warn_on_error(editor.set_text(text));
+                            }
+                        }),
+                        last_text: RefCell::new(self.text()),
+                    });
+                }
+            }
+        )*
+    };
+}
+
+impl_editor!(MultilineTextEntry, TextEntry);
@@ -2,9 +2,9 @@
 
 //! [`Window`].
 
-use std::{os::raw::c_void, ptr};
+use std::{cell::Cell, os::raw::c_void, ptr};
 
-use crate::prelude::*;
+use crate::{locale::Message, prelude::*};
 
 impl Ui {
     /// Creates a new [`Window`].
@@ -23,7 +23,7 @@ impl Ui {
         height: u16,
         has_menubar: bool,
         should_quit_on_close: bool,
-    ) -> Result<&mut Window, crate::Error> {
+    ) -> Result<&Window, crate::Error> {
         let title = make_cstring!(title.as_ref());
         let window = call_libui_new_fn!(
             ui: self,
@@ -35,6 +35,7 @@ impl Ui {
             ) -> Window,
         )?;
 
+        window.should_quit_on_close.set(should_quit_on_close);
         if should_quit_on_close {
             unsafe extern "C" fn on_closing(_: *mut uiWindow, _: *mut c_void) -> i32 {
                 // When the window recieves an event to close, call `uiQuit`.
@@ -90,11 +91,15 @@ def_subcontrol!(
         ```
     ",
     ty: Window,
+    kind: Window,
     handle: uiWindow,
     cb_fns: [
         on_content_size_changed(),
         on_closing(),
     ],
+    fields: [
+        should_quit_on_close: Cell<bool> = Cell::new(false),
+    ],
 );
 
 impl<'ui> Window<'ui> {
@@ -130,6 +135,25 @@ impl<'ui> Window<'ui> {
         libui: { fn: uiWindowSetTitle() },
     );
 
+    /// Sets the title of this window to the given message, translating it again whenever the
+    /// locale changes.
+    ///
+    /// See [`Ui::localize`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn set_localized_title(&self, message: impl Into<Message>) -> Result<(), crate::Error> {
+        self.ui.localize(self, message)
+    }
+
+    /// The [`Ui`] that created this window.
+    pub(crate) fn ui(&self) -> &'ui Ui {
+        self.ui
+    }
+
     bind_callback_fn!(
         docs: "
             Sets a callback for when the content size of this window changes.
@@ -144,6 +168,7 @@ impl<'ui> Window<'ui> {
             ty: Window<'ui>,
             handle: uiWindow,
             fn: on_content_size_changed(),
+            emit: emit_content_size_changed(),
             cb: { sig: f -> () },
         },
         libui: {
@@ -152,6 +177,19 @@ impl<'ui> Window<'ui> {
         },
     );
 
+    /// Simulates a resize, setting the content size of this window and then calling the callback
+    /// set by [`Window::on_content_size_changed`] as though the user had resized it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn simulate_resize(&self, width: u16, height: u16) {
+        self.set_content_size(width, height);
+        self.emit_content_size_changed();
+    }
+
     bind_callback_fn!(
         docs: "
             Sets a callback for when this window is requested to close.
@@ -166,6 +204,7 @@ impl<'ui> Window<'ui> {
             ty: Window<'ui>,
             handle: uiWindow,
             fn: on_closing(),
+            emit: emit_closing(),
             cb: {
                 sig: f -> (),
                 map: |_| {
@@ -181,6 +220,26 @@ impl<'ui> Window<'ui> {
         },
     );
 
+    /// Simulates a request to close this window, as though the user had clicked its close button.
+    ///
+    /// The callback set by [`Window::on_closing`] is called if there is one. Otherwise, the window
+    /// behaves as configured by `should_quit_on_close` in [`Ui::create_window`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // TODO
+    /// ```
+    pub fn simulate_close(&self) {
+        if self.emit_closing().is_none() {
+            if self.should_quit_on_close.get() {
+                unsafe { uiQuit() };
+            } else {
+                self.hide();
+            }
+        }
+    }
+
     bind_bool_fn!(
         docs: "
             Determines if this window is fullscreen.